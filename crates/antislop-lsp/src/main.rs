@@ -76,22 +76,15 @@ impl Backend {
             .findings
             .iter()
             .map(|f| {
-                // Find start and end column (antislop uses 1-based indexing, LSP uses 0-based)
-                let start_line = (f.line).saturating_sub(1) as u32;
-                let start_col = (f.column).saturating_sub(1) as u32;
-                let end_col = start_col + f.match_text.chars().count() as u32; // basic char count approximation
+                let start = lsp_position(&text, f.line, f.column);
+                // Findings with no source match, such as filename ones,
+                // get an empty range
+                let end = f.span.map_or(start, |span| {
+                    lsp_position(&text, span.end_line, span.end_column)
+                });
 
                 Diagnostic {
-                    range: Range {
-                        start: Position {
-                            line: start_line,
-                            character: start_col,
-                        },
-                        end: Position {
-                            line: start_line,
-                            character: end_col,
-                        },
-                    },
+                    range: Range { start, end },
                     severity: Some(match f.severity.as_str() {
                         "CRITICAL" => DiagnosticSeverity::ERROR,
                         "HIGH" => DiagnosticSeverity::ERROR,
@@ -99,9 +92,7 @@ impl Backend {
                         "LOW" => DiagnosticSeverity::INFORMATION,
                         _ => DiagnosticSeverity::HINT,
                     }),
-                    code: Some(NumberOrString::String(
-                        format!("{:?}", f.category).to_lowercase(),
-                    )),
+                    code: Some(NumberOrString::String(f.category.to_string())),
                     source: Some("antislop".to_string()),
                     message: f.message.clone(),
                     ..Default::default()
@@ -123,3 +114,35 @@ async fn main() {
     let (service, socket) = LspService::new(|client| Backend { client });
     Server::new(stdin, stdout, socket).serve(service).await;
 }
+
+/// Convert a 1-indexed line and byte column into an LSP position, whose
+/// character offset counts UTF-16 code units.
+fn lsp_position(text: &str, line: usize, column: usize) -> Position {
+    let source_line = text.lines().nth(line.saturating_sub(1)).unwrap_or("");
+    let mut byte = column.saturating_sub(1).min(source_line.len());
+    while !source_line.is_char_boundary(byte) {
+        byte -= 1;
+    }
+    Position {
+        line: line.saturating_sub(1) as u32,
+        character: source_line[..byte].encode_utf16().count() as u32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lsp_position_counts_utf16_units() {
+        let text = "x = 1\ny = \"😀\"  # TODO: fix\n";
+        // Byte column 15 is the "T" after a 4-byte emoji (2 UTF-16 units)
+        assert_eq!(
+            lsp_position(text, 2, 15),
+            Position {
+                line: 1,
+                character: 12,
+            }
+        );
+    }
+}
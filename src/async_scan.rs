@@ -0,0 +1,88 @@
+//! Async scanning API for embedding antislop in async services.
+//!
+//! Files are read with `tokio::fs` and the CPU-bound scan runs on the
+//! blocking thread pool, reusing the synchronous [`Scanner`].
+
+use crate::detector::{FileScanResult, Finding, ScanSummary, Scanner};
+use crate::{Config, Error, Result, Walker};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::task::JoinSet;
+
+/// Scan the given paths without blocking the async runtime.
+///
+/// Unreadable files are skipped with a warning, matching the CLI. Results
+/// are sorted by file path so output is deterministic.
+pub async fn scan_paths_async(
+    scanner: Arc<Scanner>,
+    config: &Config,
+    paths: Vec<PathBuf>,
+) -> Result<(Vec<Finding>, ScanSummary)> {
+    let walker = Walker::new(config);
+    let entries = tokio::task::spawn_blocking(move || walker.walk(&paths))
+        .await
+        .map_err(join_error)?;
+
+    let mut tasks = JoinSet::new();
+    for entry in entries {
+        let scanner = Arc::clone(&scanner);
+        tasks.spawn(async move {
+            let path = entry.path.to_string_lossy().to_string();
+            let bytes = tokio::fs::read(&entry.path).await;
+            let content = match bytes.and_then(crate::walker::decode_text) {
+                Ok(Some(c)) => c,
+                Ok(None) => {
+                    tracing::debug!("Skipping binary file '{}'", path);
+                    return Ok(None);
+                }
+                Err(e) => {
+                    tracing::warn!("Error reading file '{}': {}", path, e);
+                    return Ok(None);
+                }
+            };
+            tokio::task::spawn_blocking(move || Some(scanner.scan_file(&path, &content))).await
+        });
+    }
+
+    let mut results: Vec<FileScanResult> = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        if let Some(result) = joined.map_err(join_error)?.map_err(join_error)? {
+            results.push(result);
+        }
+    }
+    results.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let summary = ScanSummary::new(&results);
+    let findings = results.into_iter().flat_map(|r| r.findings).collect();
+    Ok((findings, summary))
+}
+
+fn join_error(e: tokio::task::JoinError) -> Error {
+    Error::Io(std::io::Error::other(e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_scan_paths_async() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("a.py"), "# TODO: implement\nx = 1\n").unwrap();
+        fs::write(temp.path().join("b.py"), "x = 2\n").unwrap();
+
+        let config = Config::default();
+        let scanner = Arc::new(Scanner::new(config.patterns.clone()).unwrap());
+        let (findings, summary) =
+            scan_paths_async(scanner, &config, vec![temp.path().to_path_buf()])
+                .await
+                .unwrap();
+
+        assert_eq!(summary.files_scanned, 2);
+        assert_eq!(summary.files_with_findings, 1);
+        assert!(!findings.is_empty());
+        assert!(findings.iter().all(|f| f.file.ends_with("a.py")));
+    }
+}
@@ -0,0 +1,444 @@
+//! Baselines of accepted findings.
+//!
+//! A baseline records findings that already exist so that later scans only
+//! report new ones. Entries are matched on file, category, pattern, and the
+//! trimmed source line rather than line numbers, so unrelated edits that
+//! shift lines do not resurface baselined findings.
+//!
+//! Baselines are stored as JSON, or as TOML grouped by file when the path
+//! ends in `.toml`, which reads better in code review.
+
+use crate::config::PatternCategory;
+use crate::detector::{FileScanResult, Finding};
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+/// A single accepted finding.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// File path, as reported in findings.
+    pub file: String,
+    /// Pattern category.
+    pub category: PatternCategory,
+    /// The regex pattern that matched.
+    pub pattern_regex: String,
+    /// The matched text.
+    pub match_text: String,
+    /// The trimmed source line containing the finding.
+    #[serde(default)]
+    pub source_line: String,
+}
+
+impl BaselineEntry {
+    /// Build the baseline entry that would suppress `finding`.
+    pub fn from_finding(finding: &Finding) -> Self {
+        Self {
+            file: finding.file.clone(),
+            category: finding.category.clone(),
+            pattern_regex: finding.pattern_regex.clone(),
+            match_text: finding.match_text.clone(),
+            source_line: finding
+                .source_line
+                .as_deref()
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
+        }
+    }
+}
+
+/// On-disk format of a baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum BaselineFormat {
+    /// A flat JSON list of entries.
+    Json,
+    /// TOML with entries grouped under their file.
+    Toml,
+}
+
+impl BaselineFormat {
+    /// TOML for `.toml` paths, JSON otherwise.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => Self::Toml,
+            _ => Self::Json,
+        }
+    }
+}
+
+/// TOML form of a baseline: entries keyed by file, in path order.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TomlBaseline {
+    #[serde(default)]
+    files: BTreeMap<String, Vec<TomlEntry>>,
+}
+
+/// A [`BaselineEntry`] without its file, which is the TOML table key.
+#[derive(Debug, Serialize, Deserialize)]
+struct TomlEntry {
+    category: PatternCategory,
+    pattern_regex: String,
+    match_text: String,
+    #[serde(default)]
+    source_line: String,
+}
+
+impl From<&Baseline> for TomlBaseline {
+    fn from(baseline: &Baseline) -> Self {
+        let mut files: BTreeMap<String, Vec<TomlEntry>> = BTreeMap::new();
+        for entry in &baseline.entries {
+            files
+                .entry(entry.file.clone())
+                .or_default()
+                .push(TomlEntry {
+                    category: entry.category.clone(),
+                    pattern_regex: entry.pattern_regex.clone(),
+                    match_text: entry.match_text.clone(),
+                    source_line: entry.source_line.clone(),
+                });
+        }
+        Self { files }
+    }
+}
+
+impl From<TomlBaseline> for Baseline {
+    fn from(toml: TomlBaseline) -> Self {
+        let entries = toml
+            .files
+            .into_iter()
+            .flat_map(|(file, entries)| {
+                entries.into_iter().map(move |entry| BaselineEntry {
+                    file: file.clone(),
+                    category: entry.category,
+                    pattern_regex: entry.pattern_regex,
+                    match_text: entry.match_text,
+                    source_line: entry.source_line,
+                })
+            })
+            .collect();
+        Self { entries }
+    }
+}
+
+/// A set of accepted findings, stored as JSON or TOML.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    /// Accepted findings; duplicates are allowed and matched one-for-one.
+    pub entries: Vec<BaselineEntry>,
+}
+
+impl Baseline {
+    /// Build a baseline accepting all of the given findings.
+    pub fn from_findings(findings: &[Finding]) -> Self {
+        Self {
+            entries: findings.iter().map(BaselineEntry::from_finding).collect(),
+        }
+    }
+
+    /// Load a baseline, choosing the format from the file extension.
+    pub fn load(path: &Path) -> Result<Self> {
+        Self::load_as(path, BaselineFormat::from_path(path))
+    }
+
+    /// Load a baseline stored in the given format.
+    pub fn load_as(path: &Path, format: BaselineFormat) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            Error::ConfigInvalid(format!(
+                "Failed to read baseline '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let invalid = |e: &dyn std::fmt::Display| {
+            Error::ConfigInvalid(format!("Invalid baseline '{}': {}", path.display(), e))
+        };
+        match format {
+            BaselineFormat::Json => serde_json::from_str(&content).map_err(|e| invalid(&e)),
+            BaselineFormat::Toml => toml::from_str::<TomlBaseline>(&content)
+                .map(Self::from)
+                .map_err(|e| invalid(&e)),
+        }
+    }
+
+    /// Save the baseline, choosing the format from the file extension.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        self.save_as(path, BaselineFormat::from_path(path))
+    }
+
+    /// Save the baseline in the given format.
+    pub fn save_as(&self, path: &Path, format: BaselineFormat) -> Result<()> {
+        let content = match format {
+            BaselineFormat::Json => {
+                serde_json::to_string_pretty(self)
+                    .map_err(|e| Error::Serialization(e.to_string()))?
+                    + "\n"
+            }
+            BaselineFormat::Toml => toml::to_string(&TomlBaseline::from(self))
+                .map_err(|e| Error::Serialization(e.to_string()))?,
+        };
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Start matching findings against this baseline.
+    pub fn matcher(&self) -> BaselineMatcher<'_> {
+        let mut remaining = HashMap::new();
+        for entry in &self.entries {
+            *remaining.entry(entry).or_insert(0) += 1;
+        }
+        BaselineMatcher {
+            remaining,
+            scanned: HashSet::new(),
+            still_present: 0,
+        }
+    }
+
+    /// Returns a copy without the given resolved entries.
+    pub fn without(&self, resolved: &[BaselineEntry]) -> Self {
+        let mut drop: HashMap<&BaselineEntry, usize> = HashMap::new();
+        for entry in resolved {
+            *drop.entry(entry).or_insert(0) += 1;
+        }
+        let entries = self
+            .entries
+            .iter()
+            .filter(|entry| match drop.get_mut(entry) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            })
+            .cloned()
+            .collect();
+        Self { entries }
+    }
+}
+
+/// Suppresses baselined findings and tracks which entries were seen.
+#[derive(Debug)]
+pub struct BaselineMatcher<'a> {
+    remaining: HashMap<&'a BaselineEntry, usize>,
+    scanned: HashSet<String>,
+    still_present: usize,
+}
+
+impl BaselineMatcher<'_> {
+    /// Remove baselined findings from a scanned file and recompute its score.
+    pub fn apply(&mut self, result: &mut FileScanResult) {
+        self.scanned.insert(result.path.clone());
+        self.suppress(&mut result.findings);
+        result.score = result.findings.iter().map(Finding::score).sum();
+    }
+
+    /// Remove baselined findings from a list of findings.
+    pub fn suppress(&mut self, findings: &mut Vec<Finding>) {
+        findings.retain(|finding| {
+            let entry = BaselineEntry::from_finding(finding);
+            match self.remaining.get_mut(&entry) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    self.still_present += 1;
+                    false
+                }
+                _ => true,
+            }
+        });
+    }
+
+    /// Entries for scanned files that matched no finding.
+    ///
+    /// Entries for files outside this scan are never reported as resolved.
+    pub fn resolved(&self) -> Vec<BaselineEntry> {
+        let mut resolved: Vec<BaselineEntry> = self
+            .remaining
+            .iter()
+            .filter(|(entry, _)| self.scanned.contains(&entry.file))
+            .flat_map(|(entry, &count)| std::iter::repeat_n((*entry).clone(), count))
+            .collect();
+        resolved.sort_by(|a, b| (&a.file, &a.source_line).cmp(&(&b.file, &b.source_line)));
+        resolved
+    }
+
+    /// How the findings seen so far compare with the baseline. Findings
+    /// left after suppression are the new ones.
+    pub fn diff(&self) -> BaselineDiff {
+        BaselineDiff {
+            still_present: self.still_present,
+            resolved: self.resolved(),
+        }
+    }
+}
+
+/// Baselined findings still present and resolved in a scan, for reporting
+/// alongside the new findings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BaselineDiff {
+    /// Baselined findings that were found again and suppressed.
+    pub still_present: usize,
+    /// Entries for scanned files that no longer match any finding.
+    pub resolved: Vec<BaselineEntry>,
+}
+
+/// Returns the age of the baseline file if it is older than `max_age`.
+pub fn stale_age(path: &Path, max_age: Duration) -> Option<Duration> {
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    let age = SystemTime::now().duration_since(modified).ok()?;
+    (age > max_age).then_some(age)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Severity;
+
+    fn finding(file: &str, line: usize, text: &str) -> Finding {
+        Finding {
+            file: file.to_string(),
+            line,
+            column: 3,
+            severity: Severity::Medium,
+            category: PatternCategory::Placeholder,
+            message: "TODO".to_string(),
+            match_text: "TODO:".to_string(),
+            pattern_regex: "(?i)TODO:".to_string(),
+            source_line: Some(text.to_string()),
+            context_before: None,
+            context_after: None,
+            ..Default::default()
+        }
+    }
+
+    fn result(path: &str, findings: Vec<Finding>) -> FileScanResult {
+        let score = findings.iter().map(Finding::score).sum();
+        FileScanResult {
+            path: path.to_string(),
+            findings,
+            score,
+            lines: 0,
+        }
+    }
+
+    #[test]
+    fn test_baseline_suppresses_known_findings() {
+        let baseline = Baseline::from_findings(&[finding("a.py", 1, "# TODO: old")]);
+        let mut matcher = baseline.matcher();
+
+        // Same finding on a shifted line is still suppressed; the new one is kept
+        let mut scanned = result(
+            "a.py",
+            vec![
+                finding("a.py", 5, "# TODO: old"),
+                finding("a.py", 6, "# TODO: new"),
+            ],
+        );
+        matcher.apply(&mut scanned);
+        assert_eq!(scanned.findings.len(), 1);
+        assert_eq!(scanned.findings[0].line, 6);
+        assert_eq!(scanned.score, Severity::Medium.score());
+        assert!(matcher.resolved().is_empty());
+    }
+
+    #[test]
+    fn test_baseline_diff_separates_new_resolved_and_present() {
+        let baseline = Baseline::from_findings(&[
+            finding("a.py", 1, "# TODO: still here"),
+            finding("a.py", 2, "# TODO: fixed since"),
+        ]);
+        let mut matcher = baseline.matcher();
+        let mut scanned = result(
+            "a.py",
+            vec![
+                finding("a.py", 1, "# TODO: still here"),
+                finding("a.py", 3, "# TODO: brand new"),
+            ],
+        );
+        matcher.apply(&mut scanned);
+
+        let new: Vec<&str> = scanned
+            .findings
+            .iter()
+            .filter_map(|f| f.source_line.as_deref())
+            .collect();
+        assert_eq!(new, vec!["# TODO: brand new"]);
+        let diff = matcher.diff();
+        assert_eq!(diff.still_present, 1);
+        let resolved: Vec<&str> = diff
+            .resolved
+            .iter()
+            .map(|e| e.source_line.as_str())
+            .collect();
+        assert_eq!(resolved, vec!["# TODO: fixed since"]);
+    }
+
+    #[test]
+    fn test_baseline_detects_resolved_entries() {
+        let baseline = Baseline::from_findings(&[
+            finding("a.py", 1, "# TODO: fixed since"),
+            finding("a.py", 2, "# TODO: still here"),
+            finding("unscanned.py", 1, "# TODO: elsewhere"),
+        ]);
+        let mut matcher = baseline.matcher();
+        matcher.apply(&mut result(
+            "a.py",
+            vec![finding("a.py", 2, "# TODO: still here")],
+        ));
+
+        let resolved = matcher.resolved();
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].source_line, "# TODO: fixed since");
+
+        let refreshed = baseline.without(&resolved);
+        assert_eq!(refreshed.entries.len(), 2);
+        assert!(!refreshed.entries.contains(&resolved[0]));
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("baseline.json");
+        let baseline = Baseline::from_findings(&[finding("a.py", 1, "# TODO: x")]);
+        baseline.save(&path).unwrap();
+        assert_eq!(Baseline::load(&path).unwrap(), baseline);
+        assert!(stale_age(&path, Duration::from_secs(3600)).is_none());
+    }
+
+    #[test]
+    fn test_baseline_formats_filter_alike() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let baseline = Baseline::from_findings(&[
+            finding("b.py", 1, "# TODO: b"),
+            finding("a.py", 1, "# TODO: one"),
+            finding("a.py", 2, "# TODO: two"),
+        ]);
+
+        let toml_path = temp.path().join(".antislop-baseline.toml");
+        baseline.save(&toml_path).unwrap();
+        let toml = fs::read_to_string(&toml_path).unwrap();
+        assert_eq!(toml.matches("[[files.\"a.py\"]]").count(), 2);
+        assert!(toml.find("a.py").unwrap() < toml.find("b.py").unwrap());
+
+        let json_path = temp.path().join("baseline.json");
+        baseline.save(&json_path).unwrap();
+
+        for path in [&toml_path, &json_path] {
+            let loaded = Baseline::load(path).unwrap();
+            let mut scanned = result(
+                "a.py",
+                vec![
+                    finding("a.py", 3, "# TODO: two"),
+                    finding("a.py", 4, "# TODO: new"),
+                ],
+            );
+            let mut matcher = loaded.matcher();
+            matcher.apply(&mut scanned);
+            assert_eq!(scanned.findings.len(), 1, "{}", path.display());
+            assert_eq!(scanned.findings[0].line, 4);
+            assert_eq!(matcher.resolved().len(), 1);
+        }
+    }
+}
@@ -2,16 +2,26 @@
 //!
 //! A blazing-fast, multi-language linter for detecting AI-generated code slop.
 
+use antislop::baseline::{Baseline, BaselineDiff, BaselineFormat};
+use antislop::config::RegexPattern;
+use antislop::detector::Language;
+use antislop::report::SarifWriter;
 use antislop::{
-    Config, FilenameCheckConfig, FilenameChecker, Format, Profile, ProfileLoader, ProfileSource,
-    Reporter, Scanner, Walker, CONFIG_FILES, VERSION,
+    Config, DocstringChecker, FilenameCheckConfig, FilenameChecker, Format, Pattern, Profile,
+    ProfileLoader, ProfileSource, Reporter, Scanner, Severity, SummaryDetail, Walker, CONFIG_FILES,
+    VERSION,
 };
-use anyhow::{Context, Result};
-use clap::{CommandFactory, Parser};
+use anyhow::{bail, Context, Result};
+use clap::{CommandFactory, Parser, Subcommand};
 use clap_complete::{generate, Shell};
+use indicatif::{ProgressBar, ProgressStyle};
+use std::collections::HashMap;
 use std::fs;
-use std::io;
-use std::path::PathBuf;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+
+/// Minimum number of files before a progress bar is shown.
+const PROGRESS_THRESHOLD: usize = 200;
 
 /// AntiSlop - A blazing-fast linter for detecting AI-generated code slop.
 #[derive(Parser, Debug)]
@@ -25,6 +35,10 @@ struct Args {
     #[arg(value_name = "PATH", default_value = ".")]
     paths: Vec<PathBuf>,
 
+    /// Read newline-delimited paths to scan from FILE ('-' for stdin)
+    #[arg(long, value_name = "FILE")]
+    files_from: Option<PathBuf>,
+
     /// Path to config file
     #[arg(short, long, value_name = "FILE", global = false)]
     config: Option<PathBuf>,
@@ -37,6 +51,10 @@ struct Args {
     #[arg(short, long, default_value = "1024", global = false)]
     max_size: u64,
 
+    /// Maximum directory depth to walk (default: unlimited)
+    #[arg(long, value_name = "N")]
+    max_depth: Option<usize>,
+
     /// File extensions to scan (comma-separated)
     #[arg(short, long, value_delimiter = ',')]
     extensions: Option<Vec<String>>,
@@ -53,10 +71,14 @@ struct Args {
     #[arg(long)]
     list_languages: bool,
 
-    /// Output format (human, json, sarif)
+    /// Output format (human, json, sarif, sqlite)
     #[arg(long, value_name = "FORMAT")]
     format: Option<String>,
 
+    /// Database file that --format sqlite appends the run to (sqlite only)
+    #[arg(long, value_name = "FILE")]
+    output: Option<PathBuf>,
+
     /// Print default configuration
     #[arg(long)]
     print_config: bool,
@@ -65,25 +87,230 @@ struct Args {
     #[arg(long)]
     no_filename_check: bool,
 
-    /// Load a community profile (file path, URL, or profile name)
+    /// Severity of filename convention findings (low, medium, high, critical)
+    #[arg(long, value_name = "LEVEL", value_parser = parse_severity)]
+    filename_severity: Option<Severity>,
+
+    /// Load a community profile (file path, URL, or profile name); repeatable
     #[arg(long, value_name = "PROFILE")]
-    profile: Option<String>,
+    profile: Vec<String>,
 
     /// Print available profiles
     #[arg(long)]
     list_profiles: bool,
 
-    /// Disable pattern categories (comma-separated: placeholder,stub,deferral,hedging)
-    #[arg(long, value_delimiter = ',', value_name = "CATEGORIES")]
-    disable: Option<Vec<String>>,
-
-    /// Only enable specific categories (comma-separated: placeholder,stub,deferral,hedging)
-    #[arg(long, value_delimiter = ',', value_name = "CATEGORIES")]
-    only: Option<Vec<String>>,
+    /// Print the resolved pattern set with each pattern's source (TOML, or
+    /// JSON with --json) after profile merging and category filters, then exit
+    #[arg(long)]
+    dump_patterns: bool,
+
+    /// Disable pattern categories (comma-separated)
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "CATEGORIES",
+        ignore_case = true
+    )]
+    disable: Option<Vec<antislop::PatternCategory>>,
+
+    /// Only enable specific categories (comma-separated)
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "CATEGORIES",
+        ignore_case = true
+    )]
+    only: Option<Vec<antislop::PatternCategory>>,
+
+    /// Disable the pattern with this id (its regex, as shown by
+    /// --show-pattern); repeatable
+    #[arg(long, value_name = "ID")]
+    disable_pattern: Vec<String>,
+
+    /// Enable a pattern that the config marks `enabled = false`; repeatable
+    #[arg(long, value_name = "ID")]
+    enable_pattern: Vec<String>,
 
     /// Run a code hygiene survey (detect project types, suggest linters/formatters)
     #[arg(long)]
     hygiene_survey: bool,
+
+    /// Extra hygiene tool definitions merged over the built-in catalog
+    /// (default: .antislop/hygiene_tools.toml if present)
+    #[arg(long, value_name = "FILE")]
+    hygiene_tools: Option<PathBuf>,
+
+    /// Exit non-zero if the hygiene survey finds no tooling of this kind
+    /// for a detected project (repeatable)
+    #[arg(long, value_enum, value_name = "KIND")]
+    hygiene_require: Vec<antislop::hygiene::HygieneRequirement>,
+
+    /// Group human-readable findings by directory with per-directory subtotals
+    #[arg(long)]
+    group_by_dir: bool,
+
+    /// Print each distinct (category, message) once with a list of its locations
+    #[arg(long)]
+    collapse: bool,
+
+    /// Flag comments that contradict the function they precede (AST languages only)
+    #[arg(long)]
+    detect_stale_comments: bool,
+
+    /// Always exit 0, even when findings are reported (reporting-only runs)
+    #[arg(long)]
+    exit_zero: bool,
+
+    /// Reject profiles whose patterns overlap with linters or formatters
+    #[arg(long)]
+    strict_profiles: bool,
+
+    /// Add an ad-hoc pattern as 'regex:severity:category:message'; repeatable
+    #[arg(long = "pattern", value_name = "SPEC")]
+    patterns: Vec<String>,
+
+    /// Report a category at a fixed SARIF level and --fail-fast severity as
+    /// 'category=level', where level is a severity or a SARIF level (note,
+    /// warning, error); repeatable
+    #[arg(long = "severity-map", value_name = "CATEGORY=LEVEL")]
+    severity_map: Vec<String>,
+
+    /// Force a language for an extension as 'ext=lang' (e.g. h=cpp); repeatable
+    #[arg(long = "force-language", value_name = "EXT=LANG")]
+    force_languages: Vec<String>,
+
+    /// List the N sloppiest files and the clean-file count in the summary
+    #[arg(long, value_name = "N")]
+    top_files: Option<usize>,
+
+    /// How much of the summary footer to print: minimal (files, findings,
+    /// score and verdict) or full (adds rates and breakdowns)
+    #[arg(long, value_enum, value_name = "LEVEL", default_value = "full")]
+    summary_detail: SummaryDetail,
+
+    /// Print findings only, without the summary footer or JSON summary object
+    #[arg(long)]
+    no_summary: bool,
+
+    /// Start from an empty pattern set; scan only with --profile/--pattern patterns
+    #[arg(long)]
+    no_default_patterns: bool,
+
+    /// Also scan documentation (.md, .txt, .rst), matching comment patterns against the prose
+    #[arg(long, alias = "scan-text")]
+    scan_docs: bool,
+
+    /// Stop at the first finding of at least LEVEL (default: any), print only
+    /// that finding and exit non-zero; a scan with none reports as usual
+    #[arg(
+        long,
+        value_name = "LEVEL",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "low",
+        value_parser = parse_severity,
+        conflicts_with = "baseline"
+    )]
+    fail_fast: Option<Severity>,
+
+    /// Show the regex of the pattern behind each finding (human and JSON output)
+    #[arg(long)]
+    show_pattern: bool,
+
+    /// Show the nearest non-blank lines as context instead of the adjacent ones
+    #[arg(long)]
+    skip_blank_context: bool,
+
+    /// Override a severity's score for this run (e.g. critical=100,high=20)
+    #[arg(
+        long = "weight",
+        value_name = "SEVERITY=N",
+        value_delimiter = ',',
+        value_parser = parse_weight
+    )]
+    weights: Vec<(Severity, u32)>,
+
+    /// Scan files that look generated or minified instead of skipping them
+    #[arg(long)]
+    include_generated: bool,
+
+    /// Disable the progress bar shown for large scans
+    #[arg(long)]
+    no_progress: bool,
+
+    /// Suppress findings recorded in this baseline file (JSON, or TOML for .toml paths)
+    #[arg(long, value_name = "FILE")]
+    baseline: Option<PathBuf>,
+
+    /// Step through findings in a terminal UI, acknowledging each into the
+    /// baseline, suppressing it with an ignore comment, or skipping it
+    #[arg(long, requires = "baseline", conflicts_with = "fail_fast")]
+    review: bool,
+
+    /// Baseline file format, overriding the one implied by its extension
+    #[arg(long, value_enum, value_name = "FORMAT", requires = "baseline")]
+    baseline_format: Option<BaselineFormat>,
+
+    /// Report findings as new since the baseline in their own section, and
+    /// list baselined findings that are now resolved
+    #[arg(long, alias = "baseline-diff", requires = "baseline")]
+    show_new: bool,
+
+    /// Rewrite the baseline, dropping resolved entries (creates it if missing)
+    #[arg(long, requires = "baseline")]
+    refresh_baseline: bool,
+
+    /// Only scan files modified within DURATION (e.g. 30m, 24h, 7d)
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+    since: Option<std::time::Duration>,
+
+    /// Warn when the baseline is older than DAYS (overrides baseline_max_age)
+    #[arg(long, value_name = "DAYS")]
+    baseline_age: Option<u64>,
+
+    /// Print a key=value summary line to stderr (e.g. files=120 findings=7 score=35)
+    #[arg(long)]
+    parseable_summary: bool,
+
+    /// Include per-pattern hit counts in JSON output, including patterns that never matched
+    #[arg(long)]
+    pattern_stats: bool,
+
+    /// Print only the paths of files with at least one finding, one per line
+    #[arg(long, alias = "only-files-with-findings")]
+    list_findings_files: bool,
+
+    /// Attribute each finding to the commit that introduced it via git blame
+    #[arg(long)]
+    blame: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Manage community profiles
+    Profile {
+        #[command(subcommand)]
+        action: ProfileCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ProfileCommand {
+    /// Remove cached remote profiles older than the given age
+    Prune {
+        /// Maximum age of cached profiles to keep, in hours
+        #[arg(long, value_name = "HOURS", default_value = "24")]
+        max_age: u64,
+    },
+    /// Print the SHA-256 checksum of a profile's patterns, for its
+    /// `metadata.checksum` field
+    Checksum {
+        /// Profile TOML file
+        file: PathBuf,
+    },
 }
 
 fn main() -> Result<()> {
@@ -100,7 +327,7 @@ fn main() -> Result<()> {
     }
 
     if args.list_profiles {
-        print_profiles()?;
+        print_profiles(args.json || args.format.as_deref() == Some("json"))?;
         return Ok(());
     }
 
@@ -109,90 +336,236 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    if let Some(command) = args.command {
+        return run_command(command);
+    }
+
+    // Other formats print to stdout; don't let the file silently go unwritten
+    if args.output.is_some() && args.format.as_deref() != Some("sqlite") {
+        bail!("--output is only supported with --format sqlite");
+    }
+
     // Run hygiene survey if requested
     if args.hygiene_survey {
-        let survey = antislop::hygiene::run_survey(&args.paths);
-        antislop::hygiene::print_report(&survey);
+        let survey =
+            antislop::hygiene::run_survey_with_tools(&args.paths, args.hygiene_tools.as_deref())?;
+        if args.json || args.format.as_deref() == Some("json") {
+            println!("{}", serde_json::to_string_pretty(&survey)?);
+        } else {
+            antislop::hygiene::print_report(&survey);
+        }
+        let unmet = survey.unmet_requirements(&args.hygiene_require);
+        for requirement in &unmet {
+            eprintln!("error: no {} configured", requirement.description());
+        }
+        if !unmet.is_empty() && !args.exit_zero {
+            std::process::exit(1);
+        }
         return Ok(());
     }
 
     init_tracing(args.verbose);
 
-    let mut config = load_config(&args.config)?;
+    let (mut config, config_path) = load_config(&args.config)?;
+    // Where each entry of config.patterns came from, for --dump-patterns
+    let config_source = match &config_path {
+        Some(path) => format!("config:{}", path.display()),
+        None => "core".to_string(),
+    };
+    let mut pattern_sources = vec![config_source; config.patterns.len()];
 
-    if let Some(extensions) = args.extensions {
-        config.file_extensions = extensions;
+    if let Some(ref extensions) = args.extensions {
+        config.file_extensions = extensions.clone();
+    } else {
+        // Extensions mapped to a language are scanned without listing them
+        for ext in config.extension_languages.keys() {
+            let ext = format!(".{}", ext.trim_start_matches('.'));
+            if !config.file_extensions.contains(&ext) {
+                config.file_extensions.push(ext);
+            }
+        }
+    }
+    if args.scan_docs {
+        for ext in Language::Text.extensions() {
+            let ext = format!(".{}", ext);
+            if !config.file_extensions.contains(&ext) {
+                config.file_extensions.push(ext);
+            }
+        }
     }
     config.max_file_size_kb = args.max_size;
+    if args.include_generated {
+        config.skip_generated = false;
+    }
+    if args.max_depth.is_some() {
+        config.max_depth = args.max_depth;
+    }
 
     config
         .validate_patterns()
         .context("Invalid pattern in configuration")?;
 
-    // Load and merge profile if specified
-    if let Some(ref profile_source) = args.profile {
-        let profile = load_profile(profile_source)?;
+    if args.no_default_patterns {
+        config.patterns.clear();
+        pattern_sources.clear();
+    }
+
+    // Load and merge profiles if specified
+    if !args.profile.is_empty() {
+        let (profile, profile_names) =
+            load_profile(&args.profile, args.verbose, args.strict_profiles)?;
         let pattern_count = profile.patterns.len();
-        let profile_name = profile.metadata.name.clone();
-        let profile_version = profile.metadata.version.clone();
+        if profile.magic_numbers.is_some() {
+            config.magic_numbers = profile.magic_numbers.clone();
+        }
 
         // Merge profile patterns with config patterns
         for pattern in profile.patterns {
+            let name = profile_names
+                .get(&(pattern.regex.to_string(), pattern.category.clone()))
+                .map_or("", String::as_str);
+            pattern_sources.push(format!("profile:{}", name));
             config.patterns.push(pattern);
         }
-        if args.verbose >= 1 {
-            eprintln!("Loaded profile: {} (v{})", profile_name, profile_version);
-            eprintln!("  {} patterns from profile", pattern_count);
+        if args.verbose >= 1 && args.profile.len() > 1 {
+            eprintln!("  {} patterns from merged profiles", pattern_count);
         }
     }
 
+    // Append ad-hoc patterns from --pattern
+    for spec in &args.patterns {
+        let pattern =
+            parse_pattern_spec(spec).with_context(|| format!("Invalid --pattern '{}'", spec))?;
+        config.patterns.push(pattern);
+        pattern_sources.push("inline".to_string());
+    }
+
+    // Toggle individual patterns (--enable-pattern and --disable-pattern)
+    for (ids, enabled) in [(&args.enable_pattern, true), (&args.disable_pattern, false)] {
+        for id in ids {
+            let mut found = false;
+            for pattern in config
+                .patterns
+                .iter_mut()
+                .filter(|p| &*p.regex == id.as_str())
+            {
+                pattern.enabled = enabled;
+                found = true;
+            }
+            if !found {
+                eprintln!("Warning: no pattern with id '{}'", id);
+            }
+        }
+    }
+    // Drop disabled patterns so --dump-patterns and the filename checker
+    // see the same set as the scanner
+    retain_patterns(&mut config.patterns, &mut pattern_sources, |p| p.enabled);
+
     // Apply category filters (--disable and --only)
     let original_count = config.patterns.len();
-    if let Some(ref only_categories) = args.only {
+    if let Some(ref categories) = args.only {
         // Keep only patterns matching specified categories
-        let categories: Vec<_> = only_categories
-            .iter()
-            .filter_map(|s| parse_category(s))
-            .collect();
-        config.patterns.retain(|p| categories.contains(&p.category));
+        retain_patterns(&mut config.patterns, &mut pattern_sources, |p| {
+            categories.contains(&p.category)
+        });
         if args.verbose >= 1 {
             eprintln!(
                 "Filtered to {} categories: {} -> {} patterns",
-                only_categories.join(","),
+                join_categories(categories),
                 original_count,
                 config.patterns.len()
             );
         }
-    } else if let Some(ref disable_categories) = args.disable {
+    } else if let Some(ref categories) = args.disable {
         // Remove patterns matching specified categories
-        let categories: Vec<_> = disable_categories
-            .iter()
-            .filter_map(|s| parse_category(s))
-            .collect();
-        config
-            .patterns
-            .retain(|p| !categories.contains(&p.category));
+        retain_patterns(&mut config.patterns, &mut pattern_sources, |p| {
+            !categories.contains(&p.category)
+        });
         if args.verbose >= 1 {
             eprintln!(
                 "Disabled {} categories: {} -> {} patterns",
-                disable_categories.join(","),
+                join_categories(categories),
                 original_count,
                 config.patterns.len()
             );
         }
     }
 
-    let scanner = Scanner::new(config.patterns.clone()).context("Failed to initialize scanner")?;
+    let mut language_overrides = config
+        .language_overrides()
+        .context("Invalid extension_languages in configuration")?;
+    for spec in &args.force_languages {
+        let (ext, lang) = parse_language_override(spec)
+            .with_context(|| format!("Invalid --force-language '{}'", spec))?;
+        language_overrides.insert(ext, lang);
+    }
+
+    let mut severity_map = HashMap::new();
+    for spec in &args.severity_map {
+        let (category, severity) = parse_severity_mapping(spec)
+            .with_context(|| format!("Invalid --severity-map '{}'", spec))?;
+        severity_map.insert(category, severity);
+    }
+
+    if args.dump_patterns {
+        let json = args.json || args.format.as_deref() == Some("json");
+        return dump_patterns(&config.patterns, &pattern_sources, json);
+    }
+
+    let score_weights: HashMap<Severity, u32> = args.weights.iter().cloned().collect();
+    let mut scanner = Scanner::new(config.patterns.clone())
+        .context("Failed to initialize scanner")?
+        .with_stale_comment_detection(args.detect_stale_comments)
+        .with_magic_number_detection(config.magic_numbers.clone())
+        .with_tree_sitter_required(config.require_tree_sitter)
+        .with_shebang_scanning(config.scan_shebang)
+        .with_all_matches(config.report_all_matches)
+        .with_comment_normalization(config.normalize_comments)
+        .with_score_dedup_by_match_text(config.dedupe_score_by_match_text)
+        .with_comment_allowlist(&config.allowlist)
+        .context("Invalid allowlist in configuration")?
+        .with_blank_context_skipped(args.skip_blank_context)
+        .with_score_weights(score_weights.clone())
+        .with_language_overrides(language_overrides);
+    if let Some(pattern) = &config.todo_ticket_pattern {
+        scanner = scanner
+            .with_todo_ticket_pattern(pattern)
+            .context("Invalid todo_ticket_pattern in configuration")?;
+    }
+    if config.ignore_test_files {
+        scanner = scanner
+            .with_test_files_ignored(&config.test_file_patterns)
+            .context("Invalid test_file_patterns in configuration")?;
+    }
 
-    let walker = Walker::new(&config);
-    let entries = walker.walk(&args.paths);
+    let modified_since = match args.since {
+        Some(since) => Some(
+            std::time::SystemTime::now()
+                .checked_sub(since)
+                .context("--since reaches further back than the system clock supports")?,
+        ),
+        None => None,
+    };
+    let walker = Walker::new(&config).with_modified_since(modified_since);
+    let entries = match args.files_from {
+        Some(ref list) if list.as_os_str() == "-" => walker
+            .walk_list(io::stdin().lock())
+            .context("Failed to read file list from stdin")?,
+        Some(ref list) => {
+            let file = fs::File::open(list)
+                .with_context(|| format!("Failed to open file list '{}'", list.display()))?;
+            walker
+                .walk_list(io::BufReader::new(file))
+                .with_context(|| format!("Failed to read file list '{}'", list.display()))?
+        }
+        None => walker.walk(&args.paths),
+    };
 
     if entries.is_empty() {
         eprintln!("No files found to scan");
         std::process::exit(1);
     }
 
-    let mut all_findings = Vec::new();
     let mut scan_results = Vec::new();
     let mut has_errors = false;
 
@@ -202,6 +575,16 @@ fn main() -> Result<()> {
         min_files_for_convention: 5, // Need 5+ files to establish pattern
         convention_threshold: 0.7,   // 70% must follow convention
         use_language_hints: false,   // Require project convention before flagging
+        all_caps_as_pascal: false,   // README, LICENSE and friends follow no style
+        exempt_filenames: antislop::filename_checker::DEFAULT_EXEMPT_FILENAMES
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+        convention_severity: args
+            .filename_severity
+            .clone()
+            .or_else(|| config.filename_convention_severity.clone()),
+        duplicate_severity: config.filename_duplicate_severity.clone(),
     };
 
     // Extract naming patterns for duplicate detection
@@ -221,7 +604,73 @@ fn main() -> Result<()> {
         ))
     };
 
+    let format = if let Some(fmt) = args.format.as_deref() {
+        match fmt {
+            "json" => Format::Json,
+            "sarif" => Format::Sarif,
+            _ => Format::Human,
+        }
+    } else if args.json {
+        Format::Json
+    } else {
+        Format::Human
+    };
+
+    let mut docstring_checker = config
+        .repeated_docstrings
+        .filter(|_| {
+            category_enabled(
+                args.only.as_deref(),
+                args.disable.as_deref(),
+                &antislop::PatternCategory::Boilerplate,
+            )
+        })
+        .map(DocstringChecker::new);
+
+    // SARIF results are written as each file finishes instead of being held
+    // until the end, unless something needs the whole set first
+    let mut sarif_stream = if format == Format::Sarif
+        && args.baseline.is_none()
+        && !args.review
+        && !args.list_findings_files
+        && args.fail_fast.is_none()
+    {
+        Some(
+            SarifWriter::new(io::BufWriter::new(io::stdout()))?
+                .with_severity_map(severity_map.clone()),
+        )
+    } else {
+        None
+    };
+    let mut streamed_summary = antislop::ScanSummary::default();
+
+    let mut category_cache = config
+        .incremental_categories
+        .as_deref()
+        .map(|path| {
+            antislop::detector::CategoryCache::load(path)
+                .with_context(|| format!("Failed to load scan cache '{}'", path.display()))
+        })
+        .transpose()?;
+
+    let progress = if !args.no_progress
+        && format == Format::Human
+        && entries.len() >= PROGRESS_THRESHOLD
+        && io::stderr().is_terminal()
+    {
+        let bar = ProgressBar::new(entries.len() as u64);
+        if let Ok(style) = ProgressStyle::with_template("{bar:40.cyan/blue} {pos}/{len} files") {
+            bar.set_style(style);
+        }
+        Some(bar)
+    } else {
+        None
+    };
+
     for entry in &entries {
+        if let Some(ref bar) = progress {
+            bar.inc(1);
+        }
         let path = entry.path.to_string_lossy().to_string();
 
         // Add to filename checker for convention analysis
@@ -229,8 +678,14 @@ fn main() -> Result<()> {
             checker.add_file(&entry.path);
         }
 
-        let content = match fs::read_to_string(&entry.path) {
-            Ok(c) => c,
+        let content = match entry.read_text() {
+            Ok(Some(c)) => c,
+            Ok(None) => {
+                if args.verbose >= 1 {
+                    eprintln!("Skipping binary file: {}", path);
+                }
+                continue;
+            }
             Err(e) => {
                 eprintln!("Error reading file '{}': {}", path, e);
                 has_errors = true;
@@ -238,89 +693,188 @@ fn main() -> Result<()> {
             }
         };
 
+        if config.skip_generated {
+            if let Some(reason) = antislop::detector::generated_reason(&content) {
+                if args.verbose >= 1 {
+                    eprintln!("Skipping generated file ({}): {}", reason, path);
+                }
+                continue;
+            }
+        }
+
         if args.verbose >= 2 {
             eprintln!("Scanning: {}", entry.path.display());
         }
 
-        let result = scanner.scan_file(&path, &content);
-        for finding in &result.findings {
-            all_findings.push(finding.clone());
+        if let Some(ref mut checker) = docstring_checker {
+            checker.add_file(&path, scanner.doc_comments(&path, &content));
+        }
+        let mut result = match category_cache {
+            Some(ref mut cache) => cache.scan_file(&scanner, &path, &content).result,
+            None => scanner.scan_file(&path, &content),
+        };
+
+        if let Some(ref threshold) = args.fail_fast {
+            result.sort_findings();
+            let first = result.findings.iter().find(|f| {
+                let severity = severity_map.get(&f.category).unwrap_or(&f.severity);
+                !f.informational && severity.score() >= threshold.score()
+            });
+            if let Some(finding) = first {
+                if let Some(ref bar) = progress {
+                    bar.finish_and_clear();
+                }
+                base_reporter(format, &config, &args)
+                    .with_severity_map(severity_map.clone())
+                    .report_findings_only(vec![finding.clone()])?;
+                std::process::exit(exit_code(1, false, args.exit_zero));
+            }
+        }
+        if let Some(ref mut writer) = sarif_stream {
+            result.sort_findings();
+            result.rescore(&score_weights, config.dedupe_score_by_match_text);
+            for finding in &result.findings {
+                writer.write_finding(finding)?;
+            }
+            streamed_summary.add(&result);
+        } else {
+            scan_results.push(result);
         }
-        scan_results.push(result);
     }
 
-    // Check for naming convention violations
-    let filename_findings = if let Some(ref checker) = filename_checker {
+    if let Some(bar) = progress {
+        bar.finish_and_clear();
+    }
+    if let (Some(cache), Some(path)) = (&category_cache, &config.incremental_categories) {
+        cache
+            .save(path)
+            .with_context(|| format!("Failed to save scan cache '{}'", path.display()))?;
+    }
+
+    // Check for naming convention violations and repeated docstrings
+    let mut project_findings = if let Some(ref checker) = filename_checker {
         checker.check()
     } else {
         Vec::new()
     };
-    for finding in &filename_findings {
-        all_findings.push(finding.clone());
+    if let Some(ref checker) = docstring_checker {
+        project_findings.extend(checker.check());
     }
 
-    // Recalculate summary including filename findings
-    let summary = antislop::ScanSummary::new(&scan_results);
+    // A review may start the baseline, so it need not exist yet
+    let baseline_pending = args.review && args.baseline.as_ref().is_some_and(|p| !p.exists());
+    let mut baseline_diff = None;
+    if let Some(baseline_path) = args.baseline.as_ref().filter(|_| !baseline_pending) {
+        baseline_diff = apply_baseline(
+            baseline_path,
+            args.baseline_format
+                .unwrap_or_else(|| BaselineFormat::from_path(baseline_path)),
+            args.refresh_baseline,
+            args.baseline_age.or(config.baseline_max_age),
+            &mut scan_results,
+            &mut project_findings,
+        )?
+        .filter(|_| args.show_new);
+    }
 
-    // Add filename findings to the total score
-    let filename_score: u32 = filename_findings.iter().map(|f| f.severity.score()).sum();
-    let total_with_filenames = summary.total_score + filename_score;
-    let exit_code = if total_with_filenames > 0 || has_errors {
-        1
-    } else {
-        0
-    };
+    // Cross-file findings join their file's result so they share one
+    // dedup + sort step and one summary with content findings
+    let mut result_index: HashMap<String, usize> = scan_results
+        .iter()
+        .enumerate()
+        .map(|(idx, result)| (result.path.clone(), idx))
+        .collect();
+    let mut unscanned = 0;
+    for finding in project_findings {
+        let idx = *result_index.entry(finding.file.clone()).or_insert_with(|| {
+            unscanned += 1;
+            scan_results.push(antislop::FileScanResult {
+                path: finding.file.clone(),
+                findings: Vec::new(),
+                score: 0,
+                lines: 0,
+            });
+            scan_results.len() - 1
+        });
+        scan_results[idx].findings.push(finding);
+    }
+    scan_results.sort_by(|a, b| a.path.cmp(&b.path));
+    for result in &mut scan_results {
+        result.sort_findings();
+        // Sorting recomputes the plain score; reapply weights and dedup
+        result.rescore(&score_weights, config.dedupe_score_by_match_text);
+    }
 
-    // Create a modified summary that includes filename findings
-    let mut summary_with_filenames = summary.clone();
-    summary_with_filenames.total_score = total_with_filenames;
-    summary_with_filenames.total_findings += filename_findings.len();
-
-    // Add filename findings to category counts
-    for finding in &filename_findings {
-        *summary_with_filenames
-            .by_category
-            .entry(finding.category.clone())
-            .or_insert(0) += 1;
-        *summary_with_filenames
-            .by_severity
-            .entry(finding.severity.clone())
-            .or_insert(0) += 1;
-    }
-
-    // Update files_with_findings if filename findings exist in previously clean files
-    if !filename_findings.is_empty() {
-        let files_with_filename_issues: std::collections::HashSet<_> =
-            filename_findings.iter().map(|f| f.file.clone()).collect();
-        let files_with_content_issues: std::collections::HashSet<_> = scan_results
-            .iter()
-            .filter(|r| !r.findings.is_empty())
-            .map(|r| r.path.clone())
-            .collect();
+    let mut summary = antislop::ScanSummary::new(&scan_results);
+    // Results made up to hold cross-file findings are not scanned files
+    summary.files_scanned -= unscanned;
+    summary.merge(streamed_summary);
+    let exit_code = exit_code(summary.total_score, has_errors, args.exit_zero);
+    let all_findings: Vec<_> = scan_results
+        .iter()
+        .flat_map(|result| result.findings.iter().cloned())
+        .collect();
+
+    if args.list_findings_files {
+        // Findings are sorted by file, so duplicates are adjacent
+        let mut files: Vec<&str> = all_findings.iter().map(|f| f.file.as_str()).collect();
+        files.dedup();
+        for file in files {
+            println!("{}", file);
+        }
+        if exit_code != 0 {
+            std::process::exit(exit_code);
+        }
+        return Ok(());
+    }
 
-        let total_files_with_issues = files_with_filename_issues
-            .union(&files_with_content_issues)
-            .count();
-        summary_with_filenames.files_with_findings = total_files_with_issues;
+    if args.review {
+        return review_findings(all_findings, &args, &scanner);
     }
 
-    let format = if let Some(fmt) = args.format {
-        match fmt.as_str() {
-            "json" => Format::Json,
-            "sarif" => Format::Sarif,
-            _ => Format::Human,
+    if args.format.as_deref() == Some("sqlite") {
+        write_sqlite_report(args.output.as_deref(), &all_findings, &summary)?;
+        if exit_code != 0 {
+            std::process::exit(exit_code);
         }
-    } else if args.json {
-        Format::Json
-    } else {
-        Format::Human
-    };
+        return Ok(());
+    }
 
-    let reporter = Reporter::new(format);
+    let mut reporter = base_reporter(format, &config, &args)
+        .with_severity_map(severity_map)
+        .with_baseline_diff(baseline_diff)
+        .with_group_by_dir(args.group_by_dir)
+        .with_collapse(args.collapse)
+        .with_top_files(args.top_files)
+        .with_summary_detail(args.summary_detail);
+    if args.blame {
+        reporter = reporter.with_blame(antislop::blame::blame_findings(&all_findings));
+    }
+    if args.pattern_stats {
+        reporter = reporter.with_pattern_stats(antislop::PatternStat::tally(
+            &config.patterns,
+            &all_findings,
+        ));
+    }
 
-    all_findings.sort_by_key(|f| (f.file.clone(), f.line));
+    let parseable_summary = args.parseable_summary.then(|| summary.parseable_line());
+
+    if let Some(mut writer) = sarif_stream {
+        // Content findings were streamed during the scan; add the
+        // cross-file ones and close the log
+        for finding in &all_findings {
+            writer.write_finding(finding)?;
+        }
+        writer.finish()?;
+    } else if args.no_summary {
+        reporter.report_findings_only(all_findings)?;
+    } else {
+        reporter.report(all_findings, summary)?;
+    }
 
-    reporter.report(all_findings, summary_with_filenames)?;
+    if let Some(line) = parseable_summary {
+        eprintln!("{}", line);
+    }
 
     if exit_code != 0 {
         std::process::exit(exit_code);
@@ -329,6 +883,213 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Triage findings in the terminal UI, then add acknowledged findings to
+/// the baseline and wrap suppressed ones in `antislop:disable` directives.
+#[cfg(feature = "review")]
+fn review_findings(findings: Vec<antislop::Finding>, args: &Args, scanner: &Scanner) -> Result<()> {
+    use antislop::review::Decision;
+
+    if findings.is_empty() {
+        eprintln!("No findings to review");
+        return Ok(());
+    }
+    let review = antislop::review::run(findings).context("Review failed")?;
+
+    let acknowledged = review.decided(Decision::Acknowledge);
+    if let (Some(path), false) = (&args.baseline, acknowledged.is_empty()) {
+        let format = args
+            .baseline_format
+            .unwrap_or_else(|| BaselineFormat::from_path(path));
+        let mut baseline = if path.exists() {
+            Baseline::load_as(path, format)?
+        } else {
+            Baseline::default()
+        };
+        baseline.entries.extend(
+            acknowledged
+                .iter()
+                .map(|f| antislop::baseline::BaselineEntry::from_finding(f)),
+        );
+        baseline.save_as(path, format)?;
+        eprintln!(
+            "Added {} finding(s) to baseline '{}'",
+            acknowledged.len(),
+            path.display()
+        );
+    }
+
+    let mut by_file: Vec<(&str, Vec<&antislop::Finding>)> = Vec::new();
+    for finding in review.decided(Decision::Suppress) {
+        match by_file.iter_mut().find(|(file, _)| *file == finding.file) {
+            Some((_, findings)) => findings.push(finding),
+            None => by_file.push((&finding.file, vec![finding])),
+        }
+    }
+    for (file, findings) in by_file {
+        let Some(comment) = scanner.resolve_language(Path::new(file)).line_comment() else {
+            eprintln!(
+                "Cannot suppress findings in '{}': no line comment syntax",
+                file
+            );
+            continue;
+        };
+        let source =
+            fs::read_to_string(file).with_context(|| format!("Failed to read '{}'", file))?;
+        let comments = scanner.comments(file, &source);
+        let mut lines = Vec::new();
+        for finding in findings {
+            if antislop::review::can_suppress(finding, &source, &comments, comment) {
+                lines.push(finding.line);
+            } else {
+                eprintln!(
+                    "Cannot suppress {}:{}: not in a line comment",
+                    file, finding.line
+                );
+            }
+        }
+        if lines.is_empty() {
+            continue;
+        }
+        fs::write(
+            file,
+            antislop::review::suppress_lines(&source, &lines, comment),
+        )
+        .with_context(|| format!("Failed to write '{}'", file))?;
+        eprintln!("Suppressed {} finding(s) in '{}'", lines.len(), file);
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "review"))]
+fn review_findings(
+    _findings: Vec<antislop::Finding>,
+    _args: &Args,
+    _scanner: &Scanner,
+) -> Result<()> {
+    bail!("--review requires antislop built with the `review` feature");
+}
+
+/// Append this run to the `--output` SQLite database.
+#[cfg(feature = "sqlite")]
+fn write_sqlite_report(
+    output: Option<&Path>,
+    findings: &[antislop::Finding],
+    summary: &antislop::ScanSummary,
+) -> Result<()> {
+    let Some(path) = output else {
+        bail!("--format sqlite requires --output <FILE>");
+    };
+    let run_id = antislop::report::write_sqlite(path, findings, summary)
+        .with_context(|| format!("Failed to write findings to '{}'", path.display()))?;
+    eprintln!(
+        "Wrote {} finding(s) to {} (run {})",
+        findings.len(),
+        path.display(),
+        run_id
+    );
+    Ok(())
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn write_sqlite_report(
+    _output: Option<&Path>,
+    _findings: &[antislop::Finding],
+    _summary: &antislop::ScanSummary,
+) -> Result<()> {
+    bail!("--format sqlite requires antislop built with the `sqlite` feature");
+}
+
+/// Suppress baselined findings, warn about stale or resolved baseline
+/// entries, and rewrite the baseline when `--refresh-baseline` is set.
+///
+/// Returns how the scan compares with the baseline, or `None` when the
+/// baseline was just created from this scan.
+fn apply_baseline(
+    path: &Path,
+    format: BaselineFormat,
+    refresh: bool,
+    max_age_days: Option<u64>,
+    scan_results: &mut [antislop::FileScanResult],
+    project_findings: &mut Vec<antislop::Finding>,
+) -> Result<Option<BaselineDiff>> {
+    if !path.exists() {
+        if !refresh {
+            bail!(
+                "Baseline '{}' not found; create it with --refresh-baseline",
+                path.display()
+            );
+        }
+        let mut findings: Vec<_> = scan_results
+            .iter_mut()
+            .flat_map(|r| std::mem::take(&mut r.findings))
+            .collect();
+        findings.append(project_findings);
+        for result in scan_results.iter_mut() {
+            result.score = 0;
+        }
+        Baseline::from_findings(&findings).save_as(path, format)?;
+        eprintln!(
+            "Created baseline '{}' with {} finding(s)",
+            path.display(),
+            findings.len()
+        );
+        return Ok(None);
+    }
+
+    let baseline = Baseline::load_as(path, format)?;
+    if let Some(days) = max_age_days {
+        let max_age = std::time::Duration::from_secs(days.saturating_mul(24 * 60 * 60));
+        if let Some(age) = antislop::baseline::stale_age(path, max_age) {
+            eprintln!(
+                "Warning: baseline '{}' is {} days old (max {}); it may be hiding regressions",
+                path.display(),
+                age.as_secs() / (24 * 60 * 60),
+                days
+            );
+        }
+    }
+
+    let mut matcher = baseline.matcher();
+    for result in scan_results.iter_mut() {
+        matcher.apply(result);
+    }
+    matcher.suppress(project_findings);
+
+    let diff = matcher.diff();
+    let resolved = &diff.resolved;
+    if refresh {
+        baseline.without(resolved).save_as(path, format)?;
+        eprintln!(
+            "Refreshed baseline '{}': removed {} resolved entr{}",
+            path.display(),
+            resolved.len(),
+            if resolved.len() == 1 { "y" } else { "ies" }
+        );
+    } else if !resolved.is_empty() {
+        eprintln!(
+            "{} baselined finding(s) no longer exist; run with --refresh-baseline to drop them",
+            resolved.len()
+        );
+    }
+
+    Ok(Some(diff))
+}
+
+/// Decide the process exit code for a completed scan.
+///
+/// `--exit-zero` is an unconditional override; otherwise any score or read
+/// error fails the run.
+fn exit_code(total_score: u32, has_errors: bool, exit_zero: bool) -> i32 {
+    if exit_zero {
+        return 0;
+    }
+    if total_score > 0 || has_errors {
+        1
+    } else {
+        0
+    }
+}
+
 fn init_tracing(verbose: u8) {
     let level = match verbose {
         0 => "warn",
@@ -344,39 +1105,109 @@ fn init_tracing(verbose: u8) {
         .ok();
 }
 
-fn load_config(path: &Option<PathBuf>) -> Result<Config> {
+/// Load the config, returning the file it came from (`None` for built-in defaults).
+fn load_config(path: &Option<PathBuf>) -> Result<(Config, Option<PathBuf>)> {
     if let Some(p) = path {
-        return Config::load(p).context("Failed to load config");
+        let config = Config::load(p).context("Failed to load config")?;
+        return Ok((config, Some(p.clone())));
     }
 
     for name in CONFIG_FILES {
         let p = PathBuf::from(name);
         // Check if path exists AND is a file (not a directory)
         if p.exists() && p.is_file() {
-            return Config::load(&p).context("Failed to load config");
+            let config = Config::load(&p).context("Failed to load config")?;
+            return Ok((config, Some(p)));
         }
     }
 
-    Ok(Config::default())
+    Ok((Config::default(), None))
+}
+
+/// Whether `--only`/`--disable` leave `category` enabled.
+fn category_enabled(
+    only: Option<&[antislop::PatternCategory]>,
+    disable: Option<&[antislop::PatternCategory]>,
+    category: &antislop::PatternCategory,
+) -> bool {
+    match (only, disable) {
+        (Some(only), _) => only.contains(category),
+        (None, Some(disable)) => !disable.contains(category),
+        (None, None) => true,
+    }
+}
+
+/// Retain patterns matching `keep`, keeping `sources` aligned with them.
+fn retain_patterns(
+    patterns: &mut Vec<antislop::Pattern>,
+    sources: &mut Vec<String>,
+    keep: impl Fn(&antislop::Pattern) -> bool,
+) {
+    let mut kept = patterns.iter().map(&keep);
+    sources.retain(|_| kept.next().unwrap_or(false));
+    patterns.retain(keep);
+}
+
+/// A resolved pattern and where it came from, for --dump-patterns.
+#[derive(serde::Serialize)]
+struct DumpedPattern<'a> {
+    source: &'a str,
+    #[serde(flatten)]
+    pattern: &'a antislop::Pattern,
+}
+
+/// Print the active pattern set with sources as TOML or JSON.
+fn dump_patterns(patterns: &[antislop::Pattern], sources: &[String], json: bool) -> Result<()> {
+    #[derive(serde::Serialize)]
+    struct Dump<'a> {
+        patterns: Vec<DumpedPattern<'a>>,
+    }
+    let dump = Dump {
+        patterns: patterns
+            .iter()
+            .zip(sources)
+            .map(|(pattern, source)| DumpedPattern { source, pattern })
+            .collect(),
+    };
+    if json {
+        println!("{}", serde_json::to_string_pretty(&dump)?);
+    } else {
+        print!("{}", toml::to_string_pretty(&dump)?);
+    }
+    Ok(())
 }
 
 fn print_languages() {
+    let list = |exts: &[&str]| {
+        exts.iter()
+            .map(|e| format!(".{}", e))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
     println!("Supported languages:");
-    println!("  Python      (.py)");
-    println!("  JavaScript  (.js, .mjs, .cjs)");
-    println!("  TypeScript  (.ts)");
-    println!("  JSX         (.jsx)");
-    println!("  TSX         (.tsx)");
-    println!("  Rust        (.rs)");
-    println!("  Go          (.go)");
-    println!("  Java        (.java)");
-    println!("  Kotlin      (.kt, .kts)");
-    println!("  C/C++       (.c, .cpp, .cc, .cxx, .h, .hpp)");
-    println!("  C#          (.cs)");
-    println!("  Ruby        (.rb)");
-    println!("  PHP         (.php)");
-    println!("  Swift       (.swift)");
-    println!("  Shell       (.sh, .bash, .zsh, .fish)");
+    for &lang in Language::ALL {
+        let note = match lang {
+            Language::Unknown => continue,
+            Language::Text => ", with --scan-docs",
+            _ => "",
+        };
+        println!(
+            "  {:<12}({}{})",
+            lang.display_name(),
+            list(lang.extensions()),
+            note
+        );
+    }
+    println!(
+        "  {:<12}({}, code cells in the kernel language)",
+        "Notebooks",
+        list(&[antislop::detector::NOTEBOOK_EXTENSION])
+    );
+    println!(
+        "  {:<12}({}, <script> blocks as JS/TS)",
+        "Markup",
+        list(antislop::detector::MARKUP_EXTENSIONS)
+    );
 }
 
 fn print_default_config() {
@@ -391,23 +1222,104 @@ fn generate_completions(shell: Shell) {
     generate(shell, &mut cmd, name, &mut io::stdout());
 }
 
-fn load_profile(source: &str) -> Result<Profile> {
-    let profile_source = ProfileSource::parse(source).context("Failed to parse profile source")?;
+/// Maps each pattern's (regex, category) to the name of the profile it came from.
+type ProfileNames = HashMap<(String, antislop::PatternCategory), String>;
+
+/// Load one or more profiles and merge them in order.
+///
+/// Later profiles take precedence over earlier ones when both define a
+/// pattern with the same regex and category.
+fn load_profile(sources: &[String], verbose: u8, strict: bool) -> Result<(Profile, ProfileNames)> {
+    let loader = ProfileLoader::new()
+        .context("Failed to initialize profile loader")?
+        .with_strict(strict);
+
+    let mut merged: Option<Profile> = None;
+    let mut names = ProfileNames::new();
+    for source in sources {
+        let profile_source =
+            ProfileSource::parse(source).context("Failed to parse profile source")?;
+        let mut profile = loader
+            .load(&profile_source)
+            .context(format!("Failed to load profile from '{}'", source))?;
+        for warning in &profile.warnings {
+            eprintln!("warning: {}", warning);
+        }
 
-    let loader = ProfileLoader::new().context("Failed to initialize profile loader")?;
+        if verbose >= 1 {
+            eprintln!(
+                "Loaded profile: {} (v{})",
+                profile.metadata.name, profile.metadata.version
+            );
+            eprintln!("  {} patterns from profile", profile.patterns.len());
+        }
+
+        // Later profiles win duplicates, matching merge_with
+        for pattern in &profile.patterns {
+            names.insert(
+                (pattern.regex.to_string(), pattern.category.clone()),
+                profile.metadata.name.clone(),
+            );
+        }
+
+        if let Some(previous) = merged.take() {
+            profile.merge_with(&previous);
+        }
+        merged = Some(profile);
+    }
 
-    loader
-        .load(&profile_source)
-        .context(format!("Failed to load profile from '{}'", source))
+    let merged = merged.context("No profile sources given")?;
+    Ok((merged, names))
 }
 
-fn print_profiles() -> Result<()> {
+fn run_command(command: Command) -> Result<()> {
+    match command {
+        Command::Profile {
+            action: ProfileCommand::Prune { max_age },
+        } => {
+            let loader = ProfileLoader::new().context("Failed to initialize profile loader")?;
+            let removed = loader
+                .prune_cache(std::time::Duration::from_secs(max_age * 60 * 60))
+                .context("Failed to prune profile cache")?;
+
+            if removed.is_empty() {
+                println!("No stale cached profiles.");
+            } else {
+                for name in &removed {
+                    println!("Removed: {}", name);
+                }
+                println!("Pruned {} cached profile(s).", removed.len());
+            }
+            Ok(())
+        }
+        Command::Profile {
+            action: ProfileCommand::Checksum { file },
+        } => {
+            let content = fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read profile: {}", file.display()))?;
+            // Parse without verification so a stale checksum can be replaced
+            let profile: Profile = toml::from_str(&content)
+                .with_context(|| format!("Failed to parse profile: {}", file.display()))?;
+            println!("{}", profile.checksum()?);
+            Ok(())
+        }
+    }
+}
+
+fn print_profiles(json: bool) -> Result<()> {
     let loader = ProfileLoader::new().context("Failed to initialize profile loader")?;
 
-    let profiles = loader.list_available();
+    let on_disk = loader.list_available();
+    let builtin = ProfileLoader::list_builtin();
+
+    if json {
+        let all: Vec<_> = on_disk.iter().chain(&builtin).collect();
+        println!("{}", serde_json::to_string_pretty(&all)?);
+        return Ok(());
+    }
 
-    if profiles.is_empty() {
-        println!("No profiles found.");
+    if on_disk.is_empty() {
+        println!("No profiles found on disk.");
         println!();
         println!("Profile search locations:");
         println!("  - .antislop/profiles/*.toml (project-local)");
@@ -417,10 +1329,11 @@ fn print_profiles() -> Result<()> {
         println!("You can also load profiles directly:");
         println!("  antislop --profile /path/to/profile.toml");
         println!("  antislop --profile https://example.com/profile.toml");
+        println!();
     } else {
         println!("Available profiles:");
         println!();
-        for profile in profiles {
+        for profile in on_disk {
             println!("  {} (v{})", profile.name, profile.version);
             if !profile.description.is_empty() {
                 println!("    {}", profile.description);
@@ -430,21 +1343,167 @@ fn print_profiles() -> Result<()> {
         }
     }
 
+    println!("Built-in profiles:");
+    println!();
+    for profile in builtin {
+        println!("  {} (v{})", profile.name, profile.version);
+        if !profile.description.is_empty() {
+            println!("    {}", profile.description);
+        }
+        println!();
+    }
+
     Ok(())
 }
 
-/// Parse a category string into a PatternCategory enum.
-fn parse_category(s: &str) -> Option<antislop::PatternCategory> {
-    use antislop::PatternCategory;
+/// A reporter with the display settings shared by every report.
+fn base_reporter(format: Format, config: &Config, args: &Args) -> Reporter {
+    Reporter::new(format)
+        .with_tab_width(config.tab_width)
+        .with_hyperlinks(supports_hyperlinks::on(supports_hyperlinks::Stream::Stdout))
+        .with_context_line_numbers(!args.skip_blank_context)
+        .with_pattern_shown(args.show_pattern)
+        .with_message_template(config.message_template.clone())
+        .with_score_weights(args.weights.iter().cloned().collect())
+}
+
+/// Comma-separated canonical names, for log messages.
+fn join_categories(categories: &[antislop::PatternCategory]) -> String {
+    categories
+        .iter()
+        .map(|c| c.as_str())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn severity_from_str(s: &str) -> Option<Severity> {
     match s.to_lowercase().as_str() {
-        "placeholder" => Some(PatternCategory::Placeholder),
-        "deferral" => Some(PatternCategory::Deferral),
-        "hedging" => Some(PatternCategory::Hedging),
-        "stub" => Some(PatternCategory::Stub),
-        "namingconvention" | "naming" => Some(PatternCategory::NamingConvention),
+        "low" => Some(Severity::Low),
+        "medium" => Some(Severity::Medium),
+        "high" => Some(Severity::High),
+        "critical" => Some(Severity::Critical),
+        _ => None,
+    }
+}
+
+/// Clap value parser for severity names.
+fn parse_severity(s: &str) -> std::result::Result<Severity, String> {
+    severity_from_str(s).ok_or_else(|| format!("unknown severity '{}'", s))
+}
+
+/// Clap value parser for a `severity=score` weight override.
+fn parse_weight(spec: &str) -> std::result::Result<(Severity, u32), String> {
+    let (severity, weight) = spec
+        .split_once('=')
+        .ok_or_else(|| "expected 'severity=score'".to_string())?;
+    let severity = parse_severity(severity.trim())?;
+    let weight = weight
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid score '{}'", weight.trim()))?;
+    Ok((severity, weight))
+}
+
+/// Clap value parser for durations such as `90s`, `30m`, `24h`, `7d` or `2w`.
+fn parse_duration(s: &str) -> std::result::Result<std::time::Duration, String> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (amount, unit) = s.split_at(split);
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("invalid duration '{}'", s))?;
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
         _ => {
-            eprintln!("Warning: unknown category '{}', ignoring", s);
-            None
+            return Err(format!(
+                "invalid duration '{}' (expected e.g. 30m, 24h, 7d)",
+                s
+            ))
         }
+    };
+    amount
+        .checked_mul(seconds)
+        .map(std::time::Duration::from_secs)
+        .ok_or_else(|| format!("duration '{}' is too long", s))
+}
+
+/// Parse an `ext=lang` spec into an extension and language.
+fn parse_language_override(spec: &str) -> Result<(String, Language)> {
+    let Some((ext, lang)) = spec.split_once('=') else {
+        bail!("expected 'ext=lang'");
+    };
+    let ext = ext.trim().trim_start_matches('.');
+    if ext.is_empty() {
+        bail!("extension must not be empty");
+    }
+    let Some(language) = Language::from_name(lang.trim()) else {
+        bail!("unknown language '{}'", lang.trim());
+    };
+    Ok((ext.to_string(), language))
+}
+
+/// Parse a `category=level` spec, where the level is a severity name or
+/// the SARIF level it maps to.
+fn parse_severity_mapping(spec: &str) -> Result<(antislop::PatternCategory, Severity)> {
+    let Some((category, level)) = spec.split_once('=') else {
+        bail!("expected 'category=level'");
+    };
+    let category: antislop::PatternCategory = category.trim().parse()?;
+    let severity = match level.trim().to_lowercase().as_str() {
+        "note" => Severity::Low,
+        "warning" => Severity::Medium,
+        "error" => Severity::High,
+        other => match severity_from_str(other) {
+            Some(severity) => severity,
+            None => bail!("unknown level '{}'", level.trim()),
+        },
+    };
+    Ok((category, severity))
+}
+
+/// Parse a `regex:severity:category:message` spec into a pattern.
+///
+/// The regex and message may themselves contain colons; the first
+/// `severity:category` pair after a non-empty regex splits the fields.
+fn parse_pattern_spec(spec: &str) -> Result<Pattern> {
+    let parts: Vec<&str> = spec.split(':').collect();
+    if parts.len() < 4 {
+        bail!("expected 'regex:severity:category:message'");
     }
+
+    let split = (1..parts.len() - 2).find(|&i| {
+        severity_from_str(parts[i]).is_some()
+            && parts[i + 1].parse::<antislop::PatternCategory>().is_ok()
+    });
+    let Some(i) = split else {
+        let categories: Vec<&str> = antislop::PatternCategory::ALL
+            .iter()
+            .map(antislop::PatternCategory::as_str)
+            .collect();
+        bail!(
+            "no valid 'severity:category' pair found \
+             (severity: low, medium, high, critical; category: {})",
+            categories.join(", ")
+        );
+    };
+
+    let regex = parts[..i].join(":");
+    let message = parts[i + 2..].join(":");
+    if message.is_empty() {
+        bail!("message must not be empty");
+    }
+
+    Ok(Pattern {
+        regex: RegexPattern::new(regex).context("invalid regex")?,
+        severity: severity_from_str(parts[i]).unwrap_or_default(),
+        category: parts[i + 1].parse().unwrap_or_default(),
+        message,
+        ast_query: None,
+        languages: vec![],
+        ..Default::default()
+    })
 }
@@ -0,0 +1,163 @@
+//! Attribute findings to the commits that introduced them via `git blame`.
+
+use crate::detector::Finding;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+/// The commit that last touched a line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BlameInfo {
+    /// Full commit hash.
+    pub commit: String,
+    /// Author name.
+    pub author: String,
+    /// Author date as `YYYY-MM-DD` (UTC).
+    pub date: String,
+}
+
+impl BlameInfo {
+    /// Abbreviated commit hash for display.
+    pub fn short_commit(&self) -> &str {
+        &self.commit[..self.commit.len().min(8)]
+    }
+}
+
+/// Blame information keyed by file path, then 1-indexed line number.
+pub type BlameMap = HashMap<String, HashMap<usize, BlameInfo>>;
+
+/// Run `git blame` once per file that has findings.
+///
+/// Files that are untracked, or not inside a git repository, are left out
+/// of the map rather than treated as errors.
+pub fn blame_findings(findings: &[Finding]) -> BlameMap {
+    let mut map = BlameMap::new();
+    for finding in findings {
+        if map.contains_key(&finding.file) {
+            continue;
+        }
+        if let Some(lines) = blame_file(Path::new(&finding.file)) {
+            map.insert(finding.file.clone(), lines);
+        }
+    }
+    map
+}
+
+/// Blame every line of a file. Returns `None` if git cannot blame it.
+pub fn blame_file(path: &Path) -> Option<HashMap<usize, BlameInfo>> {
+    let dir = path.parent().filter(|p| !p.as_os_str().is_empty());
+    let name = path.file_name()?;
+
+    let mut cmd = Command::new("git");
+    cmd.args(["blame", "--line-porcelain", "--"]).arg(name);
+    if let Some(dir) = dir {
+        cmd.current_dir(dir);
+    }
+
+    let output = cmd.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(parse_porcelain(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `git blame --line-porcelain` output.
+fn parse_porcelain(output: &str) -> HashMap<usize, BlameInfo> {
+    let mut lines = HashMap::new();
+    let mut commit = String::new();
+    let mut final_line = 0;
+    let mut author = String::new();
+    let mut date = String::new();
+
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            // Content line terminates each record
+            lines.insert(
+                final_line,
+                BlameInfo {
+                    commit: std::mem::take(&mut commit),
+                    author: std::mem::take(&mut author),
+                    date: std::mem::take(&mut date),
+                },
+            );
+        } else if let Some(name) = line.strip_prefix("author ") {
+            author = name.to_string();
+        } else if let Some(time) = line.strip_prefix("author-time ") {
+            date = time.parse().map(format_date).unwrap_or_default();
+        } else if commit.is_empty() {
+            // Header: <sha> <orig_line> <final_line> [<num_lines>]
+            let mut parts = line.split_whitespace();
+            if let (Some(sha), Some(_), Some(line_no)) = (parts.next(), parts.next(), parts.next())
+            {
+                if sha.len() >= 40 && sha.chars().all(|c| c.is_ascii_hexdigit()) {
+                    commit = sha.to_string();
+                    final_line = line_no.parse().unwrap_or(0);
+                }
+            }
+        }
+    }
+
+    lines
+}
+
+/// Format a Unix timestamp as a UTC `YYYY-MM-DD` date.
+fn format_date(timestamp: i64) -> String {
+    // Civil-from-days conversion (Howard Hinnant's algorithm)
+    let days = timestamp.div_euclid(86_400);
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_porcelain() {
+        let output = "\
+1f2e3d4c5b6a79881f2e3d4c5b6a79881f2e3d4c 1 1 2
+author Jane Doe
+author-mail <jane@example.com>
+author-time 1700000000
+author-tz +0000
+summary Initial commit
+filename code.py
+\t# TODO: implement
+1f2e3d4c5b6a79881f2e3d4c5b6a79881f2e3d4c 2 2
+author Jane Doe
+author-time 1700000000
+filename code.py
+\tpass
+";
+        let lines = parse_porcelain(output);
+        assert_eq!(lines.len(), 2);
+        let first = &lines[&1];
+        assert_eq!(first.author, "Jane Doe");
+        assert_eq!(first.date, "2023-11-14");
+        assert_eq!(first.short_commit(), "1f2e3d4c");
+    }
+
+    #[test]
+    fn test_format_date() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(951_782_400), "2000-02-29");
+    }
+
+    #[test]
+    fn test_blame_untracked_file_is_none() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let file = temp.path().join("code.py");
+        std::fs::write(&file, "# TODO\n").unwrap();
+        assert!(blame_file(&file).is_none());
+    }
+}
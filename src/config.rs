@@ -5,8 +5,9 @@
 use crate::{Error, Result};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 const DEFAULT_CONFIG_TOML: &str = include_str!("../config/default.toml");
 
@@ -55,8 +56,7 @@ impl PatternsDirectory {
 
     /// Parse patterns from a single TOML file.
     fn parse_file(&self, content: &str) -> Result<Vec<Pattern>> {
-        let parsed: PatternFile = toml::from_str(content)
-            .map_err(|e| Error::ConfigInvalid(format!("Parse error in pattern file: {}", e)))?;
+        let parsed: PatternFile = toml::from_str(content)?;
         Ok(parsed.patterns)
     }
 }
@@ -68,11 +68,15 @@ struct PatternFile {
 }
 
 /// Regex pattern with validation.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+///
+/// The regex is compiled once on construction, including when deserialized,
+/// so an invalid pattern fails config or profile loading rather than scanning.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(try_from = "String", into = "String")]
 pub struct RegexPattern(String);
 
 impl RegexPattern {
+    /// Validate and wrap a regex string.
     pub fn new(s: String) -> std::result::Result<Self, regex::Error> {
         regex::Regex::new(&s)?;
         Ok(Self(s))
@@ -92,6 +96,12 @@ impl From<RegexPattern> for String {
     }
 }
 
+impl std::fmt::Display for RegexPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
 impl std::ops::Deref for RegexPattern {
     type Target = str;
     fn deref(&self) -> &Self::Target {
@@ -136,6 +146,19 @@ impl Severity {
     }
 }
 
+impl std::fmt::Display for Severity {
+    /// Lowercase canonical name, matching the serialized form.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+            Severity::Critical => "critical",
+        };
+        f.write_str(name)
+    }
+}
+
 /// Category of slop pattern.
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
@@ -151,6 +174,128 @@ pub enum PatternCategory {
     Stub,
     /// Filename convention violations: inconsistent naming, suspicious suffixes.
     NamingConvention,
+    /// Comments that contradict the code they describe.
+    StaleComment,
+    /// Credentials left at placeholder values, e.g. `password = "changeme"`.
+    SecurityPlaceholder,
+    /// Chat transcript boilerplate, e.g. "I apologize for the confusion".
+    Boilerplate,
+    /// Leftover merge conflict markers such as `<<<<<<< HEAD`.
+    MergeConflict,
+}
+
+impl PatternCategory {
+    /// Lowercase canonical name, matching the serialized form.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PatternCategory::Placeholder => "placeholder",
+            PatternCategory::Deferral => "deferral",
+            PatternCategory::Hedging => "hedging",
+            PatternCategory::Stub => "stub",
+            PatternCategory::NamingConvention => "namingconvention",
+            PatternCategory::StaleComment => "stalecomment",
+            PatternCategory::SecurityPlaceholder => "securityplaceholder",
+            PatternCategory::Boilerplate => "boilerplate",
+            PatternCategory::MergeConflict => "mergeconflict",
+        }
+    }
+}
+
+impl PatternCategory {
+    /// Every category, in declaration order.
+    pub const ALL: &'static [PatternCategory] = &[
+        PatternCategory::Placeholder,
+        PatternCategory::Deferral,
+        PatternCategory::Hedging,
+        PatternCategory::Stub,
+        PatternCategory::NamingConvention,
+        PatternCategory::StaleComment,
+        PatternCategory::SecurityPlaceholder,
+        PatternCategory::Boilerplate,
+        PatternCategory::MergeConflict,
+    ];
+
+    /// Short names accepted on the command line besides [`as_str`](Self::as_str).
+    pub fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            PatternCategory::NamingConvention => &["naming"],
+            PatternCategory::StaleComment => &["stale"],
+            PatternCategory::SecurityPlaceholder => &["security"],
+            PatternCategory::Boilerplate => &["chat"],
+            PatternCategory::MergeConflict => &["conflict"],
+            _ => &[],
+        }
+    }
+}
+
+impl std::fmt::Display for PatternCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for PatternCategory {
+    type Err = Error;
+
+    /// Parse a canonical name or alias, ignoring case.
+    fn from_str(s: &str) -> Result<Self> {
+        let name = s.to_lowercase();
+        Self::ALL
+            .iter()
+            .find(|c| c.as_str() == name || c.aliases().contains(&name.as_str()))
+            .cloned()
+            .ok_or_else(|| Error::ConfigInvalid(format!("unknown category '{}'", s)))
+    }
+}
+
+impl clap::ValueEnum for PatternCategory {
+    fn value_variants<'a>() -> &'a [Self] {
+        Self::ALL
+    }
+
+    fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+        Some(
+            clap::builder::PossibleValue::new(self.as_str())
+                .aliases(self.aliases().iter().copied()),
+        )
+    }
+}
+
+/// Which part of a source file a pattern is matched against.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum PatternScope {
+    /// Match only against extracted comments.
+    #[default]
+    Comment,
+    /// Match line-by-line against the full source, including code.
+    Code,
+    /// Match line-by-line against the full source (comments and code alike).
+    Any,
+}
+
+/// Settings for the opt-in magic-number pass, which flags unexplained
+/// numeric literals in return statements and assignments.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct MagicNumbers {
+    /// Literals that are never flagged, written as in source (e.g. `"0.5"`).
+    #[serde(default = "default_magic_number_allowlist")]
+    pub allowlist: Vec<String>,
+}
+
+impl Default for MagicNumbers {
+    fn default() -> Self {
+        Self {
+            allowlist: default_magic_number_allowlist(),
+        }
+    }
+}
+
+fn default_magic_number_allowlist() -> Vec<String> {
+    ["0", "1", "2", "0.0", "1.0", "0.5", "10", "100"]
+        .into_iter()
+        .map(String::from)
+        .collect()
 }
 
 /// A single slop detection pattern.
@@ -175,6 +320,95 @@ pub struct Pattern {
     /// Only used when ast_query is set.
     #[serde(default)]
     pub languages: Vec<String>,
+    /// Where this pattern is matched: comments only (default), code, or anywhere.
+    #[serde(default)]
+    pub scope: PatternScope,
+    /// Informational patterns are reported but add nothing to the score
+    /// and never affect the exit code.
+    #[serde(default)]
+    pub informational: bool,
+    /// Strings this pattern must match; checked by validation.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<String>,
+    /// Strings this pattern must not match; checked by validation.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub counter_examples: Vec<String>,
+    /// Documentation explaining why this pattern flags code, linked from
+    /// human output and SARIF `helpUri`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help_url: Option<String>,
+    /// Match comment-scoped patterns against the comment's whole source line,
+    /// markers included, instead of the stripped comment text. Columns are
+    /// then relative to the line start, so `^\s*//` can match.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub match_raw_line: bool,
+    /// File extensions (e.g. `.html`, `.html.j2`) this pattern is not
+    /// matched in, such as template files where its syntax is legitimate.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_extensions: Vec<String>,
+    /// Set to false to keep a pattern in the config without matching it.
+    #[serde(default = "default_enabled", skip_serializing_if = "is_enabled")]
+    pub enabled: bool,
+}
+
+/// An empty comment-scoped pattern, for filling in the optional fields of
+/// pattern literals.
+impl Default for Pattern {
+    fn default() -> Self {
+        Self {
+            regex: RegexPattern(String::new()),
+            severity: Severity::default(),
+            message: String::new(),
+            category: PatternCategory::default(),
+            ast_query: None,
+            languages: Vec::new(),
+            scope: PatternScope::default(),
+            informational: false,
+            examples: Vec::new(),
+            counter_examples: Vec::new(),
+            help_url: None,
+            match_raw_line: false,
+            exclude_extensions: Vec::new(),
+            enabled: true,
+        }
+    }
+}
+
+impl Pattern {
+    /// Whether this pattern is matched in `path`, i.e. the file name does
+    /// not end with one of `exclude_extensions` (compared ignoring case).
+    pub fn applies_to(&self, path: &Path) -> bool {
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            return true;
+        };
+        let name = name.to_lowercase();
+        !self.exclude_extensions.iter().any(|ext| {
+            let ext = ext.trim_start_matches('.').to_lowercase();
+            name.strip_suffix(&ext)
+                .is_some_and(|stem| stem.ends_with('.'))
+        })
+    }
+
+    /// Compile the regex and check it against `examples` and `counter_examples`.
+    pub fn validate(&self) -> Result<()> {
+        let regex = Regex::new(&self.regex)?;
+
+        if let Some(example) = self.examples.iter().find(|e| !regex.is_match(e)) {
+            return Err(Error::ConfigInvalid(format!(
+                "Pattern '{}' does not match its example '{}'",
+                &*self.regex, example
+            )));
+        }
+
+        if let Some(counter) = self.counter_examples.iter().find(|e| regex.is_match(e)) {
+            return Err(Error::ConfigInvalid(format!(
+                "Pattern '{}' matches its counter-example '{}'",
+                &*self.regex, counter
+            )));
+        }
+
+        Ok(())
+    }
 }
 
 /// Main configuration structure.
@@ -195,22 +429,124 @@ pub struct Config {
     /// Maximum file size to scan in KB.
     #[serde(default = "default_max_file_size")]
     pub max_file_size_kb: u64,
+    /// Maximum directory depth to descend into, where the scanned path
+    /// itself is depth 0. Unlimited when unset.
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+    /// Skip findings in test files (names containing `test`, `spec`, or
+    /// `mock`, plus anything matching `test_file_patterns`).
+    #[serde(default)]
+    pub ignore_test_files: bool,
+    /// Additional glob patterns identifying test files.
+    #[serde(default)]
+    pub test_file_patterns: Vec<String>,
+    /// Display width of a tab when aligning carets in human output.
+    /// The default of 1 treats tabs as single characters.
+    #[serde(default = "default_tab_width")]
+    pub tab_width: usize,
+    /// Regex identifying a ticket or owner reference, e.g. `\([A-Z]+-\d+\)`.
+    /// TODO findings on lines matching it are downgraded to low severity.
+    #[serde(default)]
+    pub todo_ticket_pattern: Option<String>,
+    /// Comments never checked against patterns: an entry matches a comment
+    /// whose trimmed content equals it, or, if written `re:<regex>`, a
+    /// comment the regex matches anywhere.
+    #[serde(default)]
+    pub allowlist: Vec<String>,
+    /// Warn when the baseline file is older than this many days.
+    #[serde(default)]
+    pub baseline_max_age: Option<u64>,
+    /// Template for finding messages in human and JSON output, with
+    /// placeholders `{message}`, `{category}`, `{severity}`, `{file}`,
+    /// `{line}`, and `{id}`.
+    #[serde(default)]
+    pub message_template: Option<String>,
+    /// Enables the magic-number pass when set; usually supplied by the
+    /// `magic-numbers` profile.
+    #[serde(default)]
+    pub magic_numbers: Option<MagicNumbers>,
+    /// Skip comment extraction for languages without a tree-sitter grammar
+    /// instead of falling back to regex extraction.
+    #[serde(default)]
+    pub require_tree_sitter: bool,
+    /// Scan a leading `#!` interpreter line; skipped by default.
+    #[serde(default)]
+    pub scan_shebang: bool,
+    /// Report every match of a pattern in a comment or line instead of only
+    /// the first, e.g. two findings for `TODO and TODO`.
+    #[serde(default)]
+    pub report_all_matches: bool,
+    /// Also match comment patterns against a normalized copy of each comment
+    /// (NFKC, accents stripped, case-folded, unusual spaces made plain).
+    /// Costs a pass per comment, so it is off by default.
+    #[serde(default)]
+    pub normalize_comments: bool,
+    /// Count each distinct match text once per file toward the score, so a
+    /// copy-pasted placeholder is one issue. Every finding is still listed.
+    #[serde(default)]
+    pub dedupe_score_by_match_text: bool,
+    /// Flag function doc comments and docstrings repeated verbatim on at
+    /// least this many functions across the scan, e.g. 3. Off when unset,
+    /// since it extracts each file's comments a second time.
+    #[serde(default)]
+    pub repeated_docstrings: Option<usize>,
+    /// Cache file of each file's findings per category, e.g.
+    /// `.antislop-cache.json`. Later runs rescan only files whose content
+    /// changed and categories whose patterns changed. Off when unset.
+    #[serde(default)]
+    pub incremental_categories: Option<PathBuf>,
+    /// Skip files that look generated or minified (a `@generated` or
+    /// `DO NOT EDIT` header, very long lines, or a huge uncommented file).
+    #[serde(default = "default_skip_generated")]
+    pub skip_generated: bool,
+    /// Severity of filename convention-break findings (default: medium).
+    #[serde(default)]
+    pub filename_convention_severity: Option<Severity>,
+    /// Severity of duplicate-filename findings (default: high).
+    #[serde(default)]
+    pub filename_duplicate_severity: Option<Severity>,
+    /// Extra file extensions mapped to a known language, e.g.
+    /// `bzl = "python"`. Mapped files are scanned with that language's
+    /// comment rules.
+    #[serde(default)]
+    pub extension_languages: BTreeMap<String, String>,
 }
 
+/// Every extension of every code language, plus notebooks and markup.
+/// Prose is opt-in via `--scan-docs`.
 fn default_extensions() -> Vec<String> {
-    vec![
-        ".rs", ".py", ".js", ".ts", ".jsx", ".tsx", ".go", ".java", ".kt", ".c", ".cpp", ".h",
-        ".hpp", ".cs", ".php", ".rb", ".swift", ".dart",
-    ]
-    .into_iter()
-    .map(|s| s.to_string())
-    .collect()
+    use crate::detector::{Language, MARKUP_EXTENSIONS, NOTEBOOK_EXTENSION};
+
+    Language::ALL
+        .iter()
+        .filter(|lang| **lang != Language::Text)
+        .flat_map(|lang| lang.extensions())
+        .chain([&NOTEBOOK_EXTENSION])
+        .chain(MARKUP_EXTENSIONS)
+        .map(|ext| format!(".{}", ext))
+        .collect()
 }
 
 fn default_max_file_size() -> u64 {
     1024
 }
 
+fn default_skip_generated() -> bool {
+    true
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn is_enabled(enabled: &bool) -> bool {
+    *enabled
+}
+
+fn default_tab_width() -> usize {
+    1
+}
+
 impl Default for Config {
     fn default() -> Self {
         let mut base: Config =
@@ -223,6 +559,9 @@ impl Default for Config {
 
 impl Config {
     /// Load configuration from a file.
+    ///
+    /// The format is chosen by extension: `.yaml`/`.yml` and `.json` are
+    /// supported, anything else is parsed as TOML.
     pub fn load(path: &Path) -> Result<Self> {
         let content = fs::read_to_string(path).map_err(|e| {
             Error::ConfigInvalid(format!(
@@ -231,9 +570,11 @@ impl Config {
                 e
             ))
         })?;
-        let config: Self = toml::from_str(&content)
-            .map_err(|e| Error::ConfigInvalid(format!("Parse error: {}", e)))?;
-        Ok(config)
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("yaml") | Some("yml") => Self::from_yaml_str(&content),
+            Some("json") => Self::from_json_str(&content),
+            _ => Self::from_toml_str(&content),
+        }
     }
 
     /// Load from path if it exists, otherwise return default.
@@ -247,10 +588,27 @@ impl Config {
         }
     }
 
+    /// Parse `extension_languages` into scanner language overrides, keyed
+    /// by extension without the leading dot.
+    pub fn language_overrides(&self) -> Result<HashMap<String, crate::detector::Language>> {
+        self.extension_languages
+            .iter()
+            .map(|(ext, name)| {
+                let language = crate::detector::Language::from_name(name).ok_or_else(|| {
+                    Error::ConfigInvalid(format!(
+                        "unknown language '{}' for extension '{}' in extension_languages",
+                        name, ext
+                    ))
+                })?;
+                Ok((ext.trim_start_matches('.').to_string(), language))
+            })
+            .collect()
+    }
+
     /// Validate all regex patterns in the config.
     pub fn validate_patterns(&self) -> Result<()> {
         for pattern in &self.patterns {
-            Regex::new(&pattern.regex).map_err(Error::Regex)?;
+            pattern.validate()?;
         }
         Ok(())
     }
@@ -267,16 +625,96 @@ impl Config {
     ///
     /// This is useful for testing and fuzzing.
     pub fn from_toml_str(content: &str) -> Result<Self> {
-        let config: Self = toml::from_str(content)
-            .map_err(|e| Error::ConfigInvalid(format!("Parse error: {}", e)))?;
+        let config: Self = toml::from_str(content)?;
         Ok(config)
     }
+
+    /// Parse configuration from a YAML string.
+    pub fn from_yaml_str(content: &str) -> Result<Self> {
+        serde_yaml::from_str(content)
+            .map_err(|e| Error::ConfigInvalid(format!("Failed to parse YAML config: {}", e)))
+    }
+
+    /// Parse configuration from a JSON string.
+    pub fn from_json_str(content: &str) -> Result<Self> {
+        serde_json::from_str(content)
+            .map_err(|e| Error::ConfigInvalid(format!("Failed to parse JSON config: {}", e)))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_category_from_str_round_trips() {
+        for category in PatternCategory::ALL {
+            let name = category.to_string();
+            assert_eq!(name.parse::<PatternCategory>().unwrap(), *category);
+            assert_eq!(
+                name.to_uppercase().parse::<PatternCategory>().unwrap(),
+                *category
+            );
+            // The canonical name is the serialized form
+            assert_eq!(
+                serde_json::to_string(category).unwrap(),
+                format!("\"{}\"", name)
+            );
+            for alias in category.aliases() {
+                assert_eq!(alias.parse::<PatternCategory>().unwrap(), *category);
+            }
+        }
+        assert_eq!(
+            "naming".parse::<PatternCategory>().unwrap(),
+            PatternCategory::NamingConvention
+        );
+        assert!("nonsense".parse::<PatternCategory>().is_err());
+    }
+
+    #[test]
+    fn test_extension_languages_map_to_scanner() {
+        use crate::detector::Language;
+        use crate::Scanner;
+
+        let config = Config::from_toml_str(
+            r#"
+            [extension_languages]
+            ".bzl" = "python"
+
+            [[patterns]]
+            regex = "BOOM"
+            severity = "high"
+            message = "boom"
+            category = "stub"
+            "#,
+        )
+        .unwrap();
+        let scanner = Scanner::new(config.patterns.clone())
+            .unwrap()
+            .with_language_overrides(config.language_overrides().unwrap());
+        assert_eq!(
+            scanner.resolve_language(Path::new("defs.bzl")),
+            Language::Python
+        );
+
+        // Python comment rules: `#` starts a comment, `//` inside a string does not
+        let source = "x = 7 // 2  # BOOM\ny = \"// BOOM\"\n";
+        let lines = |path: &str| -> Vec<usize> {
+            scanner
+                .scan_file(path, source)
+                .findings
+                .iter()
+                .map(|f| f.line)
+                .collect()
+        };
+        assert_eq!(lines("defs.bzl"), lines("defs.py"));
+        #[cfg(feature = "python")]
+        assert_eq!(lines("defs.bzl"), vec![1]);
+
+        let bad = Config::from_toml_str("[extension_languages]\nbzl = \"starlark\"\n").unwrap();
+        assert!(bad.language_overrides().is_err());
+    }
+
     #[test]
     fn test_default_config_loads() {
         let config = Config::default();
@@ -347,7 +785,7 @@ mod tests {
             file_extensions = [".py"]
         "#; // Missing closing bracket
         let result = Config::from_toml_str(toml);
-        assert!(result.is_err());
+        assert!(matches!(result, Err(Error::Config(_))));
     }
 
     #[test]
@@ -370,6 +808,33 @@ mod tests {
         assert_eq!(Severity::Critical.as_str(), "CRITICAL");
     }
 
+    #[test]
+    fn test_display_matches_serde_names() {
+        for severity in [
+            Severity::Low,
+            Severity::Medium,
+            Severity::High,
+            Severity::Critical,
+        ] {
+            let serialized = serde_json::to_value(&severity).unwrap();
+            assert_eq!(serialized.as_str(), Some(severity.to_string().as_str()));
+        }
+        for category in [
+            PatternCategory::Placeholder,
+            PatternCategory::Deferral,
+            PatternCategory::Hedging,
+            PatternCategory::Stub,
+            PatternCategory::NamingConvention,
+            PatternCategory::StaleComment,
+            PatternCategory::SecurityPlaceholder,
+            PatternCategory::Boilerplate,
+            PatternCategory::MergeConflict,
+        ] {
+            let serialized = serde_json::to_value(&category).unwrap();
+            assert_eq!(serialized.as_str(), Some(category.to_string().as_str()));
+        }
+    }
+
     #[test]
     fn test_regex_pattern_new() {
         assert!(RegexPattern::new("(?i)test".to_string()).is_ok());
@@ -390,6 +855,30 @@ mod tests {
         assert_eq!(back, "test");
     }
 
+    #[test]
+    fn test_invalid_regex_in_toml_fails_to_load() {
+        let toml = r#"
+            [[patterns]]
+            regex = "(?i)TODO:("
+            severity = "medium"
+            message = "TODO found"
+            category = "placeholder"
+        "#;
+        let err = Config::from_toml_str(toml).unwrap_err();
+        assert!(matches!(err, Error::Config(_)));
+        assert!(err.to_string().contains("regex"));
+    }
+
+    #[test]
+    fn test_default_extensions_cover_languages() {
+        let extensions = Config::default().file_extensions;
+        assert_eq!(extensions, default_extensions());
+        for ext in [".rs", ".sh", ".ipynb", ".htm", ".svelte"] {
+            assert!(extensions.contains(&ext.to_string()), "{} missing", ext);
+        }
+        assert!(!extensions.contains(&".md".to_string()));
+    }
+
     #[test]
     fn test_patterns_for_category() {
         let config = Config::default();
@@ -411,4 +900,94 @@ mod tests {
         let config = Config::load_or_default(Some(Path::new("/nonexistent/path.toml")));
         assert!(!config.patterns.is_empty());
     }
+
+    #[test]
+    fn test_validate_patterns_checks_examples() {
+        let config = Config::from_toml_str(
+            r##"
+[[patterns]]
+regex = "(?i)TODO:"
+message = "TODO marker"
+examples = ["# TODO: fix", "todo: later"]
+counter_examples = ["TODO list"]
+"##,
+        )
+        .unwrap();
+        assert!(config.validate_patterns().is_ok());
+
+        let config = Config::from_toml_str(
+            r#"
+[[patterns]]
+regex = "(?i)TODO:"
+message = "TODO marker"
+examples = ["FIXME: wrong marker"]
+"#,
+        )
+        .unwrap();
+        let err = config.validate_patterns().unwrap_err();
+        assert!(err.to_string().contains("FIXME: wrong marker"));
+    }
+
+    #[test]
+    fn test_load_toml_yaml_json_equivalent() {
+        let temp = tempfile::TempDir::new().unwrap();
+
+        let toml_path = temp.path().join("antislop.toml");
+        fs::write(
+            &toml_path,
+            r#"
+file_extensions = [".py"]
+max_file_size_kb = 64
+
+[[patterns]]
+regex = "(?i)TODO:"
+severity = "high"
+message = "TODO marker"
+category = "placeholder"
+"#,
+        )
+        .unwrap();
+
+        let yaml_path = temp.path().join("antislop.yaml");
+        fs::write(
+            &yaml_path,
+            r#"
+file_extensions: [".py"]
+max_file_size_kb: 64
+patterns:
+  - regex: "(?i)TODO:"
+    severity: high
+    message: TODO marker
+    category: placeholder
+"#,
+        )
+        .unwrap();
+
+        let json_path = temp.path().join("antislop.json");
+        fs::write(
+            &json_path,
+            r#"{
+  "file_extensions": [".py"],
+  "max_file_size_kb": 64,
+  "patterns": [
+    {"regex": "(?i)TODO:", "severity": "high", "message": "TODO marker", "category": "placeholder"}
+  ]
+}"#,
+        )
+        .unwrap();
+
+        let as_value = |path: &Path| serde_json::to_value(Config::load(path).unwrap()).unwrap();
+        let from_toml = as_value(&toml_path);
+        assert_eq!(from_toml, as_value(&yaml_path));
+        assert_eq!(from_toml, as_value(&json_path));
+        assert_eq!(from_toml["max_file_size_kb"], 64);
+    }
+
+    #[test]
+    fn test_load_invalid_yaml_is_config_error() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("antislop.yml");
+        fs::write(&path, "patterns: [unclosed").unwrap();
+        assert!(matches!(Config::load(&path), Err(Error::ConfigInvalid(_))));
+    }
 }
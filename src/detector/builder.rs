@@ -0,0 +1,228 @@
+//! Fluent construction of a configured [`Scanner`].
+//!
+//! [`Scanner::new`] stays the plain default; the builder collects the
+//! optional matching behavior and applies it in one fallible `build()`, so
+//! invalid globs or regexes surface there rather than midway through a
+//! chain of `with_*` calls.
+
+use super::{FindingTransformer, Language, Scanner};
+use crate::config::{MagicNumbers, Pattern, Severity};
+use crate::Result;
+use std::collections::HashMap;
+
+/// Builder for a [`Scanner`], created with [`Scanner::builder`].
+///
+/// Every setter maps to the `Scanner::with_*` method of the same meaning;
+/// unset options keep the `Scanner::new` defaults.
+pub struct ScannerBuilder {
+    patterns: Vec<Pattern>,
+    detect_stale_comments: bool,
+    magic_numbers: Option<MagicNumbers>,
+    require_tree_sitter: bool,
+    scan_shebang: bool,
+    report_all_matches: bool,
+    normalize_comments: bool,
+    dedupe_score_by_match_text: bool,
+    skip_blank_context: bool,
+    score_weights: HashMap<Severity, u32>,
+    test_file_patterns: Option<Vec<String>>,
+    language_overrides: HashMap<String, Language>,
+    todo_ticket_pattern: Option<String>,
+    comment_allowlist: Vec<String>,
+    transformers: Vec<FindingTransformer>,
+}
+
+impl ScannerBuilder {
+    pub(super) fn new(patterns: Vec<Pattern>) -> Self {
+        Self {
+            patterns,
+            detect_stale_comments: false,
+            magic_numbers: None,
+            require_tree_sitter: false,
+            scan_shebang: false,
+            report_all_matches: false,
+            normalize_comments: false,
+            dedupe_score_by_match_text: false,
+            skip_blank_context: false,
+            score_weights: HashMap::new(),
+            test_file_patterns: None,
+            language_overrides: HashMap::new(),
+            todo_ticket_pattern: None,
+            comment_allowlist: Vec::new(),
+            transformers: Vec::new(),
+        }
+    }
+
+    /// See [`Scanner::with_stale_comment_detection`].
+    pub fn detect_stale_comments(mut self, enabled: bool) -> Self {
+        self.detect_stale_comments = enabled;
+        self
+    }
+
+    /// See [`Scanner::with_magic_number_detection`].
+    pub fn magic_numbers(mut self, settings: Option<MagicNumbers>) -> Self {
+        self.magic_numbers = settings;
+        self
+    }
+
+    /// See [`Scanner::with_tree_sitter_required`].
+    pub fn require_tree_sitter(mut self, required: bool) -> Self {
+        self.require_tree_sitter = required;
+        self
+    }
+
+    /// See [`Scanner::with_shebang_scanning`].
+    pub fn scan_shebang(mut self, enabled: bool) -> Self {
+        self.scan_shebang = enabled;
+        self
+    }
+
+    /// See [`Scanner::with_all_matches`].
+    pub fn report_all_matches(mut self, enabled: bool) -> Self {
+        self.report_all_matches = enabled;
+        self
+    }
+
+    /// See [`Scanner::with_comment_normalization`].
+    pub fn normalize_comments(mut self, enabled: bool) -> Self {
+        self.normalize_comments = enabled;
+        self
+    }
+
+    /// See [`Scanner::with_score_dedup_by_match_text`].
+    pub fn dedupe_score_by_match_text(mut self, enabled: bool) -> Self {
+        self.dedupe_score_by_match_text = enabled;
+        self
+    }
+
+    /// See [`Scanner::with_blank_context_skipped`].
+    pub fn skip_blank_context(mut self, enabled: bool) -> Self {
+        self.skip_blank_context = enabled;
+        self
+    }
+
+    /// See [`Scanner::with_score_weights`].
+    pub fn score_weights(mut self, weights: HashMap<Severity, u32>) -> Self {
+        self.score_weights = weights;
+        self
+    }
+
+    /// See [`Scanner::with_test_files_ignored`]; invalid globs fail `build`.
+    pub fn ignore_test_files(mut self, patterns: &[String]) -> Self {
+        self.test_file_patterns = Some(patterns.to_vec());
+        self
+    }
+
+    /// See [`Scanner::with_language_overrides`].
+    pub fn language_overrides(mut self, overrides: HashMap<String, Language>) -> Self {
+        self.language_overrides = overrides;
+        self
+    }
+
+    /// See [`Scanner::with_todo_ticket_pattern`]; an invalid regex fails
+    /// `build`.
+    pub fn todo_ticket_pattern(mut self, pattern: &str) -> Self {
+        self.todo_ticket_pattern = Some(pattern.to_string());
+        self
+    }
+
+    /// See [`Scanner::with_comment_allowlist`]; an invalid `re:` entry fails
+    /// `build`.
+    pub fn comment_allowlist(mut self, entries: &[String]) -> Self {
+        self.comment_allowlist = entries.to_vec();
+        self
+    }
+
+    /// See [`Scanner::with_finding_transformer`]; transformers run in the
+    /// order they are added.
+    pub fn finding_transformer(mut self, transformer: FindingTransformer) -> Self {
+        self.transformers.push(transformer);
+        self
+    }
+
+    /// Compile the patterns and apply the configured options.
+    pub fn build(self) -> Result<Scanner> {
+        let mut scanner = Scanner::new(self.patterns)?
+            .with_stale_comment_detection(self.detect_stale_comments)
+            .with_magic_number_detection(self.magic_numbers)
+            .with_tree_sitter_required(self.require_tree_sitter)
+            .with_shebang_scanning(self.scan_shebang)
+            .with_all_matches(self.report_all_matches)
+            .with_comment_normalization(self.normalize_comments)
+            .with_score_dedup_by_match_text(self.dedupe_score_by_match_text)
+            .with_blank_context_skipped(self.skip_blank_context)
+            .with_score_weights(self.score_weights)
+            .with_language_overrides(self.language_overrides)
+            .with_comment_allowlist(&self.comment_allowlist)?;
+        if let Some(patterns) = &self.test_file_patterns {
+            scanner = scanner.with_test_files_ignored(patterns)?;
+        }
+        if let Some(pattern) = &self.todo_ticket_pattern {
+            scanner = scanner.with_todo_ticket_pattern(pattern)?;
+        }
+        for transformer in self.transformers {
+            scanner = scanner.with_finding_transformer(transformer);
+        }
+        Ok(scanner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{PatternCategory, RegexPattern};
+    use crate::Error;
+
+    fn todo_pattern() -> Pattern {
+        Pattern {
+            regex: RegexPattern::new("(?i)TODO".to_string()).unwrap(),
+            severity: Severity::Medium,
+            message: "TODO".to_string(),
+            category: PatternCategory::Placeholder,
+            ast_query: None,
+            languages: vec![],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_builder_configures_matching() {
+        let code = "# TODO: a TODO twice\n# TODO(ABC-1): tracked\n";
+
+        let plain = Scanner::builder(vec![todo_pattern()]).build().unwrap();
+        assert_eq!(plain.scan_file("app.py", code).findings.len(), 2);
+
+        let scanner = Scanner::builder(vec![todo_pattern()])
+            .report_all_matches(true)
+            .todo_ticket_pattern(r"\(ABC-\d+\)")
+            .score_weights(HashMap::from([(Severity::Medium, 10)]))
+            .build()
+            .unwrap();
+        let result = scanner.scan_file("app.py", code);
+        let severities: Vec<Severity> =
+            result.findings.iter().map(|f| f.severity.clone()).collect();
+        assert_eq!(
+            severities,
+            vec![Severity::Medium, Severity::Medium, Severity::Low]
+        );
+        assert_eq!(result.score, 10 + 10 + Severity::Low.score());
+    }
+
+    #[test]
+    fn test_builder_ignores_test_files_and_reports_bad_options() {
+        let scanner = Scanner::builder(vec![todo_pattern()])
+            .ignore_test_files(&["fixtures/**".to_string()])
+            .build()
+            .unwrap();
+        assert!(scanner
+            .scan_file("fixtures/app.py", "# TODO: x\n")
+            .findings
+            .is_empty());
+        assert_eq!(scanner.scan_file("app.py", "# TODO: x\n").findings.len(), 1);
+
+        let invalid = Scanner::builder(vec![todo_pattern()])
+            .todo_ticket_pattern("(unclosed")
+            .build();
+        assert!(matches!(invalid, Err(Error::ConfigInvalid(_))));
+    }
+}
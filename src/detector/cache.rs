@@ -0,0 +1,361 @@
+//! Per-category caching of scan results.
+//!
+//! Each file's findings are cached per pattern category together with a
+//! fingerprint of that category's patterns. When the pattern set changes,
+//! only categories whose fingerprint changed are rescanned; the rest reuse
+//! their cached findings.
+//!
+//! The cache can be saved to and loaded from a JSON file, which the CLI does
+//! when `incremental_categories` is set.
+
+use super::{FileScanResult, Finding, Scanner, Span};
+use crate::config::PatternCategory;
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::Path;
+
+/// Cached findings for one file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    /// Hash of the file content and anything else that affects every category.
+    key: u64,
+    /// Pattern fingerprint and raw findings, per category.
+    categories: HashMap<PatternCategory, (u64, Vec<CachedFinding>)>,
+    /// Non-blank lines scanned, from the last rescan.
+    lines: usize,
+}
+
+/// A finding together with its span, which [`Finding`] leaves out of its
+/// serialized form.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedFinding {
+    #[serde(flatten)]
+    finding: Finding,
+    span: Option<Span>,
+}
+
+impl From<Finding> for CachedFinding {
+    fn from(finding: Finding) -> Self {
+        let span = finding.span;
+        Self { finding, span }
+    }
+}
+
+impl From<&CachedFinding> for Finding {
+    fn from(cached: &CachedFinding) -> Self {
+        Finding {
+            span: cached.span,
+            ..cached.finding.clone()
+        }
+    }
+}
+
+/// Scan cache keyed by file path and pattern category.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryCache {
+    /// antislop version that wrote the cache; other versions start empty,
+    /// since fingerprints are only stable within one build.
+    version: String,
+    entries: HashMap<String, CacheEntry>,
+}
+
+impl Default for CategoryCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Result of a cached scan.
+#[derive(Debug, Clone)]
+pub struct CachedScan {
+    /// Findings for the file, as [`Scanner::scan_file`] would return them.
+    pub result: FileScanResult,
+    /// Categories that were actually rescanned rather than served from cache.
+    pub rescanned: Vec<PatternCategory>,
+}
+
+impl CategoryCache {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Load a cache saved by [`save`](Self::save).
+    ///
+    /// A missing file, or one that is unreadable as a cache or was written by
+    /// another antislop version, gives an empty cache.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::new()),
+            Err(source) => {
+                return Err(Error::CacheIo {
+                    path: path.to_path_buf(),
+                    source,
+                })
+            }
+        };
+        Ok(serde_json::from_str::<Self>(&content)
+            .ok()
+            .filter(|cache| cache.version == env!("CARGO_PKG_VERSION"))
+            .unwrap_or_default())
+    }
+
+    /// Write the cache to `path` as JSON.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content =
+            serde_json::to_string(self).map_err(|e| Error::Serialization(e.to_string()))?;
+        fs::write(path, content).map_err(|source| Error::CacheIo {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Number of files with cached results.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if nothing is cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Scan a file, rescanning only categories whose patterns changed since
+    /// the file was last cached. A change in content rescans everything.
+    pub fn scan_file(&mut self, scanner: &Scanner, path: &str, content: &str) -> CachedScan {
+        let key = file_key(scanner, path, content);
+        let fingerprints = scanner.category_fingerprints();
+
+        let entry = self
+            .entries
+            .entry(path.to_string())
+            .or_insert_with(|| CacheEntry {
+                key,
+                categories: HashMap::new(),
+                lines: 0,
+            });
+        if entry.key != key {
+            entry.key = key;
+            entry.categories.clear();
+        }
+        entry
+            .categories
+            .retain(|category, _| fingerprints.contains_key(category));
+
+        let mut rescanned: Vec<PatternCategory> = fingerprints
+            .iter()
+            .filter(|(category, fingerprint)| {
+                entry
+                    .categories
+                    .get(*category)
+                    .is_none_or(|(cached, _)| cached != *fingerprint)
+            })
+            .map(|(category, _)| category.clone())
+            .collect();
+        rescanned.sort_by_key(|c| c.as_str());
+
+        if !rescanned.is_empty() {
+            let fresh = scanner.scan_file_categories(path, content, &rescanned);
+            entry.lines = fresh.lines;
+            for category in &rescanned {
+                let findings = fresh
+                    .findings
+                    .iter()
+                    .filter(|f| f.category == *category)
+                    .cloned()
+                    .map(CachedFinding::from)
+                    .collect();
+                entry
+                    .categories
+                    .insert(category.clone(), (fingerprints[category], findings));
+            }
+        }
+
+        let mut findings: Vec<Finding> = entry
+            .categories
+            .values()
+            .flat_map(|(_, findings)| findings.iter().map(Finding::from))
+            .collect();
+        findings.sort_by_key(|f| (f.line, f.column));
+
+        let result = scanner.finish(FileScanResult {
+            path: path.to_string(),
+            findings,
+            score: 0,
+            lines: entry.lines,
+        });
+        CachedScan { result, rescanned }
+    }
+}
+
+impl Scanner {
+    /// Fingerprint of the patterns in each category.
+    ///
+    /// Two scanners produce the same fingerprint for a category exactly when
+    /// they would match that category's patterns identically.
+    pub fn category_fingerprints(&self) -> HashMap<PatternCategory, u64> {
+        let mut hashers: HashMap<PatternCategory, DefaultHasher> = HashMap::new();
+        for compiled in &self.registry.patterns {
+            let pattern = &compiled.pattern;
+            let hasher = hashers.entry(pattern.category.clone()).or_default();
+            pattern.regex.hash(hasher);
+            pattern.severity.hash(hasher);
+            pattern.message.hash(hasher);
+            pattern.ast_query.hash(hasher);
+            pattern.languages.hash(hasher);
+            pattern.scope.hash(hasher);
+            pattern.informational.hash(hasher);
+            pattern.match_raw_line.hash(hasher);
+            pattern.exclude_extensions.hash(hasher);
+            pattern.help_url.hash(hasher);
+        }
+        if self.detect_stale_comments {
+            hashers.entry(PatternCategory::StaleComment).or_default();
+        }
+        if let Some(settings) = &self.magic_numbers {
+            settings.hash(hashers.entry(PatternCategory::Stub).or_default());
+        }
+        hashers
+            .into_iter()
+            .map(|(category, hasher)| (category, hasher.finish()))
+            .collect()
+    }
+}
+
+/// Hash of everything that affects findings in every category.
+fn file_key(scanner: &Scanner, path: &str, content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    scanner.resolve_language(Path::new(path)).hash(&mut hasher);
+    scanner
+        .is_ignored_test_file(Path::new(path))
+        .hash(&mut hasher);
+    scanner.require_tree_sitter.hash(&mut hasher);
+    scanner.scan_shebang.hash(&mut hasher);
+    scanner.report_all_matches.hash(&mut hasher);
+    scanner.normalize_comments.hash(&mut hasher);
+    scanner.skip_blank_context.hash(&mut hasher);
+    for (entry, _) in &scanner.comment_allowlist {
+        entry.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Pattern, RegexPattern, Severity};
+
+    fn pattern(regex: &str, category: PatternCategory) -> Pattern {
+        Pattern {
+            regex: RegexPattern::new(regex.to_string()).unwrap(),
+            severity: Severity::Medium,
+            message: regex.to_string(),
+            category,
+            ast_query: None,
+            languages: vec![],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_changed_category_reuses_other_categories() {
+        let code = "# TODO: fix this\n# temporary workaround for now\n";
+        let mut cache = CategoryCache::new();
+
+        let scanner = Scanner::new(vec![
+            pattern("(?i)TODO:", PatternCategory::Placeholder),
+            pattern("(?i)for now", PatternCategory::Deferral),
+        ])
+        .unwrap();
+        let first = cache.scan_file(&scanner, "test.py", code);
+        assert_eq!(first.rescanned.len(), 2);
+        assert_eq!(first.result.findings.len(), 2);
+
+        // Only the deferral pattern changes
+        let scanner = Scanner::new(vec![
+            pattern("(?i)TODO:", PatternCategory::Placeholder),
+            pattern("(?i)temporary", PatternCategory::Deferral),
+        ])
+        .unwrap();
+        let second = cache.scan_file(&scanner, "test.py", code);
+        assert_eq!(second.rescanned, vec![PatternCategory::Deferral]);
+        assert_eq!(second.result.findings.len(), 2);
+        assert_eq!(second.result.findings[1].match_text, "temporary");
+
+        // Nothing changed
+        let third = cache.scan_file(&scanner, "test.py", code);
+        assert!(third.rescanned.is_empty());
+        assert_eq!(third.result.score, second.result.score);
+
+        // A new help URL is carried on the findings, so it rescans too
+        let scanner = Scanner::new(vec![
+            Pattern {
+                help_url: Some("https://example.com/todo".to_string()),
+                ..pattern("(?i)TODO:", PatternCategory::Placeholder)
+            },
+            pattern("(?i)temporary", PatternCategory::Deferral),
+        ])
+        .unwrap();
+        let fourth = cache.scan_file(&scanner, "test.py", code);
+        assert_eq!(fourth.rescanned, vec![PatternCategory::Placeholder]);
+        assert_eq!(
+            fourth.result.findings[0].help_url.as_deref(),
+            Some("https://example.com/todo")
+        );
+    }
+
+    #[test]
+    fn test_changed_content_rescans_everything() {
+        let scanner = Scanner::new(vec![
+            pattern("(?i)TODO:", PatternCategory::Placeholder),
+            pattern("(?i)for now", PatternCategory::Deferral),
+        ])
+        .unwrap();
+        let mut cache = CategoryCache::new();
+        cache.scan_file(&scanner, "test.py", "# TODO: one\n");
+        let rescan = cache.scan_file(&scanner, "test.py", "# TODO: two\n");
+        assert_eq!(rescan.rescanned.len(), 2);
+        assert_eq!(rescan.result.findings[0].line, 1);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_saved_cache_round_trips() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("cache.json");
+        let scanner =
+            Scanner::new(vec![pattern("(?i)TODO:", PatternCategory::Placeholder)]).unwrap();
+
+        let mut cache = CategoryCache::load(&path).unwrap();
+        assert!(cache.is_empty());
+        let first = cache.scan_file(&scanner, "test.py", "# TODO: fix\n");
+        cache.save(&path).unwrap();
+
+        let mut cache = CategoryCache::load(&path).unwrap();
+        let second = cache.scan_file(&scanner, "test.py", "# TODO: fix\n");
+        assert!(second.rescanned.is_empty());
+        assert_eq!(second.result.findings.len(), 1);
+        assert_eq!(
+            second.result.findings[0].span,
+            first.result.findings[0].span
+        );
+        assert!(second.result.findings[0].span.is_some());
+
+        // A cache from another version is ignored
+        let stale = fs::read_to_string(&path)
+            .unwrap()
+            .replace(env!("CARGO_PKG_VERSION"), "0.0.0-old");
+        fs::write(&path, stale).unwrap();
+        assert!(CategoryCache::load(&path).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,80 @@
+//! Comment directives that disable scanning for a range of lines.
+//!
+//! A line containing `antislop:disable` starts a disabled range and a later
+//! line containing `antislop:enable` ends it. Directives are matched on the
+//! raw line text, so they work in any language's comment syntax. A range
+//! left open runs to the end of the file.
+
+/// Disabled line ranges of a source file, 1-indexed and inclusive.
+#[derive(Debug, Default)]
+pub(crate) struct DisabledRanges {
+    ranges: Vec<(usize, usize)>,
+}
+
+impl DisabledRanges {
+    /// Find the disabled ranges in `source`.
+    pub fn parse(source: &str) -> Self {
+        let mut ranges = Vec::new();
+        let mut start = None;
+        for (idx, line) in source.lines().enumerate() {
+            let line_no = idx + 1;
+            if start.is_none() && has_directive(line, "antislop:disable") {
+                start = Some(line_no);
+            } else if has_directive(line, "antislop:enable") {
+                if let Some(from) = start.take() {
+                    ranges.push((from, line_no));
+                }
+            }
+        }
+        if let Some(from) = start {
+            ranges.push((from, usize::MAX));
+        }
+        Self { ranges }
+    }
+
+    /// Returns true if no lines are disabled.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns true if `line` falls within a disabled range.
+    pub fn contains(&self, line: usize) -> bool {
+        self.ranges
+            .iter()
+            .any(|&(from, to)| (from..=to).contains(&line))
+    }
+}
+
+/// Whether `line` contains `directive` as a whole word.
+fn has_directive(line: &str, directive: &str) -> bool {
+    line.match_indices(directive).any(|(idx, _)| {
+        !line[idx + directive.len()..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_ranges() {
+        let source = "a\n// antislop:disable\nb\n// antislop:enable\nc\n# antislop:disable\nd\n";
+        let ranges = DisabledRanges::parse(source);
+        assert!(!ranges.contains(1));
+        assert!(ranges.contains(2));
+        assert!(ranges.contains(3));
+        assert!(ranges.contains(4));
+        assert!(!ranges.contains(5));
+        // Unterminated range runs to the end of the file
+        assert!(ranges.contains(7));
+    }
+
+    #[test]
+    fn test_directive_must_be_whole_word() {
+        let ranges = DisabledRanges::parse("// antislop:disabled\nx\n");
+        assert!(ranges.is_empty());
+    }
+}
@@ -0,0 +1,281 @@
+//! Comment marker stripping shared by the tree-sitter and regex extractors.
+//!
+//! Both extractors pass raw comment text through [`strip_comment_markers`],
+//! so a comment yields the same content whichever extractor found it.
+
+use super::{Comment, CommentKind};
+
+/// Characters that open a line comment in the supported languages: `//`,
+/// `#`, `--`, `%` (LaTeX/Erlang), `;` (Lisp/asm), and `'` (VB). Runs such as
+/// `///`, `;;;` and `%%` are stripped whole. No supported language opens a
+/// line comment with `"`, so a quote starting the comment text is kept.
+pub(crate) const LINE_MARKER_CHARS: &[char] = &['/', '#', '-', '%', ';', '\''];
+
+/// Block comment delimiters, tried in order before line markers.
+pub(crate) const BLOCK_MARKERS: &[(&str, &str)] = &[
+    ("/*", "*/"),
+    ("{-", "-}"),
+    ("--[[", "]]"),
+    ("<!--", "-->"),
+    ("(*", "*)"),
+    ("#|", "|#"),
+    ("\"\"\"", "\"\"\""),
+    ("'''", "'''"),
+];
+
+/// Strip comment delimiters and surrounding whitespace from raw comment text.
+///
+/// Text without a recognized marker is returned trimmed.
+pub(crate) fn strip_comment_markers(text: &str) -> &str {
+    let text = text.trim();
+    for (open, close) in BLOCK_MARKERS {
+        if let Some(rest) = text.strip_prefix(open) {
+            return rest.strip_suffix(close).unwrap_or(rest).trim();
+        }
+    }
+    text.trim_start_matches(LINE_MARKER_CHARS).trim()
+}
+
+/// Join runs of doc comments into one comment at the first one's position.
+///
+/// A doc comment joins the previous one when it starts on the line after
+/// the previous one ends, at the same column, and opens with the same
+/// marker, so `///` and `//!` runs stay apart. Content lines are joined
+/// with `\n`; [`Comment::position_of`] maps offsets back to source lines.
+pub(crate) fn join_doc_lines(comments: Vec<Comment>, source: &str) -> Vec<Comment> {
+    let lines: Vec<&str> = source.lines().collect();
+    let marker = |comment: &Comment| -> &str {
+        let raw = lines
+            .get(comment.line.saturating_sub(1))
+            .and_then(|line| line.get(comment.column.saturating_sub(1)..))
+            .unwrap_or("");
+        let end = raw
+            .find(|c: char| c.is_whitespace() || c.is_alphanumeric())
+            .unwrap_or(raw.len());
+        &raw[..end]
+    };
+
+    let mut joined: Vec<Comment> = Vec::new();
+    // End line and marker of the last joined comment
+    let mut last: Option<(usize, &str)> = None;
+    for comment in comments {
+        let end_line = comment.line + comment.content.matches('\n').count();
+        if comment.kind != CommentKind::Doc {
+            joined.push(comment);
+            last = None;
+            continue;
+        }
+        let current = marker(&comment);
+        match (joined.last_mut(), last) {
+            (Some(prev), Some((line, prev_marker)))
+                if comment.line == line + 1
+                    && comment.column == prev.column
+                    && current == prev_marker =>
+            {
+                prev.content.push('\n');
+                prev.content.push_str(&comment.content);
+            }
+            _ => joined.push(comment),
+        }
+        last = Some((end_line, current));
+    }
+    joined
+}
+
+/// Words that may come before a function keyword or a method's return type.
+const DECLARATION_MODIFIERS: &[&str] = &[
+    "pub",
+    "export",
+    "default",
+    "async",
+    "const",
+    "unsafe",
+    "extern",
+    "static",
+    "public",
+    "private",
+    "protected",
+    "internal",
+    "final",
+    "abstract",
+    "override",
+    "virtual",
+    "inline",
+    "synchronized",
+    "suspend",
+    "open",
+];
+
+/// Keywords that open a function declaration.
+const FUNCTION_KEYWORDS: &[&str] = &["fn", "function", "function*", "func", "fun", "def"];
+
+/// Keywords that open something other than a function, even when a
+/// parenthesis follows, as in a tuple struct or `if (...)`.
+const NON_FUNCTION_KEYWORDS: &[&str] = &[
+    "struct",
+    "enum",
+    "union",
+    "class",
+    "interface",
+    "trait",
+    "impl",
+    "type",
+    "mod",
+    "macro_rules!",
+    "if",
+    "for",
+    "while",
+    "switch",
+    "match",
+    "return",
+    "new",
+    "let",
+    "var",
+    "use",
+    "import",
+];
+
+/// Whether an outer doc comment (`///` or `/** */`) documents a function:
+/// the first line after it, past attributes, annotations and decorators,
+/// declares one. Inner docs (`//!`, `/*!`) describe the enclosing module and
+/// docstrings follow their function, so neither counts.
+pub(crate) fn documents_function(doc: &Comment, lines: &[&str]) -> bool {
+    let start = lines
+        .get(doc.line.saturating_sub(1))
+        .and_then(|line| line.get(doc.column.saturating_sub(1)..))
+        .unwrap_or("");
+    let mut following = lines.iter().copied().skip(doc.line);
+    let item = if start.starts_with("///") && !start.starts_with("////") {
+        following.nth(doc.content.matches('\n').count())
+    } else if let Some(body) = start.strip_prefix("/**") {
+        // Code may follow the closing `*/` on the same line
+        let end = match body.find("*/") {
+            Some(pos) => Some(&body[pos + 2..]),
+            None => following
+                .by_ref()
+                .find_map(|line| line.find("*/").map(|pos| &line[pos + 2..])),
+        };
+        end.filter(|rest| !rest.trim().is_empty())
+            .or_else(|| following.next())
+    } else {
+        return false;
+    };
+    let attribute = |line: &str| {
+        let line = line.trim_start();
+        line.starts_with("#[") || line.starts_with('@') || line.starts_with('[')
+    };
+    item.into_iter()
+        .chain(following)
+        .find(|line| !attribute(line))
+        .is_some_and(declares_function)
+}
+
+/// Whether a line of code opens a function or method declaration.
+fn declares_function(line: &str) -> bool {
+    let mut words = line.split_whitespace().skip_while(|word| {
+        DECLARATION_MODIFIERS.contains(word) || word.starts_with("pub(") || word.starts_with('"')
+    });
+    let Some(first) = words.next() else {
+        return false;
+    };
+    let keyword = first.split(['(', '<']).next().unwrap_or(first);
+    if FUNCTION_KEYWORDS.contains(&keyword) {
+        return true;
+    }
+    if NON_FUNCTION_KEYWORDS.contains(&keyword) {
+        return false;
+    }
+    // A method or C-style signature, `name(...)` or `Type name(...)`, but
+    // not an enum variant `Name(u8),` or an assignment
+    let Some(paren) = line.find('(') else {
+        return false;
+    };
+    let head = line[..paren].trim_end();
+    head.ends_with(|c: char| c.is_alphanumeric() || c == '_')
+        && !head.contains('=')
+        && !line.trim_end().ends_with(',')
+}
+
+/// Classify raw comment text, including its markers, by its opening marker.
+pub(crate) fn comment_kind(raw: &str) -> CommentKind {
+    let raw = raw.trim_start();
+    let is_doc = (raw.starts_with("///") && !raw.starts_with("////"))
+        || raw.starts_with("//!")
+        || (raw.starts_with("/**") && !raw.starts_with("/**/"))
+        || raw.starts_with("/*!")
+        || raw.starts_with("\"\"\"")
+        || raw.starts_with("'''");
+    if is_doc {
+        CommentKind::Doc
+    } else if BLOCK_MARKERS.iter().any(|(open, _)| raw.starts_with(open)) {
+        CommentKind::Block
+    } else {
+        CommentKind::Line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_comment_markers() {
+        assert_eq!(strip_comment_markers("// TODO: x"), "TODO: x");
+        assert_eq!(strip_comment_markers("/// doc"), "doc");
+        assert_eq!(strip_comment_markers("# note"), "note");
+        assert_eq!(strip_comment_markers("-- lua"), "lua");
+        assert_eq!(strip_comment_markers(";;; lisp"), "lisp");
+        assert_eq!(strip_comment_markers("% latex"), "latex");
+        assert_eq!(strip_comment_markers("' vb"), "vb");
+        assert_eq!(
+            strip_comment_markers("# \"quoted\" name"),
+            "\"quoted\" name"
+        );
+        assert_eq!(strip_comment_markers("/* block */"), "block");
+        assert_eq!(strip_comment_markers("{- haskell -}"), "haskell");
+        assert_eq!(strip_comment_markers("--[[ lua block ]]"), "lua block");
+        assert_eq!(strip_comment_markers("(* ocaml *)"), "ocaml");
+        assert_eq!(strip_comment_markers("plain"), "plain");
+    }
+
+    #[test]
+    fn test_comment_kind() {
+        assert_eq!(comment_kind("/// doc"), CommentKind::Doc);
+        assert_eq!(comment_kind("//! crate doc"), CommentKind::Doc);
+        assert_eq!(comment_kind("/** doc */"), CommentKind::Doc);
+        assert_eq!(comment_kind("\"\"\"Docstring.\"\"\""), CommentKind::Doc);
+        assert_eq!(comment_kind("//// banner"), CommentKind::Line);
+        assert_eq!(comment_kind("/**/"), CommentKind::Block);
+        assert_eq!(comment_kind("/* block */"), CommentKind::Block);
+        assert_eq!(comment_kind("# note"), CommentKind::Line);
+    }
+
+    #[test]
+    fn test_documents_function() {
+        let documents = |source: &str| {
+            let lines: Vec<&str> = source.lines().collect();
+            let doc = Comment {
+                line: 1,
+                column: 1,
+                content: "Doc.".to_string(),
+                prefix_len: 4,
+                kind: CommentKind::Doc,
+            };
+            documents_function(&doc, &lines)
+        };
+        assert!(documents("/// Doc.\nfn a() {}"));
+        assert!(documents("/// Doc.\n#[inline]\npub(crate) const fn a() {}"));
+        assert!(documents("/** Doc. */\nexport async function a() {}"));
+        assert!(documents(
+            "/**\n * Doc.\n */\n@Override\npublic int size() {"
+        ));
+        assert!(documents("/** Doc. */ int size();"));
+        assert!(documents("/// Doc.\nfunc (s *Server) Start() error {"));
+        assert!(!documents("/// Doc.\npub struct Point(u8);"));
+        assert!(!documents("/// Doc.\npub name: String,"));
+        assert!(!documents("/// Doc.\nVariant(u8),"));
+        assert!(!documents("/** Doc. */\nconst handler = make(1);"));
+        assert!(!documents("//! Doc.\nfn a() {}"));
+        assert!(!documents("/// Doc."));
+    }
+}
@@ -0,0 +1,121 @@
+//! Embedded `<script>` blocks in HTML, Vue and Svelte files.
+//!
+//! Each block is scanned as its own JavaScript or TypeScript source. The
+//! source is padded with the newlines and columns that precede the block,
+//! so findings carry their line and column in the original file.
+
+use super::Language;
+
+/// File extensions whose `<script>` blocks are scanned.
+pub const MARKUP_EXTENSIONS: &[&str] = &["html", "htm", "vue", "svelte"];
+
+/// The contents of one `<script>` block, positioned as in the original file.
+#[derive(Debug)]
+pub(crate) struct ScriptBlock {
+    /// Block contents, preceded by padding that puts its first character at
+    /// the same line and column it has in the file.
+    pub code: String,
+    /// JavaScript, or TypeScript when the tag says so.
+    pub language: Language,
+}
+
+/// Find every `<script>` block holding JavaScript or TypeScript.
+///
+/// Blocks with a non-script `type` (JSON data, templates) are skipped. An
+/// unclosed block runs to the end of the file.
+pub(crate) fn script_blocks(content: &str) -> Vec<ScriptBlock> {
+    // ASCII lowercasing keeps byte offsets aligned with `content`
+    let lower = content.to_ascii_lowercase();
+    let mut blocks = Vec::new();
+    let mut pos = 0;
+
+    while let Some(found) = lower[pos..].find("<script") {
+        let tag_start = pos + found;
+        let attrs_start = tag_start + "<script".len();
+        // `<scripts>` or `<script-foo>` are different tags
+        if !lower[attrs_start..].starts_with(|c: char| c == '>' || c.is_ascii_whitespace()) {
+            pos = attrs_start;
+            continue;
+        }
+        let Some(tag_len) = lower[attrs_start..].find('>') else {
+            break;
+        };
+        let attrs = &lower[attrs_start..attrs_start + tag_len];
+        let body_start = attrs_start + tag_len + 1;
+        let body_end = lower[body_start..]
+            .find("</script")
+            .map_or(content.len(), |i| body_start + i);
+        pos = body_end;
+
+        let Some(language) = script_language(attrs) else {
+            continue;
+        };
+        let prefix = &content[..body_start];
+        let line_start = prefix.rfind('\n').map_or(0, |i| i + 1);
+        let mut code = "\n".repeat(prefix.matches('\n').count());
+        code.push_str(&" ".repeat(body_start - line_start));
+        code.push_str(&content[body_start..body_end]);
+        blocks.push(ScriptBlock { code, language });
+    }
+
+    blocks
+}
+
+/// Language of a script tag from its lowercased attributes, or `None` if
+/// the `type` marks it as something other than code.
+fn script_language(attrs: &str) -> Option<Language> {
+    let value = |name: &str| {
+        attrs
+            .split_whitespace()
+            .find_map(|attr| attr.strip_prefix(name)?.strip_prefix('='))
+            .map(|v| v.trim_matches(|c| c == '"' || c == '\''))
+    };
+
+    if let Some(kind) = value("type") {
+        let is_code = kind.is_empty()
+            || kind == "module"
+            || kind.contains("javascript")
+            || kind.contains("ecmascript")
+            || kind.contains("typescript");
+        if !is_code {
+            return None;
+        }
+        if kind.contains("typescript") {
+            return Some(Language::TypeScript);
+        }
+    }
+    match value("lang") {
+        Some("ts" | "typescript") => Some(Language::TypeScript),
+        Some("tsx") => Some(Language::Tsx),
+        Some("jsx") => Some(Language::Jsx),
+        _ => Some(Language::JavaScript),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_blocks_keep_positions() {
+        let html = "<template>\n  <p>hi</p>\n</template>\n<script lang=\"ts\">\nconst x = 1;\n</script>\n<SCRIPT>let y;</SCRIPT>\n";
+        let blocks = script_blocks(html);
+        assert_eq!(blocks.len(), 2);
+
+        assert_eq!(blocks[0].language, Language::TypeScript);
+        assert_eq!(blocks[0].code.lines().nth(4), Some("const x = 1;"));
+
+        assert_eq!(blocks[1].language, Language::JavaScript);
+        let line = blocks[1].code.lines().nth(6).unwrap();
+        assert_eq!(line.trim_start(), "let y;");
+        assert_eq!(line.find("let"), html.lines().nth(6).unwrap().find("let"));
+    }
+
+    #[test]
+    fn test_script_blocks_skip_data_types() {
+        let html = "<script type=\"application/ld+json\">{}</script>\n<script type=\"module\">go()</script>\n<scripts>x</scripts>";
+        let blocks = script_blocks(html);
+        assert_eq!(blocks.len(), 1);
+        assert!(blocks[0].code.ends_with("go()"));
+    }
+}
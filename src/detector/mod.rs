@@ -3,18 +3,36 @@
 //! This module provides the core scanning functionality, extracting comments
 //! and matching against slop patterns.
 
+mod builder;
+mod cache;
+mod directives;
+mod generated;
+mod markers;
+mod markup;
+mod normalize;
+mod notebook;
 mod patterns;
 mod regex_fallback;
 
 #[cfg(feature = "tree-sitter")]
 mod tree_sitter;
 
+pub use builder::ScannerBuilder;
+pub use cache::{CachedScan, CategoryCache};
+pub use generated::generated_reason;
+pub use markup::MARKUP_EXTENSIONS;
+pub use notebook::NOTEBOOK_EXTENSION;
 pub use patterns::{CompiledPattern, PatternRegistry};
 pub use regex_fallback::RegexExtractor;
 
-use crate::config::{Pattern, PatternCategory, Severity};
-use crate::Result;
+use crate::config::{MagicNumbers, Pattern, PatternCategory, PatternScope, Severity};
+use crate::filename_checker::is_test_file;
+use crate::{Error, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use regex::{Match, Regex};
+use std::cell::OnceCell;
 use std::collections::HashMap;
+use std::ops::Range;
 use std::path::Path;
 
 /// A comment extracted from source code.
@@ -26,10 +44,75 @@ pub struct Comment {
     pub column: usize,
     /// The comment text content.
     pub content: String,
+    /// Bytes between `column` and the start of `content` (comment markers
+    /// and whitespace that were stripped).
+    pub prefix_len: usize,
+    /// Whether this is a line, block, or documentation comment.
+    pub kind: CommentKind,
+}
+
+impl Comment {
+    /// Source line and column (1-indexed) of the byte at `offset` in
+    /// `content`. Content spanning several lines, such as a block comment
+    /// or joined doc lines, is mapped back to the line holding the offset.
+    pub fn position_of(&self, offset: usize, lines: &[&str]) -> (usize, usize) {
+        // A block comment opened on its own line starts its content on the
+        // next non-blank line
+        let opening_rest = lines
+            .get(self.line.saturating_sub(1))
+            .and_then(|line| line.get(self.column.saturating_sub(1)..))
+            .map_or(0, str::len);
+        let first_line = if self.prefix_len > opening_rest {
+            let blank = lines
+                .iter()
+                .skip(self.line)
+                .take_while(|line| line.trim().is_empty())
+                .count();
+            self.line + 1 + blank
+        } else {
+            self.line
+        };
+
+        let before = &self.content[..offset];
+        let line = first_line + before.matches('\n').count();
+        if line == self.line {
+            return (line, self.column + self.prefix_len + offset);
+        }
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        let content_line = self.content[line_start..]
+            .split('\n')
+            .next()
+            .unwrap_or("")
+            .trim_end_matches('\r');
+        // Content lines are source lines with markers and indentation
+        // stripped, so they end the line unless a closing marker follows
+        let start = lines.get(line - 1).map_or(0, |source| {
+            let trimmed = source.trim_end();
+            if trimmed.ends_with(content_line) {
+                trimmed.len() - content_line.len()
+            } else {
+                source.find(content_line).unwrap_or(0)
+            }
+        });
+        (line, start + 1 + offset - line_start)
+    }
+}
+
+/// The syntactic kind of a comment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentKind {
+    /// A line comment such as `// ...` or `# ...`.
+    Line,
+    /// A block comment such as `/* ... */`.
+    Block,
+    /// A documentation comment (`///`, `//!`, `/** */`) or docstring.
+    Doc,
 }
 
 /// A single slop finding.
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct Finding {
     /// File path.
     pub file: String,
@@ -56,6 +139,61 @@ pub struct Finding {
     /// Context line(s) after the finding.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context_after: Option<String>,
+    /// Whether this finding is informational only (contributes no score).
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub informational: bool,
+    /// Documentation for the pattern that produced this finding.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help_url: Option<String>,
+    /// Suggested replacement for the matched text, e.g. a corrected filename.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+    /// Notebook code cell (1-indexed among all cells) holding the finding.
+    /// `line` then counts lines across the notebook's code cells.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cell: Option<usize>,
+    /// Where the match starts and ends in the source, recorded by the
+    /// scanner when it matched. `None` for findings with no source match,
+    /// such as filename findings.
+    #[serde(skip)]
+    pub span: Option<Span>,
+}
+
+/// Where a finding's match starts and ends, with 1-indexed lines and
+/// 1-indexed byte columns like [`Finding::line`] and [`Finding::column`].
+/// The end is exclusive: it is the position just past the last matched byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Span {
+    /// Line of the first matched byte.
+    pub start_line: usize,
+    /// Column of the first matched byte.
+    pub start_column: usize,
+    /// Line of the last matched byte.
+    pub end_line: usize,
+    /// Column just past the last matched byte.
+    pub end_column: usize,
+}
+
+impl Finding {
+    /// Returns the score this finding contributes.
+    ///
+    /// Informational findings always score 0.
+    pub fn score(&self) -> u32 {
+        self.weighted_score(&HashMap::new())
+    }
+
+    /// Like [`score`](Self::score), but severities in `weights` score their
+    /// mapped value instead of [`Severity::score`].
+    pub fn weighted_score(&self, weights: &HashMap<Severity, u32>) -> u32 {
+        if self.informational {
+            0
+        } else {
+            weights
+                .get(&self.severity)
+                .copied()
+                .unwrap_or_else(|| self.severity.score())
+        }
+    }
 }
 
 /// Result of scanning a single file.
@@ -67,10 +205,69 @@ pub struct FileScanResult {
     pub findings: Vec<Finding>,
     /// Total slop score for this file.
     pub score: u32,
+    /// Non-blank lines of source scanned.
+    pub lines: usize,
+}
+
+impl FileScanResult {
+    /// Merge another result for the same file into this one.
+    ///
+    /// Findings are appended and scores are summed, so partial results from
+    /// separate passes (e.g. comment and AST detection) can be combined.
+    pub fn merge(&mut self, other: FileScanResult) {
+        self.findings.extend(other.findings);
+        self.score += other.score;
+        self.lines = self.lines.max(other.lines);
+    }
+
+    /// Sort and deduplicate findings with [`sort_findings`], then
+    /// recompute the score.
+    pub fn sort_findings(&mut self) {
+        sort_findings(&mut self.findings);
+        self.score = self.findings.iter().map(Finding::score).sum();
+    }
+
+    /// Recompute the score counting only the first finding for each
+    /// distinct `match_text`, so a copy-pasted placeholder scores once.
+    /// All findings are kept.
+    pub fn score_unique_match_text(&mut self) {
+        self.rescore(&HashMap::new(), true);
+    }
+
+    /// Recompute the score with per-severity `weights` (see
+    /// [`Finding::weighted_score`]), optionally counting each distinct
+    /// `match_text` once.
+    pub fn rescore(&mut self, weights: &HashMap<Severity, u32>, unique_match_text: bool) {
+        let mut seen = std::collections::HashSet::new();
+        self.score = self
+            .findings
+            .iter()
+            .filter(|f| !unique_match_text || seen.insert(f.match_text.as_str()))
+            .map(|f| f.weighted_score(weights))
+            .sum();
+    }
+}
+
+/// Sort findings by file, line and column, then by category name and
+/// pattern, and drop exact duplicates: findings of the same pattern at the
+/// same position with the same message.
+pub fn sort_findings(findings: &mut Vec<Finding>) {
+    let key = |f: &Finding| {
+        (
+            f.file.clone(),
+            f.line,
+            f.column,
+            f.category.as_str(),
+            f.pattern_regex.clone(),
+            f.message.clone(),
+        )
+    };
+    findings.sort_by_cached_key(key);
+    findings.dedup_by(|a, b| key(a) == key(b));
 }
 
 /// Summary of a scan operation.
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, Default, serde::Serialize)]
 pub struct ScanSummary {
     /// Number of files scanned.
     pub files_scanned: usize,
@@ -80,49 +277,178 @@ pub struct ScanSummary {
     pub total_findings: usize,
     /// Total slop score across all files.
     pub total_score: u32,
+    /// Non-blank lines scanned across all files.
+    pub lines_scanned: usize,
     /// Findings grouped by severity.
     pub by_severity: HashMap<Severity, usize>,
     /// Findings grouped by category.
     pub by_category: HashMap<PatternCategory, usize>,
+    /// Per-file scores, in the order files were added.
+    pub file_scores: Vec<(String, u32)>,
 }
 
 impl ScanSummary {
     /// Create a summary from scan results.
     pub fn new(results: &[FileScanResult]) -> Self {
-        let mut summary = Self {
-            files_scanned: results.len(),
-            files_with_findings: 0,
-            total_findings: 0,
-            total_score: 0,
-            by_severity: HashMap::new(),
-            by_category: HashMap::new(),
-        };
-
+        let mut summary = Self::default();
         for result in results {
-            if !result.findings.is_empty() {
-                summary.files_with_findings += 1;
-            }
-            summary.total_findings += result.findings.len();
-            summary.total_score += result.score;
-
-            for finding in &result.findings {
-                *summary
-                    .by_severity
-                    .entry(finding.severity.clone())
-                    .or_insert(0) += 1;
-                *summary
-                    .by_category
-                    .entry(finding.category.clone())
-                    .or_insert(0) += 1;
-            }
+            summary.add(result);
+        }
+        summary
+    }
+
+    /// Add a single file's results to this summary.
+    pub fn add(&mut self, result: &FileScanResult) {
+        self.files_scanned += 1;
+        if !result.findings.is_empty() {
+            self.files_with_findings += 1;
+        }
+        self.total_findings += result.findings.len();
+        self.total_score += result.score;
+        self.lines_scanned += result.lines;
+        self.file_scores.push((result.path.clone(), result.score));
+
+        for finding in &result.findings {
+            *self
+                .by_severity
+                .entry(finding.severity.clone())
+                .or_insert(0) += 1;
+            *self
+                .by_category
+                .entry(finding.category.clone())
+                .or_insert(0) += 1;
+        }
+    }
+
+    /// Combine another summary into this one.
+    ///
+    /// This is the reduction step when summaries are built independently on
+    /// worker threads and folded together afterwards.
+    pub fn merge(&mut self, other: ScanSummary) {
+        self.files_scanned += other.files_scanned;
+        self.files_with_findings += other.files_with_findings;
+        self.total_findings += other.total_findings;
+        self.total_score += other.total_score;
+        self.lines_scanned += other.lines_scanned;
+
+        for (severity, count) in other.by_severity {
+            *self.by_severity.entry(severity).or_insert(0) += count;
+        }
+        for (category, count) in other.by_category {
+            *self.by_category.entry(category).or_insert(0) += count;
+        }
+        self.file_scores.extend(other.file_scores);
+    }
+
+    /// The `n` highest-scoring files, worst first. Files scoring zero are omitted.
+    pub fn sloppiest_files(&self, n: usize) -> Vec<(&str, u32)> {
+        let mut scored: Vec<(&str, u32)> = self
+            .file_scores
+            .iter()
+            .filter(|(_, score)| *score > 0)
+            .map(|(path, score)| (path.as_str(), *score))
+            .collect();
+        scored.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        scored.truncate(n);
+        scored
+    }
+
+    /// Score per thousand non-blank lines scanned, for comparing codebases
+    /// of different sizes. Zero when nothing was scanned.
+    pub fn score_per_kloc(&self) -> f64 {
+        per_kloc(self.total_score as f64, self.lines_scanned)
+    }
+
+    /// Findings per thousand non-blank lines scanned. Zero when nothing was
+    /// scanned.
+    pub fn findings_per_kloc(&self) -> f64 {
+        per_kloc(self.total_findings as f64, self.lines_scanned)
+    }
+
+    /// Number of scanned files without any findings.
+    pub fn clean_files(&self) -> usize {
+        self.files_scanned - self.files_with_findings
+    }
+
+    /// One-line `key=value` summary for grep/awk-friendly CI parsing, e.g.
+    /// `antislop: files=120 findings=7 score=35 critical=1 high=2 medium=4 low=0`.
+    pub fn parseable_line(&self) -> String {
+        let count = |severity: Severity| self.by_severity.get(&severity).copied().unwrap_or(0);
+        format!(
+            "antislop: files={} findings={} score={} critical={} high={} medium={} low={}",
+            self.files_scanned,
+            self.total_findings,
+            self.total_score,
+            count(Severity::Critical),
+            count(Severity::High),
+            count(Severity::Medium),
+            count(Severity::Low)
+        )
+    }
+}
+
+/// How often a single pattern matched across a scan.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct PatternStat {
+    /// The pattern's regex.
+    pub regex: String,
+    /// The pattern's message.
+    pub message: String,
+    /// The pattern's category.
+    pub category: PatternCategory,
+    /// Number of findings produced by the pattern.
+    pub count: usize,
+}
+
+impl PatternStat {
+    /// Count findings per pattern, in pattern order. Patterns that never
+    /// matched are included with a count of zero.
+    pub fn tally(patterns: &[Pattern], findings: &[Finding]) -> Vec<Self> {
+        let mut counts: HashMap<(&str, &PatternCategory), usize> = HashMap::new();
+        for finding in findings {
+            *counts
+                .entry((finding.pattern_regex.as_str(), &finding.category))
+                .or_insert(0) += 1;
         }
+        patterns
+            .iter()
+            .map(|pattern| {
+                let regex = pattern.regex.to_string();
+                let count = counts
+                    .get(&(regex.as_str(), &pattern.category))
+                    .copied()
+                    .unwrap_or(0);
+                Self {
+                    regex,
+                    message: pattern.message.clone(),
+                    category: pattern.category.clone(),
+                    count,
+                }
+            })
+            .collect()
+    }
+}
 
+fn per_kloc(value: f64, lines: usize) -> f64 {
+    if lines == 0 {
+        0.0
+    } else {
+        value * 1000.0 / lines as f64
+    }
+}
+
+impl FromIterator<FileScanResult> for ScanSummary {
+    fn from_iter<I: IntoIterator<Item = FileScanResult>>(iter: I) -> Self {
+        let mut summary = Self::default();
+        for result in iter {
+            summary.add(&result);
+        }
         summary
     }
 }
 
 /// Language detection strategy.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Language {
     /// Python source.
     Python,
@@ -162,43 +488,176 @@ pub enum Language {
     R,
     /// Scala.
     Scala,
+    /// Dart.
+    Dart,
     /// Shell scripts.
     Shell,
+    /// Prose (Markdown, plain text, reStructuredText); every line is
+    /// treated as comment text.
+    Text,
     /// Unknown language.
     Unknown,
 }
 
 impl Language {
+    /// Every known language, in the order `--list-languages` prints them.
+    pub const ALL: &'static [Language] = &[
+        Language::Python,
+        Language::JavaScript,
+        Language::TypeScript,
+        Language::Jsx,
+        Language::Tsx,
+        Language::Rust,
+        Language::Go,
+        Language::Java,
+        Language::Kotlin,
+        Language::CCpp,
+        Language::CSharp,
+        Language::Ruby,
+        Language::Php,
+        Language::Swift,
+        Language::Haskell,
+        Language::Lua,
+        Language::Perl,
+        Language::R,
+        Language::Scala,
+        Language::Dart,
+        Language::Shell,
+        Language::Text,
+        Language::Unknown,
+    ];
+
+    /// File extensions (without the leading dot) recognized as this language.
+    ///
+    /// This is the single source of truth for extension mapping:
+    /// [`Language::from_path`] and `--list-languages` derive from it, and
+    /// every default `file_extensions` entry maps to a language through it.
+    pub fn extensions(self) -> &'static [&'static str] {
+        match self {
+            Language::Python => &["py"],
+            Language::JavaScript => &["js", "mjs", "cjs"],
+            Language::TypeScript => &["ts"],
+            Language::Jsx => &["jsx"],
+            Language::Tsx => &["tsx"],
+            Language::Rust => &["rs"],
+            Language::Go => &["go"],
+            Language::Java => &["java"],
+            Language::Kotlin => &["kt", "kts"],
+            Language::CCpp => &["c", "cpp", "cc", "cxx", "h", "hpp"],
+            Language::CSharp => &["cs"],
+            Language::Ruby => &["rb"],
+            Language::Php => &["php"],
+            Language::Swift => &["swift"],
+            Language::Haskell => &["hs"],
+            Language::Lua => &["lua"],
+            Language::Perl => &["pl", "pm"],
+            Language::R => &["r", "R"],
+            Language::Scala => &["scala"],
+            Language::Dart => &["dart"],
+            Language::Shell => &["sh", "bash", "zsh", "fish"],
+            Language::Text => &["md", "markdown", "txt", "rst"],
+            Language::Unknown => &[],
+        }
+    }
+
+    /// Human-readable name.
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Language::Python => "Python",
+            Language::JavaScript => "JavaScript",
+            Language::TypeScript => "TypeScript",
+            Language::Jsx => "JSX",
+            Language::Tsx => "TSX",
+            Language::Rust => "Rust",
+            Language::Go => "Go",
+            Language::Java => "Java",
+            Language::Kotlin => "Kotlin",
+            Language::CCpp => "C/C++",
+            Language::CSharp => "C#",
+            Language::Ruby => "Ruby",
+            Language::Php => "PHP",
+            Language::Swift => "Swift",
+            Language::Haskell => "Haskell",
+            Language::Lua => "Lua",
+            Language::Perl => "Perl",
+            Language::R => "R",
+            Language::Scala => "Scala",
+            Language::Dart => "Dart",
+            Language::Shell => "Shell",
+            Language::Text => "Text",
+            Language::Unknown => "Unknown",
+        }
+    }
+
+    /// The marker that starts a line comment, or `None` for prose and
+    /// unknown files.
+    pub fn line_comment(self) -> Option<&'static str> {
+        match self {
+            Language::Python | Language::Ruby | Language::Perl | Language::R | Language::Shell => {
+                Some("#")
+            }
+            Language::Haskell | Language::Lua => Some("--"),
+            Language::JavaScript
+            | Language::TypeScript
+            | Language::Jsx
+            | Language::Tsx
+            | Language::Rust
+            | Language::Go
+            | Language::Java
+            | Language::Kotlin
+            | Language::CCpp
+            | Language::CSharp
+            | Language::Php
+            | Language::Swift
+            | Language::Scala
+            | Language::Dart => Some("//"),
+            Language::Text | Language::Unknown => None,
+        }
+    }
+
     /// Detect language from file extension.
     pub fn from_path(path: &Path) -> Self {
         path.extension()
             .and_then(|e| e.to_str())
-            .map(|ext| match ext {
-                "py" => Language::Python,
-                "js" | "mjs" | "cjs" => Language::JavaScript,
-                "ts" => Language::TypeScript,
-                "jsx" => Language::Jsx,
-                "tsx" => Language::Tsx,
-                "rs" => Language::Rust,
-                "go" => Language::Go,
-                "java" => Language::Java,
-                "kt" | "kts" => Language::Kotlin,
-                "c" | "cpp" | "cc" | "cxx" | "h" | "hpp" => Language::CCpp,
-                "cs" => Language::CSharp,
-                "rb" => Language::Ruby,
-                "php" => Language::Php,
-                "swift" => Language::Swift,
-                "hs" => Language::Haskell,
-                "lua" => Language::Lua,
-                "pl" | "pm" => Language::Perl,
-                "r" | "R" => Language::R,
-                "scala" => Language::Scala,
-                "sh" | "bash" | "zsh" | "fish" => Language::Shell,
-                _ => Language::Unknown,
+            .and_then(|ext| {
+                Language::ALL
+                    .iter()
+                    .copied()
+                    .find(|lang| lang.extensions().contains(&ext))
             })
             .unwrap_or(Language::Unknown)
     }
 
+    /// Parse a language name such as `cpp`, `python` or `ts`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let lang = match name.to_lowercase().as_str() {
+            "python" | "py" => Language::Python,
+            "javascript" | "js" => Language::JavaScript,
+            "typescript" | "ts" => Language::TypeScript,
+            "jsx" => Language::Jsx,
+            "tsx" => Language::Tsx,
+            "rust" | "rs" => Language::Rust,
+            "go" => Language::Go,
+            "java" => Language::Java,
+            "kotlin" | "kt" => Language::Kotlin,
+            "c" | "cpp" | "c++" | "ccpp" => Language::CCpp,
+            "csharp" | "c#" | "cs" => Language::CSharp,
+            "ruby" | "rb" => Language::Ruby,
+            "php" => Language::Php,
+            "swift" => Language::Swift,
+            "haskell" | "hs" => Language::Haskell,
+            "lua" => Language::Lua,
+            "perl" | "pl" => Language::Perl,
+            "r" => Language::R,
+            "scala" => Language::Scala,
+            "dart" => Language::Dart,
+            "shell" | "sh" | "bash" => Language::Shell,
+            "text" | "markdown" | "md" | "txt" | "rst" => Language::Text,
+            _ => return None,
+        };
+        Some(lang)
+    }
+
     /// Returns true if tree-sitter supports this language.
     pub fn has_tree_sitter(self) -> bool {
         match self {
@@ -245,103 +704,820 @@ pub trait CommentExtractor {
     fn extract(&self, source: &str) -> Vec<Comment>;
 }
 
+/// How a file's comments are extracted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtractionMethod {
+    /// Parsed with the language's tree-sitter grammar.
+    TreeSitter,
+    /// Matched with the language's comment-marker regexes.
+    Regex,
+    /// Every non-blank line of a prose document.
+    Prose,
+    /// Nothing extracted: no grammar and `require_tree_sitter` is set.
+    Skipped,
+}
+
+impl std::fmt::Display for ExtractionMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ExtractionMethod::TreeSitter => "tree-sitter",
+            ExtractionMethod::Regex => "regex",
+            ExtractionMethod::Prose => "prose",
+            ExtractionMethod::Skipped => "skipped (tree-sitter required)",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A hook that post-processes a file's findings before they are reported.
+///
+/// Transformers may drop, reorder, or edit findings; the file's score is
+/// recomputed afterwards.
+pub type FindingTransformer = Box<dyn Fn(&mut Vec<Finding>) + Send + Sync>;
+
+/// Whether the first line is a `#!` interpreter line. Rust inner
+/// attributes (`#![...]`) are not shebangs.
+fn has_shebang(source: &str) -> bool {
+    source.starts_with("#!") && !source.starts_with("#![")
+}
+
+/// Every non-blank line of a prose document, as a comment.
+fn prose_lines(source: &str) -> Vec<Comment> {
+    source
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| Comment {
+            line: idx + 1,
+            column: line.len() - line.trim_start().len() + 1,
+            content: line.trim().to_string(),
+            prefix_len: 0,
+            kind: CommentKind::Line,
+        })
+        .collect()
+}
+
 /// The main scanner.
 pub struct Scanner {
     registry: PatternRegistry,
+    /// When set, comments contradicting the function they precede are flagged.
+    detect_stale_comments: bool,
+    /// When set, unexplained numeric literals are flagged.
+    magic_numbers: Option<MagicNumbers>,
+    /// When set, languages without a tree-sitter grammar yield no comments.
+    require_tree_sitter: bool,
+    /// When set, a leading `#!` line is scanned like any other.
+    scan_shebang: bool,
+    /// When set, every match of a pattern in a comment or line is reported,
+    /// not just the first.
+    report_all_matches: bool,
+    /// When set, comments are also matched after NFKC normalization and case-folding.
+    normalize_comments: bool,
+    /// When set, repeated identical match text scores once per file.
+    dedupe_score_by_match_text: bool,
+    /// When set, context lines are the nearest non-blank lines.
+    skip_blank_context: bool,
+    /// Per-severity score overrides; unlisted severities use their default.
+    score_weights: HashMap<Severity, u32>,
+    /// When set, findings in recognized test files are skipped.
+    test_files: Option<GlobSet>,
+    /// Extension (without the dot) to language overrides.
+    language_overrides: HashMap<String, Language>,
+    /// TODOs on lines matching this are downgraded to low severity.
+    todo_ticket: Option<Regex>,
+    /// Comments matching an entry are never checked against patterns. Each
+    /// entry keeps its source text and, if it is a valid regex, the regex.
+    comment_allowlist: Vec<(String, Option<Regex>)>,
+    /// Applied in registration order after each file is scanned.
+    transformers: Vec<FindingTransformer>,
 }
 
 impl Scanner {
     /// Create a new scanner with the given patterns.
     pub fn new(patterns: Vec<Pattern>) -> Result<Self> {
         let registry = PatternRegistry::new(patterns)?;
-        Ok(Self { registry })
+        Ok(Self {
+            registry,
+            detect_stale_comments: false,
+            magic_numbers: None,
+            require_tree_sitter: false,
+            scan_shebang: false,
+            report_all_matches: false,
+            normalize_comments: false,
+            dedupe_score_by_match_text: false,
+            skip_blank_context: false,
+            score_weights: HashMap::new(),
+            test_files: None,
+            language_overrides: HashMap::new(),
+            todo_ticket: None,
+            comment_allowlist: Vec::new(),
+            transformers: Vec::new(),
+        })
+    }
+
+    /// Start building a scanner with non-default matching options.
+    pub fn builder(patterns: Vec<Pattern>) -> ScannerBuilder {
+        ScannerBuilder::new(patterns)
+    }
+
+    /// Skip findings in test files.
+    ///
+    /// Files are recognized by the same name heuristics the filename checker
+    /// uses, plus any of the given glob patterns.
+    pub fn with_test_files_ignored(mut self, patterns: &[String]) -> Result<Self> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let glob = Glob::new(pattern).map_err(|e| {
+                Error::ConfigInvalid(format!("Invalid test file pattern '{}': {}", pattern, e))
+            })?;
+            builder.add(glob);
+        }
+        let set = builder
+            .build()
+            .map_err(|e| Error::ConfigInvalid(format!("Invalid test file patterns: {}", e)))?;
+        self.test_files = Some(set);
+        Ok(self)
+    }
+
+    fn is_ignored_test_file(&self, path: &Path) -> bool {
+        self.test_files
+            .as_ref()
+            .is_some_and(|globs| is_test_file(path) || globs.is_match(path))
+    }
+
+    /// Downgrade TODO findings whose line matches `pattern` (a ticket or
+    /// owner reference such as `TODO(ABC-123)`) to low severity.
+    pub fn with_todo_ticket_pattern(mut self, pattern: &str) -> Result<Self> {
+        let regex = Regex::new(pattern).map_err(|e| {
+            Error::ConfigInvalid(format!("Invalid todo_ticket_pattern '{}': {}", pattern, e))
+        })?;
+        self.todo_ticket = Some(regex);
+        Ok(self)
+    }
+
+    /// Never flag comments matching an entry of `entries`: a comment matches
+    /// when its trimmed content equals the entry, or, for an entry written
+    /// `re:<regex>`, when the regex matches anywhere in the content. Use for
+    /// standard text such as license headers that would otherwise trip
+    /// patterns.
+    pub fn with_comment_allowlist(mut self, entries: &[String]) -> Result<Self> {
+        self.comment_allowlist = entries
+            .iter()
+            .map(|entry| {
+                let regex = match entry.strip_prefix("re:") {
+                    Some(pattern) => Some(Regex::new(pattern).map_err(|e| {
+                        Error::ConfigInvalid(format!(
+                            "Invalid allowlist regex '{}': {}",
+                            pattern, e
+                        ))
+                    })?),
+                    None => None,
+                };
+                Ok((entry.clone(), regex))
+            })
+            .collect::<Result<_>>()?;
+        Ok(self)
+    }
+
+    fn is_allowlisted(&self, content: &str) -> bool {
+        let trimmed = content.trim();
+        self.comment_allowlist
+            .iter()
+            .any(|(entry, regex)| match regex {
+                Some(regex) => regex.is_match(content),
+                None => trimmed == entry,
+            })
+    }
+
+    fn downgrade_ticketed_todos(&self, result: &mut FileScanResult) {
+        let Some(ticket) = &self.todo_ticket else {
+            return;
+        };
+        for finding in &mut result.findings {
+            let is_todo = finding.category == PatternCategory::Placeholder
+                && finding.match_text.to_lowercase().contains("todo");
+            let line = finding
+                .source_line
+                .as_deref()
+                .unwrap_or(&finding.match_text);
+            if is_todo && ticket.is_match(line) {
+                finding.severity = Severity::Low;
+            }
+        }
+    }
+
+    /// Register a transformer that post-processes each file's findings.
+    ///
+    /// Multiple transformers run in the order they were added.
+    pub fn with_finding_transformer(mut self, transformer: FindingTransformer) -> Self {
+        self.transformers.push(transformer);
+        self
+    }
+
+    /// Force a language for files with the given extensions, overriding
+    /// [`Language::from_path`]. Keys are extensions without the leading dot.
+    pub fn with_language_overrides(mut self, overrides: HashMap<String, Language>) -> Self {
+        self.language_overrides = overrides;
+        self
+    }
+
+    /// Resolve the language used to extract comments from `path`.
+    pub fn resolve_language(&self, path: &Path) -> Language {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .and_then(|ext| self.language_overrides.get(ext))
+            .copied()
+            .unwrap_or_else(|| Language::from_path(path))
+    }
+
+    /// Enable the stale-comment pass, which flags comments that contradict
+    /// the name of the function they precede. Requires tree-sitter support
+    /// for the scanned language.
+    pub fn with_stale_comment_detection(mut self, enabled: bool) -> Self {
+        self.detect_stale_comments = enabled;
+        self
+    }
+
+    /// Extract no comments from languages without a tree-sitter grammar,
+    /// rather than falling back to regex extraction, which can mistake
+    /// comment markers inside strings for comments. Prose files are
+    /// unaffected.
+    pub fn with_tree_sitter_required(mut self, required: bool) -> Self {
+        self.require_tree_sitter = required;
+        self
+    }
+
+    /// Scan a leading `#!` interpreter line. By default it is skipped, so
+    /// words in interpreter paths and arguments never produce findings.
+    pub fn with_shebang_scanning(mut self, enabled: bool) -> Self {
+        self.scan_shebang = enabled;
+        self
+    }
+
+    /// Report every non-overlapping match of a pattern, so `TODO and TODO`
+    /// in one comment yields two findings. By default only the first match
+    /// per comment (or code line) is reported.
+    pub fn with_all_matches(mut self, enabled: bool) -> Self {
+        self.report_all_matches = enabled;
+        self
+    }
+
+    /// Match comment patterns against a normalized copy of each comment
+    /// (NFKC, accents stripped, case-folded, unusual spaces made plain) when
+    /// the original text has no match, so `ＴＯＤＯ`, `Todo` or `tödo` match
+    /// a `todo` pattern. Columns and `match_text` still refer to the source
+    /// text.
+    pub fn with_comment_normalization(mut self, enabled: bool) -> Self {
+        self.normalize_comments = enabled;
+        self
+    }
+
+    /// Score findings of the given severities with these weights instead of
+    /// the defaults (low 1, medium 5, high 15, critical 50).
+    pub fn with_score_weights(mut self, weights: HashMap<Severity, u32>) -> Self {
+        self.score_weights = weights;
+        self
+    }
+
+    /// Take `context_before`/`context_after` from the nearest non-blank
+    /// lines instead of the adjacent ones, so a finding surrounded by blank
+    /// lines still shows the code around it.
+    pub fn with_blank_context_skipped(mut self, enabled: bool) -> Self {
+        self.skip_blank_context = enabled;
+        self
+    }
+
+    /// The lines shown before and after a finding on line index `idx`.
+    fn context_around(&self, lines: &[&str], idx: usize) -> (Option<String>, Option<String>) {
+        let keep = |line: &&&str| !self.skip_blank_context || !line.trim().is_empty();
+        let before = lines[..idx.min(lines.len())].iter().rev().find(keep);
+        let after = lines.get(idx + 1..).unwrap_or_default().iter().find(keep);
+        (before.map(|s| s.to_string()), after.map(|s| s.to_string()))
+    }
+
+    /// Score each distinct `match_text` once per file; see
+    /// [`FileScanResult::score_unique_match_text`].
+    pub fn with_score_dedup_by_match_text(mut self, enabled: bool) -> Self {
+        self.dedupe_score_by_match_text = enabled;
+        self
+    }
+
+    /// Enable the magic-number pass, which flags unexplained numeric
+    /// literals in returns and assignments. `None` disables it. Requires
+    /// tree-sitter support for the scanned language.
+    pub fn with_magic_number_detection(mut self, settings: Option<MagicNumbers>) -> Self {
+        self.magic_numbers = settings;
+        self
+    }
+
+    /// Replace the scanner's patterns in place.
+    ///
+    /// The new patterns are compiled before the swap, so on error the
+    /// scanner keeps its previous pattern set.
+    pub fn reload_patterns(&mut self, patterns: Vec<Pattern>) -> Result<()> {
+        self.registry = PatternRegistry::new(patterns)?;
+        Ok(())
     }
 
-    /// Scan a single file.
+    /// Scan a single file, detecting its language from the path.
     pub fn scan_file(&self, path: &str, content: &str) -> FileScanResult {
-        let lang = Language::from_path(Path::new(path));
-        let mut comment_findings = self.findings_from_comments(path, lang, content);
+        let lang = self.resolve_language(Path::new(path));
+        self.scan_file_with_language(path, content, lang)
+    }
+
+    /// Scan a single file as `lang`, bypassing extension-based detection.
+    ///
+    /// Notebooks and HTML/Vue/Svelte files are still recognized by their
+    /// extension and scanned cell by cell or block by block, with `lang`
+    /// in place of the kernel or `<script>` language unless it is
+    /// [`Language::Unknown`].
+    pub fn scan_file_with_language(
+        &self,
+        path: &str,
+        content: &str,
+        lang: Language,
+    ) -> FileScanResult {
+        let result = self.scan_file_matching(path, lang, content, &|_| true);
+        self.finish(result)
+    }
+
+    /// Scan a single file with only the patterns in `categories`.
+    ///
+    /// Returns raw findings: ticketed-TODO downgrades and finding
+    /// transformers are not applied, so partial results can be combined
+    /// and finished as a whole (see [`CategoryCache`]).
+    pub fn scan_file_categories(
+        &self,
+        path: &str,
+        content: &str,
+        categories: &[PatternCategory],
+    ) -> FileScanResult {
+        let lang = self.resolve_language(Path::new(path));
+        self.scan_file_matching(path, lang, content, &|c| categories.contains(c))
+    }
+
+    /// Apply post-match adjustments and transformers, then recompute the score.
+    fn finish(&self, mut result: FileScanResult) -> FileScanResult {
+        self.downgrade_ticketed_todos(&mut result);
+        for transformer in &self.transformers {
+            transformer(&mut result.findings);
+        }
+        result.rescore(&self.score_weights, self.dedupe_score_by_match_text);
+        result
+    }
+
+    fn scan_file_matching(
+        &self,
+        path: &str,
+        lang: Language,
+        content: &str,
+        include: &dyn Fn(&PatternCategory) -> bool,
+    ) -> FileScanResult {
+        if self.is_ignored_test_file(Path::new(path)) {
+            return FileScanResult {
+                path: path.to_string(),
+                findings: Vec::new(),
+                score: 0,
+                lines: 0,
+            };
+        }
+
+        let extension = Path::new(path).extension().and_then(|e| e.to_str());
+        if extension == Some(NOTEBOOK_EXTENSION) {
+            return self.scan_notebook(path, lang, content, include);
+        }
+        if extension.is_some_and(|e| markup::MARKUP_EXTENSIONS.contains(&e)) {
+            return self.scan_markup(path, lang, content, include);
+        }
+        self.scan_source(path, lang, content, include)
+    }
+
+    /// Scan the code cells of a Jupyter notebook as one source in the
+    /// kernel's language, or `lang` when one is forced, then attribute
+    /// findings back to their cells.
+    fn scan_notebook(
+        &self,
+        path: &str,
+        lang: Language,
+        content: &str,
+        include: &dyn Fn(&PatternCategory) -> bool,
+    ) -> FileScanResult {
+        let Some(notebook) = notebook::NotebookSource::parse(content) else {
+            return FileScanResult {
+                path: path.to_string(),
+                findings: Vec::new(),
+                score: 0,
+                lines: 0,
+            };
+        };
+
+        let lang = match lang {
+            Language::Unknown => notebook.language,
+            forced => forced,
+        };
+        let mut result = self.scan_source(path, lang, &notebook.code, include);
+        let lines: Vec<&str> = notebook.code.lines().collect();
+        for finding in &mut result.findings {
+            let Some((cell, cell_lines)) = notebook.cell_at(finding.line) else {
+                continue;
+            };
+            finding.cell = Some(cell);
+            // Context stops at the edges of the cell
+            let idx = finding.line - 1 - cell_lines.start;
+            let (before, after) = self.context_around(&lines[cell_lines], idx);
+            if finding.context_before.is_some() {
+                finding.context_before = before;
+            }
+            if finding.context_after.is_some() {
+                finding.context_after = after;
+            }
+        }
+        result
+    }
+
+    /// Scan each `<script>` block of an HTML, Vue or Svelte file as
+    /// JavaScript or TypeScript, or `lang` when one is forced. Everything
+    /// outside the blocks is ignored, apart from `antislop:disable`
+    /// directives, which apply across the whole file.
+    fn scan_markup(
+        &self,
+        path: &str,
+        lang: Language,
+        content: &str,
+        include: &dyn Fn(&PatternCategory) -> bool,
+    ) -> FileScanResult {
+        let lines: Vec<&str> = content.lines().collect();
+        let line_at = |idx: usize| lines.get(idx).map(|s| s.to_string());
+
+        let mut findings = Vec::new();
+        let mut scanned_lines = 0;
+        for block in markup::script_blocks(content) {
+            let lang = match lang {
+                Language::Unknown => block.language,
+                forced => forced,
+            };
+            let result = self.scan_source(path, lang, &block.code, include);
+            findings.extend(result.findings);
+            scanned_lines += result.lines;
+        }
+        // Blocks only hold their own lines, so a directive in the markup
+        // around them is only seen here
+        let disabled = directives::DisabledRanges::parse(content);
+        findings.retain(|f| !disabled.contains(f.line));
+        // Blocks are padded with blank lines, so take context from the file
+        for finding in &mut findings {
+            let idx = finding.line.saturating_sub(1);
+            if finding.source_line.is_some() {
+                finding.source_line = line_at(idx);
+            }
+            let (before, after) = self.context_around(&lines, idx);
+            if finding.context_before.is_some() {
+                finding.context_before = before;
+            }
+            if finding.context_after.is_some() {
+                finding.context_after = after;
+            }
+        }
+
+        FileScanResult {
+            path: path.to_string(),
+            score: findings.iter().map(Finding::score).sum(),
+            lines: scanned_lines,
+            findings,
+        }
+    }
+
+    fn scan_source(
+        &self,
+        path: &str,
+        lang: Language,
+        content: &str,
+        include: &dyn Fn(&PatternCategory) -> bool,
+    ) -> FileScanResult {
+        let mut comment_findings = self.findings_from_comments(path, lang, content, include);
+        // Prose has no code, so code-scoped patterns don't apply
+        if lang != Language::Text {
+            comment_findings.merge(self.findings_from_code(path, content, include));
+        }
 
         // Also run AST-level detection if available
         #[cfg(feature = "tree-sitter")]
         if lang.has_tree_sitter() {
             if let Some(mut extractor) = self::tree_sitter::get_extractor(lang) {
-                // Collect pattern references for AST detection
-                let patterns: Vec<&Pattern> =
-                    self.registry.patterns.iter().map(|p| &p.pattern).collect();
-                // Convert Vec<&Pattern> to a slice that lives long enough
-                let pattern_refs: Vec<Pattern> = patterns.iter().map(|p| (**p).clone()).collect();
-                let ast_findings = extractor.extract_ast_findings(content, &pattern_refs);
+                // Collect the patterns that apply to this file for AST detection
+                let patterns: Vec<Pattern> = self
+                    .patterns_for(path)
+                    .into_iter()
+                    .map(|p| &p.pattern)
+                    .filter(|p| include(&p.category))
+                    .cloned()
+                    .collect();
+                let ast_findings = extractor.extract_ast_findings(content, &patterns);
+
+                let stale_findings =
+                    if self.detect_stale_comments && include(&PatternCategory::StaleComment) {
+                        extractor.extract_stale_comment_findings(content)
+                    } else {
+                        Vec::new()
+                    };
+
+                let magic_findings = match &self.magic_numbers {
+                    Some(settings) if include(&PatternCategory::Stub) => {
+                        extractor.extract_magic_number_findings(content, &settings.allowlist)
+                    }
+                    _ => Vec::new(),
+                };
 
                 // Set file path and add to results
-                for mut finding in ast_findings {
+                for mut finding in ast_findings
+                    .into_iter()
+                    .chain(stale_findings)
+                    .chain(magic_findings)
+                {
                     finding.file = path.to_string();
-                    comment_findings.score += finding.severity.score();
+                    comment_findings.score += finding.score();
                     comment_findings.findings.push(finding);
                 }
             }
         }
 
+        let disabled = directives::DisabledRanges::parse(content);
+        let skip_shebang = !self.scan_shebang && has_shebang(content);
+        if !disabled.is_empty() || skip_shebang {
+            comment_findings
+                .findings
+                .retain(|f| !(disabled.contains(f.line) || skip_shebang && f.line == 1));
+            comment_findings.score = comment_findings.findings.iter().map(Finding::score).sum();
+        }
+
+        comment_findings.lines = content.lines().filter(|l| !l.trim().is_empty()).count();
         comment_findings
     }
 
-    /// Extract comments using the best available method.
-    fn extract_comments(&self, lang: Language, source: &str) -> Vec<Comment> {
+    /// The method [`Scanner::scan_file`] uses to extract comments for `lang`.
+    pub fn extraction_method(&self, lang: Language) -> ExtractionMethod {
+        if lang == Language::Text {
+            ExtractionMethod::Prose
+        } else if lang.has_tree_sitter() {
+            ExtractionMethod::TreeSitter
+        } else if self.require_tree_sitter {
+            ExtractionMethod::Skipped
+        } else {
+            ExtractionMethod::Regex
+        }
+    }
+
+    /// Comments in a file, extracted the way [`Scanner::scan_file`] does.
+    pub fn comments(&self, path: &str, content: &str) -> Vec<Comment> {
+        let lang = self.resolve_language(Path::new(path));
+        self.extract_comments(lang, content).0
+    }
+
+    /// Function doc comments and docstrings in a file, for cross-file
+    /// analyses such as repeated-docstring detection.
+    ///
+    /// Consecutive doc comment lines (`///` runs) are joined into one
+    /// comment, so each function's documentation is compared as a whole.
+    /// Docs on other items, ignored test files and lines under an
+    /// `antislop:disable` directive yield nothing, as in
+    /// [`Scanner::scan_file`].
+    pub fn doc_comments(&self, path: &str, content: &str) -> Vec<Comment> {
+        let lang = self.resolve_language(Path::new(path));
+        if lang == Language::Text || self.is_ignored_test_file(Path::new(path)) {
+            return Vec::new();
+        }
+        let (comments, _) = self.extract_comments(lang, content);
+        let doc_lines = comments
+            .into_iter()
+            .filter(|c| c.kind == CommentKind::Doc)
+            .collect();
+        let lines: Vec<&str> = content.lines().collect();
+        let mut docs: Vec<Comment> = markers::join_doc_lines(doc_lines, content)
+            .into_iter()
+            .filter(|doc| markers::documents_function(doc, &lines))
+            .collect();
+
         #[cfg(feature = "tree-sitter")]
-        if lang.has_tree_sitter() {
-            if let Some(mut extractor) = self::tree_sitter::get_extractor(lang) {
-                return extractor.extract(source);
+        if let Some(mut extractor) = self::tree_sitter::get_extractor(lang) {
+            docs.extend(extractor.extract_docstrings(content));
+        }
+
+        let disabled = directives::DisabledRanges::parse(content);
+        docs.retain(|doc| !disabled.contains(doc.line));
+        docs
+    }
+
+    /// Extract comments using the best available method, returning the
+    /// method that produced them.
+    fn extract_comments(&self, lang: Language, source: &str) -> (Vec<Comment>, ExtractionMethod) {
+        match self.extraction_method(lang) {
+            ExtractionMethod::Prose => (prose_lines(source), ExtractionMethod::Prose),
+            ExtractionMethod::Skipped => (Vec::new(), ExtractionMethod::Skipped),
+            ExtractionMethod::TreeSitter => {
+                #[cfg(feature = "tree-sitter")]
+                if let Some(mut extractor) = self::tree_sitter::get_extractor(lang) {
+                    return (extractor.extract(source), ExtractionMethod::TreeSitter);
+                }
+                // A grammar that fails to load is treated like a missing one
+                if self.require_tree_sitter {
+                    return (Vec::new(), ExtractionMethod::Skipped);
+                }
+                self.extract_comments_with_regex(lang, source)
             }
+            ExtractionMethod::Regex => self.extract_comments_with_regex(lang, source),
         }
+    }
 
-        // Fallback to regex-based extraction
-        RegexExtractor::new().extract(source)
+    /// Extract comments with the regex fallback.
+    fn extract_comments_with_regex(
+        &self,
+        lang: Language,
+        source: &str,
+    ) -> (Vec<Comment>, ExtractionMethod) {
+        (
+            RegexExtractor::for_language(lang).extract(source),
+            ExtractionMethod::Regex,
+        )
     }
 
     /// Convert comments to findings by matching patterns.
-    fn findings_from_comments(&self, path: &str, lang: Language, source: &str) -> FileScanResult {
+    fn findings_from_comments(
+        &self,
+        path: &str,
+        lang: Language,
+        source: &str,
+        include: &dyn Fn(&PatternCategory) -> bool,
+    ) -> FileScanResult {
         let mut findings = Vec::new();
         let mut total_score = 0u32;
 
-        let comments = self.extract_comments(lang, source);
+        let (comments, method) = self.extract_comments(lang, source);
+        tracing::debug!("{}: comments extracted with {}", path, method);
         let lines: Vec<&str> = source.lines().collect();
+        let patterns = self.patterns_for(path);
 
         for comment in &comments {
-            for pattern in &self.registry.patterns {
-                // Skip AST-only patterns for comment-based matching
-                if pattern.pattern.ast_query.is_some() {
+            if self.is_allowlisted(&comment.content) {
+                continue;
+            }
+            let raw_line = lines.get(comment.line.saturating_sub(1)).copied();
+            // Normalized copies are built on first use, once per comment
+            let normalized_content = OnceCell::new();
+            let normalized_line = OnceCell::new();
+            for pattern in &patterns {
+                // Skip AST-only and code-scoped patterns for comment-based matching
+                if pattern.pattern.ast_query.is_some()
+                    || pattern.pattern.scope != PatternScope::Comment
+                    || !include(&pattern.pattern.category)
+                {
                     continue;
                 }
 
-                if let Some(regex) = &pattern.compiled {
-                    if let Some(mat) = regex.find(&comment.content) {
-                        let severity = pattern.pattern.severity.clone();
+                // Raw-line patterns see the markers too, with columns from
+                // the line start
+                let (text, raw, normalized) = match raw_line {
+                    Some(line) if pattern.pattern.match_raw_line => (line, true, &normalized_line),
+                    _ => (comment.content.as_str(), false, &normalized_content),
+                };
+
+                let mut spans: Vec<Range<usize>> = self
+                    .pattern_matches(pattern, text)
+                    .iter()
+                    .map(Match::range)
+                    .collect();
+                if spans.is_empty() && self.normalize_comments {
+                    let normalized =
+                        normalized.get_or_init(|| normalize::NormalizedText::new(text));
+                    spans = self
+                        .pattern_matches(pattern, &normalized.text)
+                        .iter()
+                        .map(|mat| normalized.source_range(mat.range()))
+                        .collect();
+                }
+
+                for span in spans {
+                    let severity = pattern.pattern.severity.clone();
+                    if !pattern.pattern.informational {
                         total_score += severity.score();
+                    }
+
+                    let ((line, column), (end_line, end_column)) = if raw {
+                        ((comment.line, 1 + span.start), (comment.line, 1 + span.end))
+                    } else {
+                        (
+                            comment.position_of(span.start, &lines),
+                            comment.position_of(span.end, &lines),
+                        )
+                    };
+                    // Extract context lines (1-indexed to 0-indexed)
+                    let line_idx = line.saturating_sub(1);
+                    let source_line = lines.get(line_idx).map(|s| s.to_string());
+                    let (context_before, context_after) = self.context_around(&lines, line_idx);
 
-                        // Extract context lines (1-indexed to 0-indexed)
-                        let line_idx = comment.line.saturating_sub(1);
-                        let source_line = lines.get(line_idx).map(|s| s.to_string());
-                        let context_before = if line_idx > 0 {
-                            lines.get(line_idx - 1).map(|s| s.to_string())
-                        } else {
-                            None
-                        };
-                        let context_after = lines.get(line_idx + 1).map(|s| s.to_string());
-
-                        findings.push(Finding {
-                            file: path.to_string(),
-                            line: comment.line,
-                            column: comment.column + mat.start(),
-                            severity,
-                            category: pattern.pattern.category.clone(),
-                            message: pattern.pattern.message.clone(),
-                            match_text: mat.as_str().to_string(),
-                            pattern_regex: pattern.pattern.regex.to_string(),
-                            source_line,
-                            context_before,
-                            context_after,
-                        });
+                    findings.push(Finding {
+                        file: path.to_string(),
+                        line,
+                        column,
+                        severity,
+                        category: pattern.pattern.category.clone(),
+                        message: pattern.pattern.message.clone(),
+                        match_text: text[span].to_string(),
+                        pattern_regex: pattern.pattern.regex.to_string(),
+                        source_line,
+                        context_before,
+                        context_after,
+                        informational: pattern.pattern.informational,
+                        help_url: pattern.pattern.help_url.clone(),
+                        span: Some(Span {
+                            start_line: line,
+                            start_column: column,
+                            end_line,
+                            end_column,
+                        }),
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+
+        FileScanResult {
+            path: path.to_string(),
+            findings,
+            score: total_score,
+            lines: 0,
+        }
+    }
+
+    /// Patterns that apply to `path`, honoring `exclude_extensions`.
+    fn patterns_for(&self, path: &str) -> Vec<&CompiledPattern> {
+        let path = Path::new(path);
+        self.registry
+            .patterns
+            .iter()
+            .filter(|p| p.pattern.applies_to(path))
+            .collect()
+    }
+
+    /// Matches of `pattern` in `text` that become findings.
+    fn pattern_matches<'t>(&self, pattern: &CompiledPattern, text: &'t str) -> Vec<Match<'t>> {
+        let mut matches = pattern.matches_all(text);
+        if !self.report_all_matches {
+            matches.truncate(1);
+        }
+        matches
+    }
+
+    /// Match code-scoped patterns line-by-line against the full source.
+    fn findings_from_code(
+        &self,
+        path: &str,
+        source: &str,
+        include: &dyn Fn(&PatternCategory) -> bool,
+    ) -> FileScanResult {
+        let mut findings = Vec::new();
+        let mut total_score = 0u32;
+
+        let lines: Vec<&str> = source.lines().collect();
+
+        for pattern in self.patterns_for(path) {
+            if pattern.pattern.ast_query.is_some()
+                || pattern.pattern.scope == PatternScope::Comment
+                || !include(&pattern.pattern.category)
+            {
+                continue;
+            }
+
+            for (line_idx, line) in lines.iter().enumerate() {
+                for mat in self.pattern_matches(pattern, line) {
+                    let severity = pattern.pattern.severity.clone();
+                    if !pattern.pattern.informational {
+                        total_score += severity.score();
                     }
+
+                    let (context_before, context_after) = self.context_around(&lines, line_idx);
+
+                    findings.push(Finding {
+                        file: path.to_string(),
+                        line: line_idx + 1,
+                        column: mat.start() + 1,
+                        severity,
+                        category: pattern.pattern.category.clone(),
+                        message: pattern.pattern.message.clone(),
+                        match_text: mat.as_str().to_string(),
+                        pattern_regex: pattern.pattern.regex.to_string(),
+                        source_line: Some(line.to_string()),
+                        context_before,
+                        context_after,
+                        informational: pattern.pattern.informational,
+                        help_url: pattern.pattern.help_url.clone(),
+                        span: Some(Span {
+                            start_line: line_idx + 1,
+                            start_column: mat.start() + 1,
+                            end_line: line_idx + 1,
+                            end_column: mat.end() + 1,
+                        }),
+                        ..Default::default()
+                    });
                 }
             }
         }
@@ -350,6 +1526,7 @@ impl Scanner {
             path: path.to_string(),
             findings,
             score: total_score,
+            lines: 0,
         }
     }
 }
@@ -358,6 +1535,7 @@ impl Scanner {
 mod tests {
     use super::*;
     use crate::config::RegexPattern;
+    use crate::Config;
 
     fn test_patterns() -> Vec<Pattern> {
         vec![
@@ -368,6 +1546,7 @@ mod tests {
                 category: PatternCategory::Placeholder,
                 ast_query: None,
                 languages: vec![],
+                ..Default::default()
             },
             Pattern {
                 regex: RegexPattern::new("(?i)for now".to_string()).unwrap(),
@@ -376,6 +1555,7 @@ mod tests {
                 category: PatternCategory::Deferral,
                 ast_query: None,
                 languages: vec![],
+                ..Default::default()
             },
         ]
     }
@@ -395,16 +1575,370 @@ mod tests {
     }
 
     #[test]
-    fn test_score_calculation() {
-        let scanner = Scanner::new(test_patterns()).unwrap();
-        let code = "# TODO: fix this # for now we do this";
+    fn test_code_scoped_pattern() {
+        let mut patterns = test_patterns();
+        patterns.push(Pattern {
+            regex: RegexPattern::new(r"except:\s*pass".to_string()).unwrap(),
+            severity: Severity::High,
+            message: "Swallowed exception".to_string(),
+            category: PatternCategory::Stub,
+            ast_query: None,
+            languages: vec![],
+            scope: PatternScope::Code,
+            ..Default::default()
+        });
+        let scanner = Scanner::new(patterns).unwrap();
+        let code = "try:\n    run()\nexcept: pass\n";
         let result = scanner.scan_file("test.py", code);
-        assert_eq!(result.score, 6);
+        assert_eq!(result.findings.len(), 1);
+        assert_eq!(result.findings[0].line, 3);
+        assert_eq!(result.findings[0].column, 1);
+        assert_eq!(result.findings[0].match_text, "except: pass");
+        assert_eq!(result.score, 15);
     }
 
     #[test]
-    fn test_language_detection() {
-        assert_eq!(Language::from_path(Path::new("test.py")), Language::Python);
+    fn test_require_tree_sitter_skips_regex_fallback() {
+        let scanner = Scanner::new(test_patterns())
+            .unwrap()
+            .with_tree_sitter_required(true);
+        // Kotlin has no grammar, so only the regex extractor could see this
+        let result = scanner.scan_file("test.kt", "fun foo() {\n    // TODO: implement\n}\n");
+        assert!(result.findings.is_empty());
+        assert_eq!(result.score, 0);
+
+        let result = scanner.scan_file("test.py", "def foo():\n    # TODO: implement\n    pass\n");
+        assert_eq!(result.findings.len(), 1);
+    }
+
+    #[test]
+    fn test_report_all_matches_in_one_comment() {
+        let code = "# TODO: one and TODO: two\n";
+
+        let scanner = Scanner::new(test_patterns()).unwrap();
+        assert_eq!(scanner.scan_file("a.py", code).findings.len(), 1);
+
+        let scanner = Scanner::new(test_patterns())
+            .unwrap()
+            .with_all_matches(true);
+        let result = scanner.scan_file("a.py", code);
+        let columns: Vec<usize> = result.findings.iter().map(|f| f.column).collect();
+        assert_eq!(columns, vec![3, 17]);
+        assert_eq!(result.score, 2 * Severity::Medium.score());
+    }
+
+    #[test]
+    fn test_comment_normalization_matches_full_width() {
+        let code = "# ＴＯＤＯ: widen this\n";
+
+        let scanner = Scanner::new(test_patterns()).unwrap();
+        assert!(scanner.scan_file("a.py", code).findings.is_empty());
+
+        let scanner = Scanner::new(test_patterns())
+            .unwrap()
+            .with_comment_normalization(true);
+        let result = scanner.scan_file("a.py", code);
+        assert_eq!(result.findings.len(), 1);
+        assert_eq!(result.findings[0].match_text, "ＴＯＤＯ:");
+        assert_eq!(result.findings[0].column, 3);
+    }
+
+    #[test]
+    fn test_comment_normalization_folds_case() {
+        let mut patterns = test_patterns();
+        patterns.push(Pattern {
+            regex: RegexPattern::new("xxx".to_string()).unwrap(),
+            severity: Severity::Medium,
+            message: "XXX marker".to_string(),
+            category: PatternCategory::Placeholder,
+            ..Default::default()
+        });
+        let scanner = Scanner::new(patterns)
+            .unwrap()
+            .with_comment_normalization(true);
+
+        let matched = |code: &str| -> Vec<String> {
+            scanner
+                .scan_file("a.py", code)
+                .findings
+                .into_iter()
+                .map(|f| f.match_text)
+                .collect()
+        };
+        assert_eq!(matched("# ＸＸＸ widen this\n"), vec!["ＸＸＸ"]);
+        assert_eq!(matched("# Xxx widen this\n"), vec!["Xxx"]);
+        assert_eq!(matched("# xẍx widen this\n"), vec!["xẍx"]);
+        // A no-break space reads as a space
+        assert_eq!(matched("# done for\u{a0}now\n"), vec!["for\u{a0}now"]);
+    }
+
+    #[test]
+    fn test_extraction_method_per_language() {
+        let scanner = Scanner::new(test_patterns()).unwrap();
+        assert_eq!(
+            scanner.extraction_method(Language::Text),
+            ExtractionMethod::Prose
+        );
+        assert_eq!(
+            scanner.extraction_method(Language::Kotlin),
+            ExtractionMethod::Regex
+        );
+        #[cfg(feature = "python")]
+        assert_eq!(
+            scanner.extraction_method(Language::Python),
+            ExtractionMethod::TreeSitter
+        );
+
+        let strict = Scanner::new(test_patterns())
+            .unwrap()
+            .with_tree_sitter_required(true);
+        assert_eq!(
+            strict.extraction_method(Language::Kotlin),
+            ExtractionMethod::Skipped
+        );
+    }
+
+    #[test]
+    fn test_score_dedup_by_match_text() {
+        let code = "# TODO: header\n# TODO: header\nx = 1\n# TODO: header\n";
+
+        let scanner = Scanner::new(test_patterns()).unwrap();
+        let result = scanner.scan_file("a.py", code);
+        assert_eq!(result.findings.len(), 3);
+        assert_eq!(result.score, 3 * Severity::Medium.score());
+
+        let scanner = Scanner::new(test_patterns())
+            .unwrap()
+            .with_score_dedup_by_match_text(true);
+        let result = scanner.scan_file("a.py", code);
+        assert_eq!(result.findings.len(), 3);
+        assert_eq!(result.score, Severity::Medium.score());
+    }
+
+    #[test]
+    fn test_finding_span() {
+        let scanner = Scanner::new(test_patterns()).unwrap();
+        let result = scanner.scan_file("a.py", "x = 1\ny = 2\t# é TODO: fix\n");
+        let span = result.findings[0].span.unwrap();
+        assert_eq!(
+            span,
+            Span {
+                start_line: 2,
+                start_column: 12,
+                end_line: 2,
+                end_column: 17,
+            }
+        );
+        let line = "y = 2\t# é TODO: fix";
+        assert_eq!(&line[span.start_column - 1..span.end_column - 1], "TODO:");
+
+        // A match running across the lines of a block comment ends on the
+        // line and column where the matched text ends
+        let mut patterns = test_patterns();
+        patterns.push(Pattern {
+            regex: RegexPattern::new(r"(?s)FIXME.*?later".to_string()).unwrap(),
+            severity: Severity::Low,
+            message: "Multi-line".to_string(),
+            category: PatternCategory::Deferral,
+            ast_query: None,
+            languages: vec![],
+            ..Default::default()
+        });
+        let scanner = Scanner::new(patterns).unwrap();
+        let code = "fn main() {}\n/* FIXME: some\n   day, later */\n";
+        let result = scanner.scan_file("a.rs", code);
+        let finding = result
+            .findings
+            .iter()
+            .find(|f| f.message == "Multi-line")
+            .unwrap();
+        assert_eq!(
+            finding.span,
+            Some(Span {
+                start_line: 2,
+                start_column: 4,
+                end_line: 3,
+                end_column: 14,
+            })
+        );
+    }
+
+    #[test]
+    fn test_shebang_line_skipped_by_default() {
+        let mut patterns = test_patterns();
+        patterns.push(Pattern {
+            regex: RegexPattern::new("(?i)hack".to_string()).unwrap(),
+            severity: Severity::High,
+            message: "Hack".to_string(),
+            category: PatternCategory::Deferral,
+            ast_query: None,
+            languages: vec![],
+            ..Default::default()
+        });
+        let code = "#!/opt/hack/bin/python\n# TODO: fix this\nprint('hi')\n";
+
+        let scanner = Scanner::new(patterns.clone()).unwrap();
+        let result = scanner.scan_file("run.py", code);
+        let lines: Vec<usize> = result.findings.iter().map(|f| f.line).collect();
+        assert_eq!(lines, vec![2]);
+
+        let scanner = Scanner::new(patterns).unwrap().with_shebang_scanning(true);
+        let result = scanner.scan_file("run.py", code);
+        assert!(result.findings.iter().any(|f| f.line == 1));
+    }
+
+    #[test]
+    fn test_indented_comment_column() {
+        let scanner = Scanner::new(test_patterns()).unwrap();
+        // AST-extracted (Python) and regex-fallback (Kotlin) comments
+        for (path, code) in [
+            ("test.py", "def foo():\n    # TODO: implement\n    pass\n"),
+            ("test.rs", "fn foo() {\n    // TODO: implement\n}\n"),
+            ("test.kt", "fun foo() {\n    // TODO: implement\n}\n"),
+            ("test.kt", "val x = 1 /* TODO: later */\n"),
+            ("test.py", "x = 1  # TODO: later\n"),
+        ] {
+            let result = scanner.scan_file(path, code);
+            assert_eq!(result.findings.len(), 1, "{}", path);
+            let finding = &result.findings[0];
+            let line = code.lines().nth(finding.line - 1).unwrap();
+            assert_eq!(
+                finding.column,
+                line.find("TODO").unwrap() + 1,
+                "{}: {:?}",
+                path,
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn test_multi_line_comment_positions() {
+        let scanner = Scanner::new(test_patterns()).unwrap();
+        // Joined Rust doc lines, a regex-extracted and an AST-extracted block
+        for (path, code, line) in [
+            (
+                "lib.rs",
+                "/// Loads the config.\n///\n///   TODO: cache it\nfn load() {}\n",
+                3,
+            ),
+            ("a.kt", "/*\n * Parses input.\n * TODO: errors\n */\n", 3),
+            ("a.js", "  /** Does it.\n\n   *   TODO: x */\n", 3),
+        ] {
+            let result = scanner.scan_file(path, code);
+            assert_eq!(result.findings.len(), 1, "{}", path);
+            let finding = &result.findings[0];
+            assert_eq!(finding.line, line, "{}", path);
+            let source = code.lines().nth(line - 1).unwrap();
+            assert_eq!(finding.column, source.find("TODO").unwrap() + 1, "{}", path);
+            assert_eq!(finding.source_line.as_deref(), Some(source));
+        }
+    }
+
+    #[test]
+    fn test_comment_scoped_pattern_ignores_code() {
+        let scanner = Scanner::new(test_patterns()).unwrap();
+        let code = "todo_list = load()  # for now\nprint('TODO: not a comment')\n";
+        let result = scanner.scan_file("test.py", code);
+        assert_eq!(result.findings.len(), 1);
+        assert_eq!(result.findings[0].category, PatternCategory::Deferral);
+    }
+
+    #[test]
+    fn test_dart_comments() {
+        let scanner = Scanner::new(test_patterns()).unwrap();
+        let code = r#"
+// TODO: implement widget
+/* for now this is static */
+final tag = '#TODO: not a comment';
+"#;
+        let result = scanner.scan_file("main.dart", code);
+        assert_eq!(result.findings.len(), 2);
+        assert_eq!(result.findings[0].line, 2);
+        assert_eq!(result.findings[1].category, PatternCategory::Deferral);
+    }
+
+    #[test]
+    fn test_informational_pattern_scores_zero() {
+        let patterns = vec![Pattern {
+            regex: RegexPattern::new("(?i)generated by".to_string()).unwrap(),
+            severity: Severity::High,
+            message: "AI-generated header".to_string(),
+            category: PatternCategory::Placeholder,
+            ast_query: None,
+            languages: vec![],
+            informational: true,
+            ..Default::default()
+        }];
+        let scanner = Scanner::new(patterns).unwrap();
+        let result = scanner.scan_file("test.py", "# Generated by an assistant\nx = 1\n");
+        assert_eq!(result.findings.len(), 1);
+        assert!(result.findings[0].informational);
+        assert_eq!(result.findings[0].score(), 0);
+        assert_eq!(result.score, 0);
+
+        let summary = ScanSummary::new(&[result]);
+        assert_eq!(summary.total_findings, 1);
+        assert_eq!(summary.total_score, 0);
+    }
+
+    #[test]
+    fn test_reload_patterns() {
+        let mut scanner = Scanner::new(test_patterns()).unwrap();
+        let code = "# TODO: fix\n# HACK: around it\n";
+        assert_eq!(scanner.scan_file("test.py", code).findings.len(), 1);
+
+        scanner
+            .reload_patterns(vec![Pattern {
+                regex: RegexPattern::new("(?i)HACK:".to_string()).unwrap(),
+                severity: Severity::High,
+                message: "Hack comment".to_string(),
+                category: PatternCategory::Placeholder,
+                ast_query: None,
+                languages: vec![],
+                ..Default::default()
+            }])
+            .unwrap();
+
+        let result = scanner.scan_file("test.py", code);
+        assert_eq!(result.findings.len(), 1);
+        assert_eq!(result.findings[0].message, "Hack comment");
+        assert_eq!(result.findings[0].line, 2);
+    }
+
+    #[test]
+    fn test_ignore_test_files() {
+        let code = "# TODO: implement\n";
+        let scanner = Scanner::new(test_patterns()).unwrap();
+        assert_eq!(scanner.scan_file("test_foo.py", code).findings.len(), 1);
+
+        let scanner = scanner
+            .with_test_files_ignored(&["fixtures/**".to_string()])
+            .unwrap();
+        assert!(scanner.scan_file("test_foo.py", code).findings.is_empty());
+        assert!(scanner
+            .scan_file("fixtures/data.py", code)
+            .findings
+            .is_empty());
+        assert_eq!(scanner.scan_file("foo.py", code).findings.len(), 1);
+
+        let invalid = Scanner::new(test_patterns())
+            .unwrap()
+            .with_test_files_ignored(&["[".to_string()]);
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn test_score_calculation() {
+        let scanner = Scanner::new(test_patterns()).unwrap();
+        let code = "# TODO: fix this # for now we do this";
+        let result = scanner.scan_file("test.py", code);
+        assert_eq!(result.score, 6);
+    }
+
+    #[test]
+    fn test_language_detection() {
+        assert_eq!(Language::from_path(Path::new("test.py")), Language::Python);
         assert_eq!(Language::from_path(Path::new("test.rs")), Language::Rust);
         assert_eq!(
             Language::from_path(Path::new("test.js")),
@@ -417,6 +1951,466 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_todo_ticket_pattern_downgrades_ticketed_todos() {
+        let patterns = vec![Pattern {
+            regex: RegexPattern::new(r"(?i)\bTODO\b".to_string()).unwrap(),
+            severity: Severity::Medium,
+            message: "TODO".to_string(),
+            category: PatternCategory::Placeholder,
+            ast_query: None,
+            languages: vec![],
+            ..Default::default()
+        }];
+        let scanner = Scanner::new(patterns)
+            .unwrap()
+            .with_todo_ticket_pattern(r"\([A-Z]+-\d+\)")
+            .unwrap();
+
+        let code = "# TODO(ABC-1): wire up retries\n# TODO: wire up retries\n";
+        let result = scanner.scan_file("test.py", code);
+        assert_eq!(result.findings.len(), 2);
+        assert_eq!(result.findings[0].severity, Severity::Low);
+        assert_eq!(result.findings[1].severity, Severity::Medium);
+        assert_eq!(
+            result.score,
+            Severity::Low.score() + Severity::Medium.score()
+        );
+    }
+
+    #[test]
+    fn test_finding_transformer_drops_low_findings() {
+        let scanner = Scanner::new(test_patterns())
+            .unwrap()
+            .with_finding_transformer(Box::new(|findings: &mut Vec<Finding>| {
+                findings.retain(|f| f.severity != Severity::Low)
+            }));
+        let code = "# TODO: fix this # for now we do this";
+        let result = scanner.scan_file("test.py", code);
+        assert_eq!(result.findings.len(), 1);
+        assert_eq!(result.findings[0].severity, Severity::Medium);
+        assert_eq!(result.score, Severity::Medium.score());
+    }
+
+    #[test]
+    fn test_todo_ticket_pattern_invalid() {
+        let result = Scanner::new(vec![]).unwrap().with_todo_ticket_pattern("(");
+        assert!(matches!(result, Err(Error::ConfigInvalid(_))));
+    }
+
+    #[test]
+    fn test_match_raw_line_sees_comment_markers() {
+        let marker_pattern = |match_raw_line| Pattern {
+            regex: RegexPattern::new(r"^\s*//\s*TODO".to_string()).unwrap(),
+            severity: Severity::Medium,
+            message: "TODO on its own line".to_string(),
+            category: PatternCategory::Placeholder,
+            ast_query: None,
+            languages: vec![],
+            match_raw_line,
+            ..Default::default()
+        };
+        let code = "fn main() {\n    // TODO: wire up\n    run(); // TODO: trailing\n}\n";
+
+        let scanner = Scanner::new(vec![marker_pattern(false)]).unwrap();
+        assert!(scanner.scan_file("main.rs", code).findings.is_empty());
+
+        let scanner = Scanner::new(vec![marker_pattern(true)]).unwrap();
+        let findings = scanner.scan_file("main.rs", code).findings;
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].line, 2);
+        assert_eq!(findings[0].column, 1);
+        assert_eq!(findings[0].match_text, "    // TODO");
+    }
+
+    #[test]
+    fn test_blank_lines_skipped_for_context() {
+        let code = "import os\n\n\n# TODO: handle errors\n   \n\ndef main():\n    pass\n";
+
+        let scanner = Scanner::new(test_patterns()).unwrap();
+        let finding = &scanner.scan_file("test.py", code).findings[0];
+        assert_eq!(finding.context_before.as_deref(), Some(""));
+        assert_eq!(finding.context_after.as_deref(), Some("   "));
+
+        let scanner = scanner.with_blank_context_skipped(true);
+        let finding = &scanner.scan_file("test.py", code).findings[0];
+        assert_eq!(finding.line, 4);
+        assert_eq!(finding.context_before.as_deref(), Some("import os"));
+        assert_eq!(finding.context_after.as_deref(), Some("def main():"));
+    }
+
+    #[test]
+    fn test_comment_allowlist_skips_matching_comments() {
+        let scanner = Scanner::new(test_patterns())
+            .unwrap()
+            .with_comment_allowlist(&[
+                "TODO: keep in sync with upstream (see NOTICE".to_string(),
+                r"re:^SPDX-License-Identifier: \S+ TODO:".to_string(),
+                // Not a regex without the prefix, so only the exact text
+                "TODO: .*".to_string(),
+            ])
+            .unwrap();
+        let code = "# TODO: keep in sync with upstream (see NOTICE\n\
+                    # SPDX-License-Identifier: MIT TODO: audit\n\
+                    # TODO: real work\n\
+                    # TODO: .*\n";
+        let result = scanner.scan_file("test.py", code);
+        assert_eq!(result.findings.len(), 1);
+        assert_eq!(result.findings[0].line, 3);
+
+        let result = Scanner::new(test_patterns())
+            .unwrap()
+            .with_comment_allowlist(&["re:(unclosed".to_string()]);
+        assert!(matches!(result, Err(Error::ConfigInvalid(_))));
+    }
+
+    #[test]
+    fn test_pattern_stats_counts_hits() {
+        let scanner = Scanner::new(test_patterns()).unwrap();
+        let code = "# TODO: one\n# TODO: two\n";
+        let result = scanner.scan_file("test.py", code);
+        let stats = PatternStat::tally(&test_patterns(), &result.findings);
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].regex, "(?i)TODO:");
+        assert_eq!(stats[0].count, 2);
+        assert_eq!(stats[1].regex, "(?i)for now");
+        assert_eq!(stats[1].count, 0);
+    }
+
+    #[test]
+    fn test_default_patterns_flag_merge_conflict_markers() {
+        let scanner = Scanner::new(crate::Config::default().patterns).unwrap();
+        let code = "def greet():\n<<<<<<< HEAD\n    return \"hi\"\n=======\n    return \"hello\"\n>>>>>>> feature/greeting\n";
+        let result = scanner.scan_file("greet.py", code);
+        let conflicts: Vec<&Finding> = result
+            .findings
+            .iter()
+            .filter(|f| f.category == PatternCategory::MergeConflict)
+            .collect();
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!((conflicts[0].line, conflicts[1].line), (2, 6));
+        assert!(conflicts.iter().all(|f| f.severity == Severity::Critical));
+    }
+
+    #[test]
+    fn test_default_patterns_flag_template_placeholders() {
+        let scanner = Scanner::new(crate::Config::default().patterns).unwrap();
+        let placeholders = |result: FileScanResult| -> Vec<String> {
+            result
+                .findings
+                .into_iter()
+                .filter(|f| f.category == PatternCategory::Placeholder)
+                .map(|f| f.match_text)
+                .collect()
+        };
+
+        let code = "const key = \"${API_KEY_HERE}\";\nconst user = \"<INSERT_NAME>\";\nconst hi = `Hello ${name}`;\n";
+        let found = placeholders(scanner.scan_file("config.js", code));
+        assert_eq!(found, vec!["${API_KEY_HERE}", "<INSERT_NAME>"]);
+        assert!(scanner
+            .scan_file("config.js", code)
+            .findings
+            .iter()
+            .all(|f| f.severity == Severity::High));
+
+        // Template files use this syntax on purpose
+        let template = "<p>{{ YOUR_NAME }}</p>\n";
+        assert_eq!(
+            placeholders(scanner.scan_file("mail.py", &format!("body = \"{}\"", template))),
+            vec!["{{ YOUR_NAME }}"]
+        );
+        assert!(placeholders(scanner.scan_file_with_language(
+            "mail.hbs",
+            template,
+            Language::JavaScript
+        ))
+        .is_empty());
+    }
+
+    #[test]
+    fn test_default_patterns_flag_ellipsis_placeholders() {
+        let scanner = Scanner::new(crate::Config::default().patterns).unwrap();
+        let ellipsis_stubs = |path: &str, code: &str| -> Vec<usize> {
+            scanner
+                .scan_file(path, code)
+                .findings
+                .iter()
+                .filter(|f| f.category == PatternCategory::Stub && f.match_text.contains("..."))
+                .map(|f| f.line)
+                .collect()
+        };
+
+        assert_eq!(ellipsis_stubs("stub.py", "def f(): ...\n"), vec![1]);
+        assert_eq!(
+            ellipsis_stubs("app.js", "function f() {\n  // ... TODO\n}\n"),
+            vec![2]
+        );
+        assert!(ellipsis_stubs("slice.py", "def f(a):\n    return a[1:...]\n").is_empty());
+        let typeshed =
+            scanner.scan_file_with_language("stub.pyi", "def f(): ...\n", Language::Python);
+        assert!(typeshed.findings.is_empty());
+    }
+
+    #[test]
+    fn test_scan_markdown_as_prose() {
+        let mut patterns = test_patterns();
+        patterns.push(Pattern {
+            regex: RegexPattern::new("(?i)this section is a placeholder".to_string()).unwrap(),
+            severity: Severity::Medium,
+            message: "Placeholder prose".to_string(),
+            category: PatternCategory::Placeholder,
+            ast_query: None,
+            languages: vec![],
+            ..Default::default()
+        });
+        let scanner = Scanner::new(patterns).unwrap();
+        let doc = "# Setup\n\n  This section is a placeholder until the API settles.\n";
+        let result = scanner.scan_file("docs/setup.md", doc);
+        assert_eq!(result.findings.len(), 1);
+        assert_eq!(result.findings[0].line, 3);
+        assert_eq!(result.findings[0].column, 3);
+        assert_eq!(result.findings[0].message, "Placeholder prose");
+    }
+
+    #[test]
+    fn test_disabled_range_drops_findings() {
+        let scanner = Scanner::new(test_patterns()).unwrap();
+        let code = r#"
+# TODO: reported before the range
+# antislop:disable
+# TODO: ignored inside the range
+# for now this is ignored too
+# antislop:enable
+# for now this is reported after the range
+"#;
+        let result = scanner.scan_file("test.py", code);
+        let lines: Vec<usize> = result.findings.iter().map(|f| f.line).collect();
+        assert_eq!(lines, vec![2, 7]);
+        assert_eq!(
+            result.score,
+            Severity::Medium.score() + Severity::Low.score()
+        );
+    }
+
+    #[test]
+    fn test_scan_notebook_code_cell() {
+        let notebook = r##"{
+            "metadata": {"kernelspec": {"language": "python"}},
+            "cells": [
+                {"cell_type": "markdown", "source": ["TODO: not code\n"]},
+                {"cell_type": "code", "source": ["import pandas as pd\n"]},
+                {"cell_type": "code", "source": ["# TODO: clean columns\n", "df = pd.read_csv('a.csv')\n"]}
+            ],
+            "nbformat": 4
+        }"##;
+        let scanner = Scanner::new(test_patterns()).unwrap();
+        let result = scanner.scan_file("analysis.ipynb", notebook);
+        assert_eq!(result.findings.len(), 1);
+        let finding = &result.findings[0];
+        assert_eq!(finding.file, "analysis.ipynb");
+        assert_eq!(finding.line, 2);
+        assert_eq!(finding.cell, Some(3));
+        assert_eq!(finding.message, "Placeholder comment found");
+        // Context comes from the finding's own cell
+        assert_eq!(finding.context_before, None);
+        assert_eq!(
+            finding.context_after.as_deref(),
+            Some("df = pd.read_csv('a.csv')")
+        );
+
+        let result = scanner.scan_file("analysis.ipynb", &notebook.replace("python", "rust"));
+        assert!(result.findings.is_empty());
+        let overrides = HashMap::from([("ipynb".to_string(), Language::Python)]);
+        let scanner = scanner.with_language_overrides(overrides);
+        let result = scanner.scan_file("analysis.ipynb", &notebook.replace("python", "rust"));
+        assert_eq!(result.findings.len(), 1);
+    }
+
+    #[test]
+    fn test_scan_vue_script_block() {
+        let vue = "<template>\n  <!-- TODO: not code -->\n  <div>{{ msg }}</div>\n</template>\n\n<script>\nexport default {\n  // TODO: implement\n};\n</script>\n";
+        let scanner = Scanner::new(test_patterns()).unwrap();
+        let result = scanner.scan_file("App.vue", vue);
+        assert_eq!(result.findings.len(), 1);
+        let finding = &result.findings[0];
+        assert_eq!(finding.file, "App.vue");
+        assert_eq!(finding.line, 8);
+        assert_eq!(finding.source_line.as_deref(), Some("  // TODO: implement"));
+        assert_eq!(result.score, finding.score());
+    }
+
+    #[test]
+    fn test_exclude_extensions_on_every_scan_path() {
+        let mut patterns = test_patterns();
+        patterns.push(Pattern {
+            regex: RegexPattern::new("pass".to_string()).unwrap(),
+            severity: Severity::Medium,
+            message: "pass stub".to_string(),
+            category: PatternCategory::Stub,
+            ast_query: Some("(pass_statement) @stub".to_string()),
+            languages: vec!["Python".to_string()],
+            ..Default::default()
+        });
+        for pattern in &mut patterns {
+            pattern.exclude_extensions = vec![".pyi".into(), ".vue".into(), ".ipynb".into()];
+        }
+        let scanner = Scanner::new(patterns).unwrap();
+
+        let python = "# TODO: implement\ndef f():\n    pass\n";
+        let result = scanner.scan_file_with_language("stub.py", python, Language::Python);
+        assert_eq!(result.findings.len(), 2);
+        let result = scanner.scan_file_with_language("stub.pyi", python, Language::Python);
+        assert!(result.findings.is_empty(), "{:?}", result.findings);
+
+        let vue = "<script>\n// TODO: implement\n</script>\n";
+        assert!(scanner.scan_file("App.vue", vue).findings.is_empty());
+        let notebook = r##"{"metadata": {"kernelspec": {"language": "python"}},
+            "cells": [{"cell_type": "code", "source": ["# TODO: implement\n"]}]}"##;
+        assert!(scanner.scan_file("a.ipynb", notebook).findings.is_empty());
+    }
+
+    #[test]
+    fn test_scan_markup_honors_forced_language_and_directives() {
+        let html = "<!-- antislop:disable -->\n<script>\n// TODO: hidden\n</script>\n<!-- antislop:enable -->\n<script>\n# TODO: not a js comment\n// TODO: shown\n</script>\n";
+        let scanner = Scanner::new(test_patterns()).unwrap();
+        let lines: Vec<usize> = scanner
+            .scan_file("page.htm", html)
+            .findings
+            .iter()
+            .map(|f| f.line)
+            .collect();
+        assert_eq!(lines, vec![8]);
+
+        let overrides = HashMap::from([("htm".to_string(), Language::Python)]);
+        let scanner = scanner.with_language_overrides(overrides);
+        let lines: Vec<usize> = scanner
+            .scan_file("page.htm", html)
+            .findings
+            .iter()
+            .map(|f| f.line)
+            .collect();
+        assert_eq!(lines, vec![7]);
+    }
+
+    #[test]
+    fn test_default_extensions_map_to_languages() {
+        for ext in Config::default().file_extensions {
+            let path = format!("file{}", ext);
+            let ext = ext.trim_start_matches('.');
+            let is_container = ext == NOTEBOOK_EXTENSION || MARKUP_EXTENSIONS.contains(&ext);
+            assert!(
+                is_container || Language::from_path(Path::new(&path)) != Language::Unknown,
+                "{} maps to no language",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_language_extensions_round_trip() {
+        for &lang in Language::ALL {
+            for ext in lang.extensions() {
+                let path = format!("file.{}", ext);
+                assert_eq!(Language::from_path(Path::new(&path)), lang);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sort_findings_orders_and_dedups() {
+        let finding = |file: &str, column, category, regex: &str| Finding {
+            file: file.to_string(),
+            line: 1,
+            column,
+            severity: Severity::Medium,
+            category,
+            message: "msg".to_string(),
+            match_text: String::new(),
+            pattern_regex: regex.to_string(),
+            source_line: None,
+            context_before: None,
+            context_after: None,
+            ..Default::default()
+        };
+        let mut findings = vec![
+            finding("b.py", 1, PatternCategory::Placeholder, "TODO"),
+            finding("a.py", 1, PatternCategory::Stub, "pass"),
+            finding("a.py", 5, PatternCategory::Placeholder, "TODO"),
+            finding("a.py", 1, PatternCategory::NamingConvention, "name"),
+            finding("a.py", 5, PatternCategory::Placeholder, "FIXME"),
+            finding("a.py", 1, PatternCategory::NamingConvention, "name"),
+        ];
+        sort_findings(&mut findings);
+
+        let order: Vec<_> = findings
+            .iter()
+            .map(|f| (f.file.as_str(), f.column, f.pattern_regex.as_str()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                ("a.py", 1, "name"),
+                ("a.py", 1, "pass"),
+                ("a.py", 5, "FIXME"),
+                ("a.py", 5, "TODO"),
+                ("b.py", 1, "TODO"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_language_from_name() {
+        assert_eq!(Language::from_name("cpp"), Some(Language::CCpp));
+        assert_eq!(Language::from_name("C++"), Some(Language::CCpp));
+        assert_eq!(Language::from_name("python"), Some(Language::Python));
+        assert_eq!(Language::from_name("ts"), Some(Language::TypeScript));
+        assert_eq!(Language::from_name("cobol"), None);
+    }
+
+    #[test]
+    fn test_scan_file_with_language_bypasses_extension() {
+        let scanner = Scanner::new(test_patterns()).unwrap();
+        let code = "x = 1  # TODO: later\n";
+        // A `.rs` path would only see `//` comments
+        assert!(scanner.scan_file("fake.rs", code).findings.is_empty());
+
+        let result = scanner.scan_file_with_language("fake.rs", code, Language::Python);
+        assert_eq!(result.findings.len(), 1);
+        assert_eq!(result.findings[0].file, "fake.rs");
+        assert_eq!(result.findings[0].line, 1);
+
+        // Test files are still ignored
+        let scanner = scanner.with_test_files_ignored(&[]).unwrap();
+        assert!(scanner
+            .scan_file_with_language("fake_test.rs", code, Language::Python)
+            .findings
+            .is_empty());
+    }
+
+    #[test]
+    fn test_language_overrides() {
+        let overrides = HashMap::from([("h".to_string(), Language::from_name("cpp").unwrap())]);
+        let scanner = Scanner::new(test_patterns())
+            .unwrap()
+            .with_language_overrides(overrides);
+        assert_eq!(
+            scanner.resolve_language(Path::new("include/util.h")),
+            Language::CCpp
+        );
+        assert_eq!(
+            scanner.resolve_language(Path::new("main.py")),
+            Language::Python
+        );
+
+        // An override changes which extractor sees the file
+        let code = "# TODO: fix this\n";
+        let scanner = Scanner::new(test_patterns()).unwrap();
+        assert!(scanner.scan_file("util.h", code).findings.is_empty());
+        let scanner =
+            scanner.with_language_overrides(HashMap::from([("h".to_string(), Language::Python)]));
+        assert_eq!(scanner.scan_file("util.h", code).findings.len(), 1);
+    }
+
     #[test]
     fn test_language_detection_all_types() {
         // Test more file extensions
@@ -446,10 +2440,14 @@ mod tests {
             Language::from_path(Path::new("test.scala")),
             Language::Scala
         );
+        assert_eq!(Language::from_path(Path::new("test.dart")), Language::Dart);
         assert_eq!(Language::from_path(Path::new("test.sh")), Language::Shell);
         assert_eq!(Language::from_path(Path::new("test.bash")), Language::Shell);
         assert_eq!(Language::from_path(Path::new("test.zsh")), Language::Shell);
         assert_eq!(Language::from_path(Path::new("test.fish")), Language::Shell);
+        assert_eq!(Language::from_path(Path::new("README.md")), Language::Text);
+        assert_eq!(Language::from_path(Path::new("notes.txt")), Language::Text);
+        assert_eq!(Language::from_path(Path::new("index.rst")), Language::Text);
     }
 
     #[test]
@@ -472,6 +2470,8 @@ mod tests {
             line: 10,
             column: 5,
             content: "TODO: implement this".to_string(),
+            prefix_len: 3,
+            kind: CommentKind::Line,
         };
         assert_eq!(comment.line, 10);
         assert_eq!(comment.column, 5);
@@ -492,6 +2492,7 @@ mod tests {
             source_line: None,
             context_before: None,
             context_after: None,
+            ..Default::default()
         };
         assert_eq!(finding.file, "test.py");
         assert_eq!(finding.line, 10);
@@ -505,6 +2506,7 @@ mod tests {
             path: "test.py".to_string(),
             findings: vec![],
             score: 0,
+            lines: 0,
         };
         assert_eq!(result.path, "test.py");
         assert!(result.findings.is_empty());
@@ -537,8 +2539,10 @@ mod tests {
                 source_line: None,
                 context_before: None,
                 context_after: None,
+                ..Default::default()
             }],
             score: 5,
+            lines: 0,
         }];
         let summary = ScanSummary::new(&results);
         assert_eq!(summary.files_scanned, 1);
@@ -555,6 +2559,122 @@ mod tests {
         );
     }
 
+    fn make_result(path: &str, severities: &[Severity]) -> FileScanResult {
+        let findings: Vec<Finding> = severities
+            .iter()
+            .enumerate()
+            .map(|(idx, severity)| Finding {
+                file: path.to_string(),
+                line: idx + 1,
+                column: 1,
+                severity: severity.clone(),
+                category: PatternCategory::Placeholder,
+                message: "TODO".to_string(),
+                match_text: "TODO".to_string(),
+                pattern_regex: "(?i)todo".to_string(),
+                source_line: None,
+                context_before: None,
+                context_after: None,
+                ..Default::default()
+            })
+            .collect();
+        let score = findings.iter().map(|f| f.severity.score()).sum();
+        FileScanResult {
+            path: path.to_string(),
+            findings,
+            score,
+            lines: 0,
+        }
+    }
+
+    #[test]
+    fn test_file_scan_result_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<FileScanResult>();
+        assert_send_sync::<ScanSummary>();
+    }
+
+    #[test]
+    fn test_file_scan_result_merge() {
+        let mut result = make_result("a.py", &[Severity::Low]);
+        result.merge(make_result("a.py", &[Severity::High, Severity::Medium]));
+        assert_eq!(result.findings.len(), 3);
+        assert_eq!(result.score, 21);
+    }
+
+    #[test]
+    fn test_scan_summary_from_iter_out_of_order() {
+        let results = vec![
+            make_result("a.py", &[Severity::Medium]),
+            make_result("b.py", &[]),
+            make_result("c.py", &[Severity::High, Severity::Low]),
+        ];
+        let expected = ScanSummary::new(&results);
+
+        let summary: ScanSummary = results.iter().rev().cloned().collect();
+        assert_eq!(summary.files_scanned, expected.files_scanned);
+        assert_eq!(summary.files_with_findings, expected.files_with_findings);
+        assert_eq!(summary.total_findings, expected.total_findings);
+        assert_eq!(summary.total_score, expected.total_score);
+        assert_eq!(summary.by_severity, expected.by_severity);
+        assert_eq!(summary.by_category, expected.by_category);
+
+        // Folding per-worker summaries gives the same totals
+        let mut folded: ScanSummary = results[2..].iter().cloned().collect();
+        folded.merge(results[..2].iter().cloned().collect());
+        assert_eq!(folded.total_score, expected.total_score);
+        assert_eq!(folded.files_scanned, 3);
+        assert_eq!(folded.by_severity, expected.by_severity);
+    }
+
+    #[test]
+    fn test_scan_summary_parseable_line() {
+        let scanner = Scanner::new(test_patterns()).unwrap();
+        let results = vec![
+            scanner.scan_file("a.py", "# TODO: fix this # for now we do this"),
+            scanner.scan_file("b.py", "x = 1\n"),
+        ];
+        let summary = ScanSummary::new(&results);
+        assert_eq!(
+            summary.parseable_line(),
+            "antislop: files=2 findings=2 score=6 critical=0 high=0 medium=1 low=1"
+        );
+    }
+
+    #[test]
+    fn test_scan_summary_per_kloc() {
+        let scanner = Scanner::new(test_patterns()).unwrap();
+        // 250 non-blank lines with one medium TODO (score 5), plus blank lines
+        let mut code = "# TODO: fix\n\n".to_string();
+        code.push_str(&"x = 1\n\n".repeat(249));
+        let result = scanner.scan_file("a.py", &code);
+        assert_eq!(result.lines, 250);
+
+        let summary = ScanSummary::new(&[
+            result,
+            scanner.scan_file("b.py", "y = 2\n".repeat(250).as_str()),
+        ]);
+        assert_eq!(summary.lines_scanned, 500);
+        assert_eq!(summary.total_score, 5);
+        assert_eq!(summary.score_per_kloc(), 10.0);
+        assert_eq!(summary.findings_per_kloc(), 2.0);
+        assert_eq!(ScanSummary::default().score_per_kloc(), 0.0);
+    }
+
+    #[test]
+    fn test_scan_summary_sloppiest_files() {
+        let summary = ScanSummary::new(&[
+            make_result("a.py", &[Severity::Medium]),
+            make_result("b.py", &[]),
+            make_result("c.py", &[Severity::High, Severity::Low]),
+            make_result("d.py", &[Severity::Low]),
+        ]);
+
+        assert_eq!(summary.sloppiest_files(2), vec![("c.py", 16), ("a.py", 5)]);
+        assert_eq!(summary.sloppiest_files(10).len(), 3);
+        assert_eq!(summary.clean_files(), 1);
+    }
+
     #[test]
     fn test_scan_summary_new_empty_results() {
         let results = vec![
@@ -562,11 +2682,13 @@ mod tests {
                 path: "clean.py".to_string(),
                 findings: vec![],
                 score: 0,
+                lines: 0,
             },
             FileScanResult {
                 path: "sloppy.py".to_string(),
                 findings: vec![],
                 score: 0,
+                lines: 0,
             },
         ];
         let summary = ScanSummary::new(&results);
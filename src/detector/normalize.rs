@@ -0,0 +1,90 @@
+//! Comment normalization for matching stylized text.
+//!
+//! Each character is NFKC-normalized with its accents stripped, then
+//! case-folded, and unusual spaces become plain ones, so `ＴＯＤＯ`, `Todo`
+//! and `tödo` all read as `todo` and a no-break space as a space. Each
+//! normalized byte remembers the source byte it came from, so a match maps
+//! back to the original text for columns and `match_text`.
+
+use std::ops::Range;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Normalized text with a map back to the source it came from.
+pub(crate) struct NormalizedText<'a> {
+    source: &'a str,
+    /// The normalized text that patterns are matched against.
+    pub text: String,
+    /// Source byte offset of the character each normalized byte came from.
+    offsets: Vec<usize>,
+}
+
+impl<'a> NormalizedText<'a> {
+    pub fn new(source: &'a str) -> Self {
+        let mut text = String::with_capacity(source.len());
+        let mut offsets = Vec::with_capacity(source.len());
+
+        for (idx, ch) in source.char_indices() {
+            // Accents are only separable once decomposed; recompose the rest
+            let normalized = std::iter::once(ch)
+                .nfkd()
+                .filter(|c| !is_combining_mark(*c))
+                .nfkc()
+                .flat_map(char::to_lowercase);
+            for folded in normalized {
+                let folded = if folded.is_whitespace() && folded != '\n' {
+                    ' '
+                } else {
+                    folded
+                };
+                text.push(folded);
+                offsets.extend(std::iter::repeat_n(idx, folded.len_utf8()));
+            }
+        }
+
+        Self {
+            source,
+            text,
+            offsets,
+        }
+    }
+
+    /// Source byte range covering the characters that produced `range` of
+    /// the normalized text.
+    pub fn source_range(&self, range: Range<usize>) -> Range<usize> {
+        if range.is_empty() {
+            let start = self.offsets.get(range.start).copied();
+            let start = start.unwrap_or(self.source.len());
+            return start..start;
+        }
+        let start = self.offsets[range.start];
+        let last = self.offsets[range.end - 1];
+        let end = last + self.source[last..].chars().next().map_or(0, char::len_utf8);
+        start..end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalized_text_maps_back_to_source() {
+        let source = "note: ＴＯＤＯ fix\u{a0}ﬁle née";
+        let normalized = NormalizedText::new(source);
+        assert_eq!(normalized.text, "note: todo fix file nee");
+
+        let start = normalized.text.find("todo").unwrap();
+        let range = normalized.source_range(start..start + 4);
+        assert_eq!(&source[range], "ＴＯＤＯ");
+
+        // A match ending inside a ligature's expansion covers the ligature
+        let start = normalized.text.find("file").unwrap();
+        let range = normalized.source_range(start..start + 1);
+        assert_eq!(&source[range], "ﬁ");
+
+        let start = normalized.text.find("nee").unwrap();
+        let range = normalized.source_range(start..start + 3);
+        assert_eq!(&source[range], "née");
+    }
+}
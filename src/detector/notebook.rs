@@ -0,0 +1,105 @@
+//! Jupyter notebook (`.ipynb`) support.
+//!
+//! Code cells are concatenated into a single source so the regular
+//! extractors can scan them, and each line of that source is mapped back to
+//! the cell it came from.
+
+use super::Language;
+use serde_json::Value;
+use std::ops::Range;
+
+/// File extension of Jupyter notebooks.
+pub const NOTEBOOK_EXTENSION: &str = "ipynb";
+
+/// Code cells of a notebook, flattened into one source.
+#[derive(Debug)]
+pub(crate) struct NotebookSource {
+    /// Code-cell sources joined with newlines.
+    pub code: String,
+    /// Language of the notebook's kernel.
+    pub language: Language,
+    /// For each code cell, its 1-indexed number among all cells and the
+    /// 0-indexed lines of `code` it spans.
+    cells: Vec<(usize, Range<usize>)>,
+}
+
+impl NotebookSource {
+    /// Parse notebook JSON. Returns `None` if it is not a valid notebook.
+    pub fn parse(content: &str) -> Option<Self> {
+        let notebook: Value = serde_json::from_str(content).ok()?;
+        let cells = notebook.get("cells")?.as_array()?;
+
+        let language = notebook
+            .pointer("/metadata/language_info/name")
+            .or_else(|| notebook.pointer("/metadata/kernelspec/language"))
+            .and_then(Value::as_str)
+            .and_then(Language::from_name)
+            .unwrap_or(Language::Python);
+
+        let mut source = Self {
+            code: String::new(),
+            language,
+            cells: Vec::new(),
+        };
+        for (idx, cell) in cells.iter().enumerate() {
+            if cell.get("cell_type").and_then(Value::as_str) != Some("code") {
+                continue;
+            }
+            // `source` is either a list of lines (with newlines) or one string
+            let text = match cell.get("source") {
+                Some(Value::String(s)) => s.clone(),
+                Some(Value::Array(parts)) => parts.iter().filter_map(Value::as_str).collect(),
+                _ => continue,
+            };
+            let start = source.cells.last().map_or(0, |(_, lines)| lines.end);
+            for line in text.lines() {
+                source.code.push_str(line);
+                source.code.push('\n');
+            }
+            source
+                .cells
+                .push((idx + 1, start..start + text.lines().count()));
+        }
+        Some(source)
+    }
+
+    /// The cell holding 1-indexed `line` of `code`: its number and the
+    /// 0-indexed lines of `code` it spans.
+    pub fn cell_at(&self, line: usize) -> Option<(usize, Range<usize>)> {
+        let idx = line.checked_sub(1)?;
+        self.cells
+            .iter()
+            .find(|(_, lines)| lines.contains(&idx))
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_notebook_maps_lines_to_cells() {
+        let notebook = r##"{
+            "metadata": {"language_info": {"name": "python"}},
+            "cells": [
+                {"cell_type": "markdown", "source": ["# Title\n"]},
+                {"cell_type": "code", "source": ["import os\n", "x = 1\n"]},
+                {"cell_type": "code", "source": "y = 2\nz = 3"}
+            ]
+        }"##;
+        let source = NotebookSource::parse(notebook).unwrap();
+        assert_eq!(source.language, Language::Python);
+        assert_eq!(source.code, "import os\nx = 1\ny = 2\nz = 3\n");
+        assert_eq!(source.cells, vec![(2, 0..2), (3, 2..4)]);
+        assert_eq!(source.cell_at(3), Some((3, 2..4)));
+        assert_eq!(source.cell_at(0), None);
+        assert_eq!(source.cell_at(5), None);
+    }
+
+    #[test]
+    fn test_parse_invalid_notebook() {
+        assert!(NotebookSource::parse("not json").is_none());
+        assert!(NotebookSource::parse("{}").is_none());
+    }
+}
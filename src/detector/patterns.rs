@@ -2,7 +2,7 @@
 
 use crate::config::{Pattern, Severity};
 use crate::{Error, Result};
-use regex::Regex;
+use regex::{Match, Regex};
 
 /// A compiled pattern ready for matching.
 pub struct CompiledPattern {
@@ -12,6 +12,15 @@ pub struct CompiledPattern {
     pub compiled: Option<Regex>,
 }
 
+impl CompiledPattern {
+    /// Every non-overlapping match in `text`, left to right.
+    pub fn matches_all<'t>(&self, text: &'t str) -> Vec<Match<'t>> {
+        self.compiled
+            .as_ref()
+            .map_or_else(Vec::new, |regex| regex.find_iter(text).collect())
+    }
+}
+
 /// Registry of slop detection patterns.
 pub struct PatternRegistry {
     /// All registered patterns.
@@ -19,10 +28,12 @@ pub struct PatternRegistry {
 }
 
 impl PatternRegistry {
-    /// Create a new registry from pattern definitions.
+    /// Create a new registry from pattern definitions, skipping disabled
+    /// ones.
     pub fn new(patterns: Vec<Pattern>) -> Result<Self> {
         let compiled: Result<Vec<CompiledPattern>> = patterns
             .into_iter()
+            .filter(|p| p.enabled)
             .map(|p| {
                 let compiled = Regex::new(&p.regex).map_err(Error::Regex)?;
                 Ok(CompiledPattern {
@@ -65,6 +76,7 @@ mod tests {
             category: PatternCategory::Placeholder,
             ast_query: None,
             languages: vec![],
+            ..Default::default()
         }];
 
         let registry = PatternRegistry::new(patterns);
@@ -91,6 +103,7 @@ mod tests {
                 category: PatternCategory::Stub,
                 ast_query: None,
                 languages: vec![],
+                ..Default::default()
             },
             Pattern {
                 regex: RegexPattern::new("(?i)MEDIUM:".to_string()).unwrap(),
@@ -99,6 +112,7 @@ mod tests {
                 category: PatternCategory::Stub,
                 ast_query: None,
                 languages: vec![],
+                ..Default::default()
             },
             Pattern {
                 regex: RegexPattern::new("(?i)LOW:".to_string()).unwrap(),
@@ -107,6 +121,7 @@ mod tests {
                 category: PatternCategory::Stub,
                 ast_query: None,
                 languages: vec![],
+                ..Default::default()
             },
         ];
 
@@ -3,7 +3,8 @@
 //! This module provides comment extraction for languages without
 //! tree-sitter support or when tree-sitter is disabled.
 
-use crate::detector::Comment;
+use crate::detector::markers::{comment_kind, strip_comment_markers};
+use crate::detector::{Comment, Language};
 use regex::Regex;
 
 /// Regex-based comment extractor.
@@ -35,6 +36,25 @@ impl RegexExtractor {
         }
     }
 
+    /// Create an extractor that only recognizes `//` and `/* */` comments.
+    pub fn c_style() -> Self {
+        Self {
+            line_comments: vec![Regex::new(r"//.*").unwrap()],
+            block_comments: vec![(Regex::new(r"/\*").unwrap(), Regex::new(r"\*/").unwrap())],
+        }
+    }
+
+    /// Create an extractor using the comment syntax of the given language.
+    ///
+    /// Languages without a known syntax fall back to [`RegexExtractor::new`],
+    /// which recognizes every common comment marker.
+    pub fn for_language(lang: Language) -> Self {
+        match lang {
+            Language::Dart => Self::c_style(),
+            _ => Self::new(),
+        }
+    }
+
     /// Extract all comments from source code.
     pub fn extract(&self, source: &str) -> Vec<Comment> {
         let mut comments = Vec::new();
@@ -44,17 +64,15 @@ impl RegexExtractor {
             // Extract line comments
             for regex in &self.line_comments {
                 if let Some(mat) = regex.find(line) {
-                    let content = mat
-                        .as_str()
-                        .trim_start_matches(&['/', '#', '-', '%', ';', '"', '\''][..])
-                        .trim_start_matches(['/', '"', '\''])
-                        .trim();
+                    let content = strip_comment_markers(mat.as_str());
 
                     if !content.is_empty() {
                         comments.push(Comment {
                             line: idx + 1,
                             column: mat.start() + 1,
                             content: content.to_string(),
+                            prefix_len: mat.as_str().find(content).unwrap_or(0),
+                            kind: comment_kind(mat.as_str()),
                         });
                     }
                 }
@@ -73,34 +91,70 @@ impl RegexExtractor {
         let mut in_block: Option<(usize, usize)> = None; // (start_line, start_col)
 
         for (idx, line) in lines.iter().enumerate() {
-            if let Some((start_line, _)) = in_block {
+            if let Some((start_line, start_col)) = in_block {
                 // Check for block end
                 for (_, end_regex) in &self.block_comments {
-                    if let Some(mat) = end_regex.find(line) {
-                        let _end_col = mat.start();
-                        let content: String = lines[start_line..=idx].join("\n").trim().to_string();
-
-                        if !content.is_empty() {
-                            comments.push(Comment {
-                                line: start_line + 1,
-                                column: 1,
-                                content,
-                            });
-                        }
+                    if end_regex.is_match(line) {
+                        comments.extend(Self::block_comment(
+                            &lines[start_line..=idx],
+                            start_line,
+                            start_col,
+                        ));
                         in_block = None;
                         break;
                     }
                 }
             } else {
                 // Check for block start
-                for (start_regex, _) in &self.block_comments {
+                for (start_regex, end_regex) in &self.block_comments {
                     if let Some(mat) = start_regex.find(line) {
-                        in_block = Some((idx, mat.start()));
+                        // Block opened and closed on the same line
+                        if let Some(end) = end_regex.find(&line[mat.end()..]) {
+                            let raw = &line[mat.end()..mat.end() + end.start()];
+                            let content = raw.trim();
+                            if !content.is_empty() {
+                                comments.push(Comment {
+                                    line: idx + 1,
+                                    column: mat.start() + 1,
+                                    content: content.to_string(),
+                                    prefix_len: mat.len() + raw.len() - raw.trim_start().len(),
+                                    kind: comment_kind(&line[mat.start()..]),
+                                });
+                            }
+                        } else {
+                            in_block = Some((idx, mat.start()));
+                        }
                         break;
                     }
                 }
             }
         }
+
+        // An unterminated block runs to EOF; keep its content rather than dropping it
+        if let Some((start_line, start_col)) = in_block {
+            comments.extend(Self::block_comment(
+                &lines[start_line..],
+                start_line,
+                start_col,
+            ));
+        }
+    }
+
+    /// Build a comment from the lines of a multi-line block whose opening
+    /// marker is at `start_col` of `start_line`.
+    fn block_comment(block: &[&str], start_line: usize, start_col: usize) -> Option<Comment> {
+        let raw = block.join("\n");
+        let content = strip_comment_markers(&raw);
+        if content.is_empty() {
+            return None;
+        }
+        Some(Comment {
+            line: start_line + 1,
+            column: 1,
+            content: content.to_string(),
+            prefix_len: raw.find(content).unwrap_or(0),
+            kind: comment_kind(&block[0][start_col..]),
+        })
     }
 }
 
@@ -113,6 +167,7 @@ impl Default for RegexExtractor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::detector::CommentKind;
 
     #[test]
     fn test_extract_line_comments() {
@@ -143,6 +198,67 @@ def foo():
         assert!(comments[0].content.contains("TODO"));
     }
 
+    #[test]
+    fn test_unterminated_block_comment_is_extracted() {
+        let extractor = RegexExtractor::c_style();
+        let code = "int main() {\n  return 0;\n}\n/* Helpers\n * TODO: implement parser\n";
+        let comments = extractor.extract(code);
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].line, 4);
+        assert!(comments[0].content.contains("TODO: implement parser"));
+    }
+
+    #[test]
+    fn test_single_line_blocks_close_on_their_line() {
+        // A block opened and closed on one line must not swallow the lines
+        // up to the next block's end, whatever the comment syntax
+        let extractor = RegexExtractor::new();
+        let blocks = |code: &str| -> Vec<(usize, String)> {
+            extractor
+                .extract(code)
+                .into_iter()
+                .filter(|c| c.kind != CommentKind::Line)
+                .map(|c| (c.line, c.content))
+                .collect()
+        };
+
+        let c = "/* TODO: one */ int x;\nint y;\n/* FIXME:\n   two */\n";
+        let python = "\"\"\"Summary.\"\"\"\nx = 1\n\"\"\"\nLonger.\n\"\"\"\n";
+        let html = "<!-- TODO: one -->\n<p>x</p>\n<!--\n  two\n-->\n";
+        for (code, first) in [(c, "TODO: one"), (python, "Summary."), (html, "TODO: one")] {
+            let found = blocks(code);
+            let lines: Vec<usize> = found.iter().map(|(line, _)| *line).collect();
+            assert_eq!(lines, vec![1, 3], "{:?}", found);
+            assert_eq!(found[0].1, first);
+        }
+    }
+
+    #[test]
+    fn test_extract_dart_comments() {
+        let extractor = RegexExtractor::for_language(Language::Dart);
+        let code = r#"
+/// Doc comment
+void main() {
+  // TODO: implement
+  var x = a % b; # not a comment
+}
+"#;
+        let comments = extractor.extract(code);
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].content, "Doc comment");
+        assert!(comments[1].content.contains("TODO"));
+    }
+
+    #[test]
+    fn test_semicolon_comment_matches_shared_stripping() {
+        let raw = ";; TODO: handle the empty list";
+        let comments = RegexExtractor::new().extract(&format!("(defun f ()\n  {}\n  nil)\n", raw));
+        assert_eq!(comments.len(), 1);
+        // The tree-sitter extractor strips node text with the same function
+        assert_eq!(comments[0].content, strip_comment_markers(raw));
+        assert_eq!(comments[0].content, "TODO: handle the empty list");
+    }
+
     #[test]
     fn test_extract_shell_comments() {
         let extractor = RegexExtractor::new();
@@ -3,14 +3,27 @@
 //! Provides accurate, language-aware comment extraction using tree-sitter,
 //! as well as AST-level pattern matching for code slop that regex cannot detect.
 
-use crate::config::Pattern;
-use crate::detector::{Comment, Finding, Language};
+use crate::config::{Pattern, PatternCategory, Severity};
+use crate::detector::markers::{comment_kind, join_doc_lines, strip_comment_markers};
+use crate::detector::{Comment, CommentKind, Finding, Language, Span};
 use streaming_iterator::StreamingIterator;
 
 // ...
 
 use tree_sitter::{Node, Parser, Query, QueryCursor};
 
+/// The source span a node covers, in [`Span`]'s 1-indexed terms.
+#[cfg(feature = "tree-sitter")]
+fn node_span(node: &Node) -> Span {
+    let (start, end) = (node.start_position(), node.end_position());
+    Span {
+        start_line: start.row + 1,
+        start_column: start.column + 1,
+        end_line: end.row + 1,
+        end_column: end.column + 1,
+    }
+}
+
 /// Get a comment extractor for the given language.
 #[cfg(feature = "tree-sitter")]
 pub fn get_extractor(lang: Language) -> Option<TreeSitterExtractor> {
@@ -40,6 +53,11 @@ impl TreeSitterExtractor {
     }
 
     /// Extract all comments from source code.
+    ///
+    /// In Rust, consecutive `///` or `//!` lines are returned as one doc
+    /// comment at the first line's position, since the grammar yields a
+    /// node per line; [`Comment::position_of`] maps matches back to their
+    /// own line.
     pub fn extract(&mut self, source: &str) -> Vec<Comment> {
         let mut comments = Vec::new();
 
@@ -49,9 +67,53 @@ impl TreeSitterExtractor {
         };
 
         extract_comments_recursive(&tree.root_node(), source, &mut comments);
+        if self.language == Language::Rust {
+            comments = join_doc_lines(comments, source);
+        }
         comments
     }
 
+    /// Extract function docstrings, which are string literals rather than
+    /// comments in languages like Python. Other languages document
+    /// functions with doc comments and yield nothing here.
+    pub fn extract_docstrings(&mut self, source: &str) -> Vec<Comment> {
+        let query_str = match self.language {
+            Language::Python => {
+                "(function_definition body: (block . (expression_statement (string) @doc)))"
+            }
+            _ => return Vec::new(),
+        };
+        let (Some(tree), Some(ts_lang)) = (self.parser.parse(source, None), self.parser.language())
+        else {
+            return Vec::new();
+        };
+        let Ok(query) = Query::new(&ts_lang, query_str) else {
+            return Vec::new();
+        };
+
+        let mut docstrings = Vec::new();
+        let mut cursor = QueryCursor::new();
+        let mut matches = cursor.matches(&query, tree.root_node(), source.as_bytes());
+        while let Some(mat) = matches.next() {
+            for capture in mat.captures {
+                let node = capture.node;
+                let raw = node.utf8_text(source.as_bytes()).unwrap_or("");
+                let content = strip_comment_markers(raw);
+                if content.is_empty() {
+                    continue;
+                }
+                docstrings.push(Comment {
+                    line: node.start_position().row + 1,
+                    column: node.start_position().column + 1,
+                    content: content.to_string(),
+                    prefix_len: raw.find(content).unwrap_or(0),
+                    kind: CommentKind::Doc,
+                });
+            }
+        }
+        docstrings
+    }
+
     /// Extract AST-level findings using tree-sitter queries.
     ///
     /// Returns findings from patterns that have `ast_query` set and apply to this language.
@@ -127,6 +189,10 @@ impl TreeSitterExtractor {
                         source_line: None, // TODO: Extract from source
                         context_before: None,
                         context_after: None,
+                        informational: pattern.informational,
+                        help_url: pattern.help_url.clone(),
+                        span: Some(node_span(&node)),
+                        ..Default::default()
                     });
                 }
             }
@@ -135,6 +201,55 @@ impl TreeSitterExtractor {
         findings
     }
 
+    /// Flag comments whose wording contradicts the function directly below them.
+    ///
+    /// This is deliberately conservative: a finding is only produced when the
+    /// comment uses one word of a known antonym pair, the function name uses
+    /// the other, and neither mentions both.
+    pub fn extract_stale_comment_findings(&mut self, source: &str) -> Vec<Finding> {
+        let mut findings = Vec::new();
+
+        let tree = match self.parser.parse(source, None) {
+            Some(t) => t,
+            None => return findings,
+        };
+
+        let lines: Vec<&str> = source.lines().collect();
+        find_stale_comments(&tree.root_node(), source, &lines, &mut findings);
+        findings
+    }
+
+    /// Flag unexplained numeric literals in return statements and assignments.
+    ///
+    /// Literals in `allowlist`, in index expressions, in named constants
+    /// (`const` items or UPPER_CASE targets), or with a comment on the same
+    /// or previous line are accepted. At most one finding is made per line.
+    pub fn extract_magic_number_findings(
+        &mut self,
+        source: &str,
+        allowlist: &[String],
+    ) -> Vec<Finding> {
+        let mut findings = Vec::new();
+
+        let tree = match self.parser.parse(source, None) {
+            Some(t) => t,
+            None => return findings,
+        };
+
+        let mut comment_rows = std::collections::HashSet::new();
+        collect_comment_rows(&tree.root_node(), &mut comment_rows);
+
+        let lines: Vec<&str> = source.lines().collect();
+        let context = MagicNumberContext {
+            source,
+            lines: &lines,
+            allowlist,
+            comment_rows: &comment_rows,
+        };
+        find_magic_numbers(&tree.root_node(), &context, &mut findings);
+        findings
+    }
+
     fn language_name(&self) -> &'static str {
         match self.language {
             #[cfg(feature = "python")]
@@ -207,16 +322,22 @@ fn extract_comments_recursive(node: &Node, source: &str, comments: &mut Vec<Comm
     if node.kind().contains("comment") {
         let line = node.start_position().row + 1;
         let column = node.start_position().column + 1;
-        let content = node.utf8_text(source.as_bytes()).unwrap_or("").to_string();
+        let raw = node.utf8_text(source.as_bytes()).unwrap_or("");
 
         // Strip comment markers for consistency with regex extractor
-        let content = strip_comment_markers(&content, node.kind());
+        let content = strip_comment_markers(raw).to_string();
+        let prefix_len = raw.find(content.as_str()).unwrap_or(0);
 
         comments.push(Comment {
             line,
             column,
             content,
+            prefix_len,
+            kind: comment_kind(raw),
         });
+        // A comment's own children, such as the marker and body nodes of a
+        // Rust doc comment, would repeat it with the marker half stripped
+        return;
     }
 
     let mut cursor = node.walk();
@@ -225,34 +346,287 @@ fn extract_comments_recursive(node: &Node, source: &str, comments: &mut Vec<Comm
     }
 }
 
+/// Word pairs whose members contradict each other when one appears in a
+/// comment and the other in the name of the function it documents.
+const ANTONYMS: &[(&str, &str)] = &[
+    ("sum", "product"),
+    ("add", "subtract"),
+    ("increment", "decrement"),
+    ("min", "max"),
+    ("minimum", "maximum"),
+    ("encode", "decode"),
+    ("encrypt", "decrypt"),
+    ("serialize", "deserialize"),
+    ("enable", "disable"),
+    ("open", "close"),
+    ("start", "stop"),
+    ("push", "pop"),
+    ("show", "hide"),
+    ("lock", "unlock"),
+    ("ascending", "descending"),
+    ("first", "last"),
+];
+
+/// Node kinds that declare a named function or method.
+const FUNCTION_KINDS: &[&str] = &[
+    "function_item",
+    "function_definition",
+    "function_declaration",
+    "method_declaration",
+    "method_definition",
+    "method",
+];
+
 #[cfg(feature = "tree-sitter")]
-fn strip_comment_markers(text: &str, kind: &str) -> String {
-    let text = text.trim();
-
-    // Handle various comment styles
-    if kind.contains("line") {
-        // Single-line comments: //, #, --, etc.
-        if let Some(rest) = text.strip_prefix("//") {
-            return rest.trim_start().to_string();
+fn find_stale_comments(node: &Node, source: &str, lines: &[&str], findings: &mut Vec<Finding>) {
+    if node.kind().contains("comment") {
+        if let Some(function) = following_function(node) {
+            let name = function
+                .child_by_field_name("name")
+                .and_then(|n| n.utf8_text(source.as_bytes()).ok())
+                .unwrap_or("");
+            let comment = node.utf8_text(source.as_bytes()).unwrap_or("");
+
+            if let Some((said, meant)) = contradiction(comment, name) {
+                let line_idx = node.start_position().row;
+                findings.push(Finding {
+                    file: String::new(), // Caller will set
+                    line: line_idx + 1,
+                    column: node.start_position().column + 1,
+                    severity: Severity::Low,
+                    category: PatternCategory::StaleComment,
+                    message: format!(
+                        "Comment mentions '{}' but function '{}' suggests '{}'",
+                        said, name, meant
+                    ),
+                    match_text: comment.trim().to_string(),
+                    pattern_regex: "stale_comment".to_string(),
+                    source_line: lines.get(line_idx).map(|s| s.to_string()),
+                    context_before: None,
+                    context_after: lines.get(line_idx + 1).map(|s| s.to_string()),
+                    span: Some(node_span(node)),
+                    ..Default::default()
+                });
+            }
         }
-        if let Some(rest) = text.strip_prefix('#') {
-            return rest.trim_start().to_string();
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        find_stale_comments(&child, source, lines, findings);
+    }
+}
+
+/// Node kinds of numeric literals across the supported grammars.
+const NUMBER_KINDS: &[&str] = &[
+    "integer",
+    "float",
+    "number",
+    "integer_literal",
+    "float_literal",
+    "int_literal",
+    "number_literal",
+    "decimal_integer_literal",
+    "decimal_floating_point_literal",
+];
+
+/// Node kinds in which an unexplained literal is a magic number.
+const MAGIC_CONTEXT_KINDS: &[&str] = &[
+    "return_statement",
+    "return_expression",
+    "assignment",
+    "augmented_assignment",
+    "assignment_expression",
+    "augmented_assignment_expression",
+    "compound_assignment_expr",
+    "variable_declarator",
+    "let_declaration",
+    "short_var_declaration",
+    "assignment_statement",
+    "var_spec",
+    "init_declarator",
+];
+
+/// Node kinds that give a literal a name or make it an index.
+const NAMED_CONSTANT_KINDS: &[&str] = &[
+    "const_item",
+    "static_item",
+    "const_declaration",
+    "const_spec",
+    "enum_item",
+    "enum_declaration",
+    "subscript",
+    "subscript_expression",
+    "index_expression",
+    "element_reference",
+];
+
+#[cfg(feature = "tree-sitter")]
+struct MagicNumberContext<'a> {
+    source: &'a str,
+    lines: &'a [&'a str],
+    allowlist: &'a [String],
+    comment_rows: &'a std::collections::HashSet<usize>,
+}
+
+#[cfg(feature = "tree-sitter")]
+fn collect_comment_rows(node: &Node, rows: &mut std::collections::HashSet<usize>) {
+    if node.kind().contains("comment") {
+        rows.extend(node.start_position().row..=node.end_position().row);
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_comment_rows(&child, rows);
+    }
+}
+
+#[cfg(feature = "tree-sitter")]
+fn find_magic_numbers(node: &Node, context: &MagicNumberContext, findings: &mut Vec<Finding>) {
+    if NUMBER_KINDS.contains(&node.kind()) {
+        let row = node.start_position().row;
+        let text = node.utf8_text(context.source.as_bytes()).unwrap_or("");
+        let normalized = text.replace('_', "");
+        let explained = context.comment_rows.contains(&row)
+            || (row > 0 && context.comment_rows.contains(&(row - 1)));
+        let already_flagged = findings.last().is_some_and(|f| f.line == row + 1);
+
+        if !explained
+            && !already_flagged
+            && !context.allowlist.contains(&normalized)
+            && in_magic_context(node, context.source)
+        {
+            findings.push(Finding {
+                file: String::new(), // Caller will set
+                line: row + 1,
+                column: node.start_position().column + 1,
+                severity: Severity::Low,
+                category: PatternCategory::Stub,
+                message: format!(
+                    "Magic number {} without explanation; name it or add a comment",
+                    text
+                ),
+                match_text: text.to_string(),
+                pattern_regex: "magic_number".to_string(),
+                source_line: context.lines.get(row).map(|s| s.to_string()),
+                context_before: None,
+                context_after: None,
+                span: Some(node_span(node)),
+                ..Default::default()
+            });
         }
-        if let Some(rest) = text.strip_prefix("--") {
-            return rest.trim_start().to_string();
+        return;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        find_magic_numbers(&child, context, findings);
+    }
+}
+
+/// Whether a literal sits in a return or assignment that does not name it.
+#[cfg(feature = "tree-sitter")]
+fn in_magic_context(literal: &Node, source: &str) -> bool {
+    let mut current = literal.parent();
+    while let Some(node) = current {
+        let kind = node.kind();
+        if NAMED_CONSTANT_KINDS.contains(&kind) {
+            return false;
         }
-    } else if kind.contains("block") {
-        // Block comments: /* */, {- -}, etc.
-        if let Some(rest) = text.strip_prefix("/*") {
-            return rest.strip_suffix("*/").unwrap_or(rest).trim().to_string();
+        if MAGIC_CONTEXT_KINDS.contains(&kind) {
+            let target = ["left", "name", "pattern", "declarator"]
+                .iter()
+                .find_map(|field| node.child_by_field_name(field))
+                .and_then(|n| n.utf8_text(source.as_bytes()).ok())
+                .unwrap_or("");
+            return !is_constant_name(target);
         }
-        if let Some(rest) = text.strip_prefix("{-") {
-            return rest.strip_suffix("-}").unwrap_or(rest).trim().to_string();
+        if kind == "block" || FUNCTION_KINDS.contains(&kind) {
+            return false;
         }
+        current = node.parent();
     }
+    false
+}
 
-    // If no marker found, return as-is
-    text.to_string()
+/// UPPER_SNAKE_CASE names conventionally denote named constants.
+#[cfg(feature = "tree-sitter")]
+fn is_constant_name(name: &str) -> bool {
+    name.chars().any(|c| c.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Return the function declared on the line directly after a comment, if any.
+#[cfg(feature = "tree-sitter")]
+fn following_function<'a>(comment: &Node<'a>) -> Option<Node<'a>> {
+    let mut next = comment.next_named_sibling()?;
+    if next.start_position().row != comment.end_position().row + 1 {
+        return None;
+    }
+    // JavaScript/TypeScript: `export function foo() {}`
+    if next.kind() == "export_statement" {
+        next = next.child_by_field_name("declaration")?;
+    }
+    FUNCTION_KINDS.contains(&next.kind()).then_some(next)
+}
+
+/// Find an antonym pair split between comment wording and function name.
+fn contradiction(comment: &str, function_name: &str) -> Option<(&'static str, &'static str)> {
+    let comment_words: Vec<String> = comment
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| stem(&w.to_lowercase()))
+        .collect();
+    let name_words: Vec<String> = split_identifier(function_name)
+        .iter()
+        .map(|w| stem(w))
+        .collect();
+
+    let has = |words: &[String], w: &str| words.iter().any(|x| x == w);
+
+    for &(a, b) in ANTONYMS {
+        for (said, meant) in [(a, b), (b, a)] {
+            if has(&comment_words, said)
+                && !has(&comment_words, meant)
+                && has(&name_words, meant)
+                && !has(&name_words, said)
+            {
+                return Some((said, meant));
+            }
+        }
+    }
+    None
+}
+
+/// Split a snake_case, kebab-case, or camelCase identifier into lowercase words.
+fn split_identifier(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    for c in name.chars() {
+        if c == '_' || c == '-' {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+        } else if c.is_uppercase() && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+            current.extend(c.to_lowercase());
+        } else {
+            current.extend(c.to_lowercase());
+        }
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Strip a plural/third-person `s` so "returns the sums" matches "sum".
+fn stem(word: &str) -> String {
+    match word.strip_suffix('s') {
+        Some(rest) if rest.len() > 2 && !rest.ends_with('s') => rest.to_string(),
+        _ => word.to_string(),
+    }
 }
 
 /// Dummy extractor when tree-sitter is disabled.
@@ -284,6 +658,47 @@ def foo():
         assert!(comments.len() >= 2);
     }
 
+    #[test]
+    fn test_comment_content_matches_regex_extractor() {
+        let code = "/// TODO: document this\nfn f() {}\n/* FIXME: block */\n";
+        let mut extractor = get_extractor(Language::Rust).expect("Rust extractor");
+        let tree_sitter: Vec<String> = extractor
+            .extract(code)
+            .into_iter()
+            .map(|c| c.content)
+            .collect();
+        let regex: Vec<String> = crate::detector::RegexExtractor::c_style()
+            .extract(code)
+            .into_iter()
+            .map(|c| c.content)
+            .collect();
+        assert_eq!(tree_sitter, vec!["TODO: document this", "FIXME: block"]);
+        assert_eq!(tree_sitter, regex);
+    }
+
+    #[test]
+    fn test_comment_child_nodes_not_reported() {
+        let code = "/// TODO: document this\n//! FIXME: crate docs\nfn f() {}\n";
+        let mut extractor = get_extractor(Language::Rust).expect("Rust extractor");
+        let comments = extractor.extract(code);
+        let positions: Vec<(usize, usize)> = comments.iter().map(|c| (c.line, c.column)).collect();
+        assert_eq!(positions, vec![(1, 1), (2, 1)]);
+    }
+
+    #[test]
+    fn test_rust_doc_lines_joined() {
+        let code = "//! Crate docs.\n/// Loads the config.\n///\n/// TODO: cache it\nfn load() {}\n// plain\n";
+        let mut extractor = get_extractor(Language::Rust).expect("Rust extractor");
+        let comments = extractor.extract(code);
+
+        // `//!` and `///` runs stay separate, as do plain comments
+        let lines: Vec<usize> = comments.iter().map(|c| c.line).collect();
+        assert_eq!(lines, vec![1, 2, 6]);
+        assert_eq!(comments[1].content, "Loads the config.\n\nTODO: cache it");
+        assert_eq!(comments[1].column, 1);
+        assert_eq!(comments[1].kind, CommentKind::Doc);
+    }
+
     #[test]
     fn test_javascript_extractor() {
         let mut extractor = get_extractor(Language::JavaScript).expect("JS extractor");
@@ -333,6 +748,7 @@ fn foo() -> Option<()> {
             category: PatternCategory::Stub,
             ast_query: Some("(raise_statement) @stub".to_string()),
             languages: vec!["Python".to_string()],
+            ..Default::default()
         }];
 
         let code = r#"
@@ -356,6 +772,7 @@ def process_data(data):
             category: PatternCategory::Stub,
             ast_query: Some("(pass_statement) @stub".to_string()),
             languages: vec!["Python".to_string()],
+            ..Default::default()
         }];
 
         let code = r#"
@@ -368,6 +785,104 @@ def stub_function():
         assert_eq!(findings[0].severity, Severity::Medium);
     }
 
+    #[test]
+    fn test_stale_comment_mismatch() {
+        let mut extractor = get_extractor(Language::Rust).expect("Rust extractor");
+        let code = r#"
+// Returns the sum of both values
+fn product(a: u32, b: u32) -> u32 {
+    a * b
+}
+"#;
+        let findings = extractor.extract_stale_comment_findings(code);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].category, PatternCategory::StaleComment);
+        assert_eq!(findings[0].line, 2);
+        assert!(findings[0].message.contains("'sum'"));
+    }
+
+    #[test]
+    fn test_stale_comment_consistent_is_clean() {
+        let mut extractor = get_extractor(Language::Python).expect("Python extractor");
+        let code = r#"
+# Returns the sum of both values
+def sum_values(a, b):
+    return a + b
+
+# Opens the file, then closes it again
+def close_file(f):
+    f.close()
+
+# Computes the maximum
+
+def min_value(xs):
+    return min(xs)
+"#;
+        assert!(extractor.extract_stale_comment_findings(code).is_empty());
+    }
+
+    #[test]
+    fn test_magic_number_unexplained_return() {
+        let mut extractor = get_extractor(Language::Python).expect("Python extractor");
+        let allowlist = crate::config::MagicNumbers::default().allowlist;
+        let code = "def area(r):\n    return 42 * 3.14159\n";
+        let findings = extractor.extract_magic_number_findings(code, &allowlist);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].line, 2);
+        assert_eq!(findings[0].match_text, "42");
+        assert_eq!(findings[0].category, PatternCategory::Stub);
+        assert_eq!(findings[0].severity, Severity::Low);
+    }
+
+    #[test]
+    fn test_magic_number_named_or_explained_is_clean() {
+        let mut extractor = get_extractor(Language::Python).expect("Python extractor");
+        let allowlist = crate::config::MagicNumbers::default().allowlist;
+        let code = r#"
+SCALE_FACTOR = 42
+PI = 3.14159
+
+def area(r):
+    return SCALE_FACTOR * PI * r * r
+
+def first(xs):
+    total = 0
+    # Leap-year cycle length in days
+    cycle = 1461
+    return xs[3]
+"#;
+        assert!(extractor
+            .extract_magic_number_findings(code, &allowlist)
+            .is_empty());
+
+        let mut extractor = get_extractor(Language::Rust).expect("Rust extractor");
+        let code = "const TIMEOUT_MS: u64 = 1500;\nfn timeout() -> u64 {\n    TIMEOUT_MS\n}\n";
+        assert!(extractor
+            .extract_magic_number_findings(code, &allowlist)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_magic_number_allowlist() {
+        let mut extractor = get_extractor(Language::Python).expect("Python extractor");
+        let code = "def retries():\n    return 3\n";
+        assert_eq!(
+            extractor
+                .extract_magic_number_findings(code, &["0".to_string()])
+                .len(),
+            1
+        );
+        assert!(extractor
+            .extract_magic_number_findings(code, &["3".to_string()])
+            .is_empty());
+    }
+
+    #[test]
+    fn test_split_identifier() {
+        assert_eq!(split_identifier("getMaxValue"), vec!["get", "max", "value"]);
+        assert_eq!(split_identifier("encode_url"), vec!["encode", "url"]);
+    }
+
     #[test]
     fn test_ast_query_todo_macro() {
         let mut extractor = get_extractor(Language::Rust).expect("Rust extractor");
@@ -379,6 +894,7 @@ def stub_function():
             category: PatternCategory::Stub,
             ast_query: Some("(macro_invocation) @stub".to_string()),
             languages: vec!["Rust".to_string()],
+            ..Default::default()
         }];
 
         let code = r#"
@@ -0,0 +1,141 @@
+//! Repeated docstring detection.
+//!
+//! Assistants often document every function with the same stock sentence
+//! ("Returns the result."). A docstring that appears verbatim on several
+//! functions across a scan describes none of them, so every occurrence is
+//! reported as boilerplate.
+
+use crate::config::{PatternCategory, Severity};
+use crate::detector::{Comment, Finding};
+use std::collections::HashMap;
+
+/// Identical docstrings needed before they are flagged, unless configured.
+pub const DEFAULT_REPEAT_THRESHOLD: usize = 3;
+
+/// Collects doc comments across files and flags those repeated verbatim.
+#[derive(Debug)]
+pub struct DocstringChecker {
+    threshold: usize,
+    /// Docstring text to every `(file, comment)` carrying it.
+    occurrences: HashMap<String, Vec<(String, Comment)>>,
+    /// Docstring texts in order of first appearance, for stable output.
+    order: Vec<String>,
+}
+
+impl DocstringChecker {
+    /// Flag docstrings that appear on at least `threshold` functions.
+    /// Thresholds below 2 are raised to 2, since a single docstring cannot
+    /// repeat.
+    pub fn new(threshold: usize) -> Self {
+        Self {
+            threshold: threshold.max(2),
+            occurrences: HashMap::new(),
+            order: Vec::new(),
+        }
+    }
+
+    /// Record a file's doc comments, as returned by
+    /// [`Scanner::doc_comments`](crate::Scanner::doc_comments).
+    pub fn add_file(&mut self, path: &str, docs: Vec<Comment>) {
+        for doc in docs.into_iter().filter(|d| !d.content.trim().is_empty()) {
+            let entry = self.occurrences.entry(doc.content.clone()).or_default();
+            if entry.is_empty() {
+                self.order.push(doc.content.clone());
+            }
+            entry.push((path.to_string(), doc));
+        }
+    }
+
+    /// A boilerplate finding for each occurrence of every repeated docstring.
+    pub fn check(&self) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for text in &self.order {
+            let occurrences = &self.occurrences[text];
+            if occurrences.len() < self.threshold {
+                continue;
+            }
+            for (path, doc) in occurrences {
+                findings.push(Finding {
+                    file: path.clone(),
+                    line: doc.line,
+                    column: doc.column + doc.prefix_len,
+                    severity: Severity::Medium,
+                    category: PatternCategory::Boilerplate,
+                    message: format!(
+                        "Docstring repeated verbatim on {} functions",
+                        occurrences.len()
+                    ),
+                    match_text: doc.content.clone(),
+                    pattern_regex: "repeated_docstring".to_string(),
+                    source_line: None,
+                    context_before: None,
+                    context_after: None,
+                    ..Default::default()
+                });
+            }
+        }
+        findings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Scanner;
+
+    #[test]
+    fn test_repeated_docstrings_flagged() {
+        let scanner = Scanner::new(vec![]).unwrap();
+        let rust = "/// Returns the result.\nfn a() {}\n\n/// Returns the result.\nfn b() {}\n\n/// Parses the header.\nfn c() {}\n";
+        let python = "def d():\n    \"\"\"Returns the result.\"\"\"\n    return 1\n\ndef e():\n    \"\"\"Loads the config file.\"\"\"\n";
+
+        let mut checker = DocstringChecker::new(DEFAULT_REPEAT_THRESHOLD);
+        checker.add_file("lib.rs", scanner.doc_comments("lib.rs", rust));
+        checker.add_file("app.py", scanner.doc_comments("app.py", python));
+        let findings = checker.check();
+
+        let locations: Vec<(&str, usize)> =
+            findings.iter().map(|f| (f.file.as_str(), f.line)).collect();
+        assert_eq!(locations, vec![("lib.rs", 1), ("lib.rs", 4), ("app.py", 2)]);
+        assert!(findings
+            .iter()
+            .all(|f| f.category == PatternCategory::Boilerplate
+                && f.match_text == "Returns the result."));
+
+        // Below the threshold nothing is flagged
+        let mut checker = DocstringChecker::new(4);
+        checker.add_file("lib.rs", scanner.doc_comments("lib.rs", rust));
+        checker.add_file("app.py", scanner.doc_comments("app.py", python));
+        assert!(checker.check().is_empty());
+    }
+
+    #[test]
+    fn test_only_function_docs_collected() {
+        let scanner = Scanner::new(vec![])
+            .unwrap()
+            .with_test_files_ignored(&[])
+            .unwrap();
+        let rust = "//! Returns the result.\n\n/// Returns the result.\npub struct A;\n\n/// Returns the result.\nconst B: u8 = 1;\n\n// antislop:disable\n/// Returns the result.\nfn c() {}\n";
+        assert!(scanner.doc_comments("lib.rs", rust).is_empty());
+
+        let function = "/// Returns the result.\nfn a() {}\n";
+        assert_eq!(scanner.doc_comments("lib.rs", function).len(), 1);
+        assert!(scanner
+            .doc_comments("tests/test_lib.rs", function)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_doc_comment_runs_compared_whole() {
+        let scanner = Scanner::new(vec![]).unwrap();
+        let rust = "/// Adds numbers.\n///\n/// # Errors\nfn a() {}\n/// Subtracts.\n///\n/// # Errors\nfn b() {}\n/// Multiplies.\n///\n/// # Errors\nfn c() {}\n";
+
+        let docs = scanner.doc_comments("lib.rs", rust);
+        assert_eq!(docs.len(), 3);
+        assert_eq!(docs[0].content, "Adds numbers.\n\n# Errors");
+
+        let mut checker = DocstringChecker::new(DEFAULT_REPEAT_THRESHOLD);
+        checker.add_file("lib.rs", docs);
+        assert!(checker.check().is_empty());
+    }
+}
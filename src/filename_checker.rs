@@ -25,6 +25,66 @@ pub struct FilenameCheckConfig {
     pub convention_threshold: f64,
     /// If true, use language conventions as hints when no dominant project convention exists
     pub use_language_hints: bool,
+    /// If true, all-caps stems (`README`, `HTTP_CLIENT`) count as PascalCase;
+    /// otherwise they follow no convention: they still count toward the
+    /// convention ratio's total but are never flagged
+    pub all_caps_as_pascal: bool,
+    /// File names exempt from convention checks, matched case-insensitively
+    /// against the full name or the stem (see [`DEFAULT_EXEMPT_FILENAMES`])
+    pub exempt_filenames: Vec<String>,
+    /// Severity of convention-break findings (default: medium)
+    pub convention_severity: Option<Severity>,
+    /// Severity of duplicate-file findings (default: high)
+    pub duplicate_severity: Option<Severity>,
+}
+
+/// Conventional file names that follow no project naming style.
+pub const DEFAULT_EXEMPT_FILENAMES: &[&str] = &[
+    "README",
+    "LICENSE",
+    "LICENCE",
+    "CHANGELOG",
+    "CONTRIBUTING",
+    "AUTHORS",
+    "NOTICE",
+    "Makefile",
+    "Dockerfile",
+    "Rakefile",
+    "Gemfile",
+    "Procfile",
+    "Justfile",
+    "Vagrantfile",
+    "Jenkinsfile",
+];
+
+impl FilenameCheckConfig {
+    /// Whether `path` is exempt from convention checks by name.
+    fn is_exempt(&self, path: &Path) -> bool {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        self.exempt_filenames
+            .iter()
+            .any(|e| e.eq_ignore_ascii_case(name) || e.eq_ignore_ascii_case(stem))
+    }
+
+    /// Detect a stem's convention, applying the all-caps setting.
+    fn convention_of(&self, stem: &str) -> NamingConvention {
+        if !self.all_caps_as_pascal && is_all_caps(stem) {
+            return NamingConvention::Unknown;
+        }
+        NamingConvention::detect(stem)
+    }
+}
+
+/// Whether a stem is written entirely in capitals, e.g. `README` or `HTTP_CLIENT`.
+///
+/// Mixed-case stems with acronyms such as `HTTPServer` are not all-caps and
+/// are classified by their first letter as usual.
+fn is_all_caps(stem: &str) -> bool {
+    stem.chars().filter(|c| c.is_alphabetic()).count() > 1
+        && stem
+            .chars()
+            .all(|c| c.is_uppercase() || c.is_ascii_digit() || c == '_' || c == '-')
 }
 
 /// Extract suffix patterns from naming patterns (for duplicate detection).
@@ -188,6 +248,8 @@ impl NamingConvention {
             "rb" => Some(NamingConvention::SnakeCase),
             // Lua: convention is snake_case
             "lua" => Some(NamingConvention::SnakeCase),
+            // Dart: effective Dart style requires lowercase_with_underscores
+            "dart" => Some(NamingConvention::SnakeCase),
             // JavaScript/TypeScript: no strong preference, varies by framework
             // React components often use PascalCase, utilities use camelCase
             "js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs" => None,
@@ -209,6 +271,23 @@ impl NamingConvention {
         }
     }
 
+    /// Rewrite `stem` in this convention, e.g. `fileFive` as `file_five`.
+    /// Returns `None` for [`NamingConvention::Unknown`].
+    fn convert(&self, stem: &str) -> Option<String> {
+        let words = split_words(stem);
+        match self {
+            NamingConvention::SnakeCase => Some(words.join("_")),
+            NamingConvention::KebabCase => Some(words.join("-")),
+            NamingConvention::PascalCase => Some(words.iter().map(|w| capitalize(w)).collect()),
+            NamingConvention::CamelCase => {
+                let (first, rest) = words.split_first()?;
+                let rest: String = rest.iter().map(|w| capitalize(w)).collect();
+                Some(format!("{}{}", first, rest))
+            }
+            NamingConvention::Unknown => None,
+        }
+    }
+
     /// Returns a human-readable description of this convention.
     pub fn description(&self) -> &'static str {
         match self {
@@ -221,6 +300,51 @@ impl NamingConvention {
     }
 }
 
+/// Split a stem into lowercase words at `_`, `-` and case boundaries.
+///
+/// A run of capitals is one word, so `HTTPServer` splits as `http`, `server`.
+fn split_words(stem: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for part in stem.split(['_', '-']).filter(|p| !p.is_empty()) {
+        let chars: Vec<char> = part.chars().collect();
+        let mut word = String::new();
+        for (i, &c) in chars.iter().enumerate() {
+            let prev = i.checked_sub(1).map(|j| chars[j]);
+            let next = chars.get(i + 1);
+            let boundary = c.is_uppercase()
+                && prev.is_some_and(|p| {
+                    p.is_lowercase()
+                        || p.is_ascii_digit()
+                        || (p.is_uppercase() && next.is_some_and(|n| n.is_lowercase()))
+                });
+            if boundary && !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            word.extend(c.to_lowercase());
+        }
+        if !word.is_empty() {
+            words.push(word);
+        }
+    }
+    words
+}
+
+/// Uppercase the first character of a lowercase word.
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Whether a file looks like a test, spec, or mock by its name.
+pub fn is_test_file(path: &Path) -> bool {
+    path.file_stem()
+        .and_then(|s| s.to_str())
+        .is_some_and(|stem| stem.contains("test") || stem.contains("spec") || stem.contains("mock"))
+}
+
 /// A group of files for convention analysis.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct FileGroup {
@@ -234,11 +358,10 @@ impl FileGroup {
     /// Create a file group from a path.
     fn from_path(path: &Path) -> Option<Self> {
         let extension = path.extension()?.to_str()?;
-        let stem = path.file_stem()?.to_str()?;
 
         // Skip test files, benchmarks, mocks for convention analysis
         // These often legitimately use different naming
-        if stem.contains("test") || stem.contains("spec") || stem.contains("mock") {
+        if is_test_file(path) {
             return None;
         }
 
@@ -338,6 +461,11 @@ impl FilenameChecker {
     /// Check for potential duplicate files (AI pattern of creating "alternatives").
     fn check_duplicate_patterns(&self) -> Vec<Finding> {
         let mut findings = Vec::new();
+        let duplicate_severity = self
+            .config
+            .duplicate_severity
+            .clone()
+            .unwrap_or(Severity::High);
 
         // Group files by directory
         let mut by_dir: HashMap<&str, Vec<&str>> = HashMap::new();
@@ -389,7 +517,7 @@ impl FilenameChecker {
                                 file: file_path_str.to_string(),
                                 line: 1,
                                 column: 1,
-                                severity: Severity::High,
+                                severity: duplicate_severity.clone(),
                                 category: crate::config::PatternCategory::NamingConvention,
                                 message: format!(
                                     "Potential duplicate: '{}{}' exists alongside '{}{}'",
@@ -400,6 +528,7 @@ impl FilenameChecker {
                                 source_line: None,
                                 context_before: None,
                                 context_after: None,
+                                ..Default::default()
                             });
                         }
                         break;
@@ -432,7 +561,7 @@ impl FilenameChecker {
                                 file: file_path_str.to_string(),
                                 line: 1,
                                 column: 1,
-                                severity: Severity::High,
+                                severity: duplicate_severity.clone(),
                                 category: crate::config::PatternCategory::NamingConvention,
                                 message: format!(
                                     "Potential duplicate: '{}{}' exists alongside '{}{}'",
@@ -443,6 +572,7 @@ impl FilenameChecker {
                                 source_line: None,
                                 context_before: None,
                                 context_after: None,
+                                ..Default::default()
                             });
                         }
                         break;
@@ -457,6 +587,11 @@ impl FilenameChecker {
     /// Check for naming convention breaks within file groups.
     fn check_convention_breaks(&self) -> Vec<Finding> {
         let mut findings = Vec::new();
+        let convention_severity = self
+            .config
+            .convention_severity
+            .clone()
+            .unwrap_or(Severity::Medium);
 
         for (group, files) in self.grouped_files.iter() {
             // Need minimum files to establish a convention
@@ -468,8 +603,11 @@ impl FilenameChecker {
                 .iter()
                 .filter_map(|path| {
                     let path_obj = Path::new(path);
+                    if self.config.is_exempt(path_obj) {
+                        return None;
+                    }
                     let stem = path_obj.file_stem()?.to_str()?;
-                    Some((NamingConvention::detect(stem), path.clone()))
+                    Some((self.config.convention_of(stem), path.clone()))
                 })
                 .collect();
 
@@ -480,13 +618,14 @@ impl FilenameChecker {
             // Count conventions
             let convention_counts = self.count_conventions(&conventions);
 
-            // Find the dominant convention based on threshold
+            // Find the dominant convention based on threshold. Unknown and
+            // all-caps stems count toward the total but can't dominate
             let total = conventions.len() as f64;
             let dominant = convention_counts
                 .iter()
-                .find(|(_, &count)| {
+                .find(|(&conv, &count)| {
                     let ratio = count as f64 / total;
-                    ratio >= self.config.convention_threshold
+                    conv != NamingConvention::Unknown && ratio >= self.config.convention_threshold
                 })
                 .map(|(conv, _)| *conv);
 
@@ -516,24 +655,40 @@ impl FilenameChecker {
                         .file_name()
                         .and_then(|n| n.to_str())
                         .unwrap_or("");
+                    let stem = Path::new(path)
+                        .file_stem()
+                        .and_then(|s| s.to_str())
+                        .unwrap_or("");
+                    // The stem is a prefix of the name; keep the extension as is
+                    let suggestion = dominant_convention
+                        .convert(stem)
+                        .filter(|s| !s.is_empty() && s != stem)
+                        .map(|s| format!("{}{}", s, &filename[stem.len()..]));
+                    let hint = suggestion
+                        .as_ref()
+                        .map(|s| format!(" (rename to '{}')", s))
+                        .unwrap_or_default();
 
                     findings.push(Finding {
                         file: path.to_string(),
                         line: 1,
                         column: 1,
-                        severity: Severity::Medium,
+                        severity: convention_severity.clone(),
                         category: crate::config::PatternCategory::NamingConvention,
                         message: format!(
-                            "Naming inconsistency: '{}' uses {} but project uses {}",
+                            "Naming inconsistency: '{}' uses {} but project uses {}{}",
                             filename,
                             convention.description(),
-                            dominant_convention.description()
+                            dominant_convention.description(),
+                            hint
                         ),
                         match_text: filename.to_string(),
                         pattern_regex: "naming_convention".to_string(),
                         source_line: None,
                         context_before: None,
                         context_after: None,
+                        suggestion,
+                        ..Default::default()
                     });
                 }
             }
@@ -593,6 +748,104 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_acronym_stems() {
+        assert_eq!(
+            NamingConvention::detect("HTTPServer"),
+            NamingConvention::PascalCase
+        );
+        assert_eq!(
+            NamingConvention::detect("parseHTTPResponse"),
+            NamingConvention::CamelCase
+        );
+        assert!(is_all_caps("README"));
+        assert!(is_all_caps("HTTP_CLIENT"));
+        assert!(!is_all_caps("HTTPServer"));
+        assert!(!is_all_caps("X"));
+    }
+
+    #[test]
+    fn test_exempt_and_acronym_files_not_flagged() {
+        let config = FilenameCheckConfig {
+            min_files_for_convention: 5,
+            convention_threshold: 0.7,
+            use_language_hints: true,
+            exempt_filenames: DEFAULT_EXEMPT_FILENAMES
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            ..Default::default()
+        };
+        let mut checker = FilenameChecker::with_config(config);
+
+        checker.add_file(Path::new("/docs/getting_started.md"));
+        checker.add_file(Path::new("/docs/install_guide.md"));
+        checker.add_file(Path::new("/docs/faq_page.md"));
+        checker.add_file(Path::new("/docs/api_reference.md"));
+        checker.add_file(Path::new("/docs/README.md"));
+        checker.add_file(Path::new("/docs/CHANGELOG.md"));
+
+        checker.add_file(Path::new("/java/Client.java"));
+        checker.add_file(Path::new("/java/Request.java"));
+        checker.add_file(Path::new("/java/Response.java"));
+        checker.add_file(Path::new("/java/Router.java"));
+        checker.add_file(Path::new("/java/HTTPServer.java"));
+
+        assert!(checker.check_convention_breaks().is_empty());
+    }
+
+    #[test]
+    fn test_all_caps_as_pascal_is_flagged_among_snake_case() {
+        let config = FilenameCheckConfig {
+            min_files_for_convention: 5,
+            convention_threshold: 0.7,
+            all_caps_as_pascal: true,
+            ..Default::default()
+        };
+        let mut checker = FilenameChecker::with_config(config);
+        for name in ["one_a", "two_b", "three_c", "four_d", "HTTP"] {
+            checker.add_file(Path::new(&format!("/src/{}.rs", name)));
+        }
+        let findings = checker.check_convention_breaks();
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].file.ends_with("HTTP.rs"));
+    }
+
+    #[test]
+    fn test_all_caps_stems_count_toward_convention_ratio() {
+        let config = FilenameCheckConfig {
+            min_files_for_convention: 5,
+            convention_threshold: 0.7,
+            ..Default::default()
+        };
+        let mut checker = FilenameChecker::with_config(config);
+        // 4 of 7 snake_case files is below the threshold, even though the
+        // all-caps stems follow no convention
+        for name in [
+            "one_a",
+            "two_b",
+            "three_c",
+            "four_d",
+            "fiveE",
+            "HTTP",
+            "TLS_CONFIG",
+        ] {
+            checker.add_file(Path::new(&format!("/src/{}.rs", name)));
+        }
+        assert!(checker.check_convention_breaks().is_empty());
+
+        // All-caps stems alone never set the convention
+        let mut checker = FilenameChecker::with_config(FilenameCheckConfig {
+            min_files_for_convention: 5,
+            convention_threshold: 0.7,
+            ..Default::default()
+        });
+        for name in ["HTTP", "TLS", "DNS_CACHE", "URL_MAP", "one_a"] {
+            checker.add_file(Path::new(&format!("/src/{}.rs", name)));
+        }
+        assert!(checker.check_convention_breaks().is_empty());
+    }
+
     #[test]
     fn test_expected_convention_for_language() {
         assert_eq!(
@@ -626,6 +879,7 @@ mod tests {
             min_files_for_convention: 5,
             convention_threshold: 0.7,
             use_language_hints: false,
+            ..Default::default()
         };
 
         // Create mock patterns for testing
@@ -638,6 +892,7 @@ mod tests {
                 category: PatternCategory::NamingConvention,
                 ast_query: None,
                 languages: vec![],
+                ..Default::default()
             },
             Pattern {
                 regex: crate::config::RegexPattern::new("(?i)_new\\.(rs|py)".to_string())
@@ -647,6 +902,7 @@ mod tests {
                 category: PatternCategory::NamingConvention,
                 ast_query: None,
                 languages: vec![],
+                ..Default::default()
             },
         ];
 
@@ -671,6 +927,7 @@ mod tests {
             min_files_for_convention: 5,
             convention_threshold: 0.6, // 60% threshold
             use_language_hints: false,
+            ..Default::default()
         };
         let mut checker = FilenameChecker::with_config(config);
 
@@ -686,6 +943,58 @@ mod tests {
         let findings = checker.check_convention_breaks();
         assert_eq!(findings.len(), 1);
         assert!(findings[0].file.contains("fileFive"));
+        assert_eq!(findings[0].suggestion.as_deref(), Some("file_five.rs"));
+        assert!(findings[0].message.ends_with("(rename to 'file_five.rs')"));
+    }
+
+    #[test]
+    fn test_convert_between_conventions() {
+        let cases = [
+            ("fileFive", NamingConvention::SnakeCase, "file_five"),
+            ("HTTPServer", NamingConvention::SnakeCase, "http_server"),
+            (
+                "parse_v2Header",
+                NamingConvention::KebabCase,
+                "parse-v2-header",
+            ),
+            ("my-file-name", NamingConvention::PascalCase, "MyFileName"),
+            ("my_file_name", NamingConvention::CamelCase, "myFileName"),
+            ("UserProfile", NamingConvention::CamelCase, "userProfile"),
+        ];
+        for (stem, convention, expected) in cases {
+            assert_eq!(
+                convention.convert(stem).as_deref(),
+                Some(expected),
+                "{}",
+                stem
+            );
+            assert_eq!(NamingConvention::detect(expected), convention);
+        }
+        assert_eq!(NamingConvention::Unknown.convert("fileFive"), None);
+    }
+
+    #[test]
+    fn test_convention_break_uses_configured_severity() {
+        let config = FilenameCheckConfig {
+            min_files_for_convention: 5,
+            convention_threshold: 0.7,
+            convention_severity: Some(Severity::Low),
+            ..Default::default()
+        };
+        let mut checker = FilenameChecker::with_config(config);
+        for name in [
+            "file_one",
+            "file_two",
+            "file_three",
+            "file_four",
+            "fileFive",
+        ] {
+            checker.add_file(Path::new(&format!("/src/{}.rs", name)));
+        }
+
+        let findings = checker.check_convention_breaks();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Low);
     }
 
     #[test]
@@ -695,6 +1004,7 @@ mod tests {
             min_files_for_convention: 5,
             convention_threshold: 0.8, // 80% threshold
             use_language_hints: false,
+            ..Default::default()
         };
         let mut checker = FilenameChecker::with_config(config);
 
@@ -718,6 +1028,7 @@ mod tests {
             min_files_for_convention: 10, // High threshold
             convention_threshold: 0.6,
             use_language_hints: false,
+            ..Default::default()
         };
         let mut checker = FilenameChecker::with_config(config);
 
@@ -773,6 +1084,7 @@ mod tests {
             min_files_for_convention: 5,
             convention_threshold: 0.7,
             use_language_hints: false,
+            ..Default::default()
         };
 
         // Create mock patterns for testing
@@ -784,6 +1096,7 @@ mod tests {
             category: PatternCategory::NamingConvention,
             ast_query: None,
             languages: vec![],
+            ..Default::default()
         }];
 
         let mut checker = FilenameChecker::with_config_and_patterns(config, &patterns);
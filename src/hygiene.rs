@@ -8,11 +8,12 @@
 #![allow(clippy::write_literal)]
 
 use owo_colors::OwoColorize;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 /// Embedded tool definitions from TOML
 const TOOLS_TOML: &str = include_str!("../data/hygiene_tools.toml");
@@ -66,7 +67,7 @@ struct ToolsConfig {
 // ============================================================================
 
 /// A tool found or recommended.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Tool {
     pub name: String,
     pub install_cmd: String,
@@ -95,14 +96,16 @@ impl Tool {
 }
 
 /// CI/CD platform detection.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CIPipeline {
     pub platform: String,
     pub config_file: String,
 }
 
 /// Result of a hygiene survey.
-#[derive(Debug)]
+///
+/// Serializes to JSON for `--hygiene-survey --json`.
+#[derive(Debug, Serialize)]
 pub struct HygieneSurvey {
     /// Detected project types and their marker file.
     pub project_types: Vec<(String, String)>,
@@ -120,20 +123,145 @@ pub struct HygieneSurvey {
     pub recommendations: HashMap<String, Vec<Tool>>,
 }
 
+/// A category of tooling that `--hygiene-require` can demand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum HygieneRequirement {
+    /// At least one linter.
+    Linter,
+    /// At least one formatter.
+    Formatter,
+    /// A CI/CD pipeline.
+    Ci,
+    /// Pre-commit hooks.
+    Precommit,
+}
+
+impl HygieneRequirement {
+    /// Human-readable name for messages.
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Linter => "linter",
+            Self::Formatter => "formatter",
+            Self::Ci => "CI/CD pipeline",
+            Self::Precommit => "pre-commit hook setup",
+        }
+    }
+}
+
+impl HygieneSurvey {
+    /// The requirements this survey fails, in the order given.
+    ///
+    /// Requirements only apply once a project type is detected, so a
+    /// directory with no recognized project never fails.
+    pub fn unmet_requirements(&self, required: &[HygieneRequirement]) -> Vec<HygieneRequirement> {
+        if self.project_types.is_empty() {
+            return Vec::new();
+        }
+        required
+            .iter()
+            .copied()
+            .filter(|requirement| match requirement {
+                HygieneRequirement::Linter => self.linters_found.is_empty(),
+                HygieneRequirement::Formatter => self.formatters_found.is_empty(),
+                HygieneRequirement::Ci => self.ci_pipelines.is_empty(),
+                HygieneRequirement::Precommit => self.precommit_found.is_none(),
+            })
+            .collect()
+    }
+}
+
 // ============================================================================
 // Core Functions
 // ============================================================================
 
+/// Project-local tool definitions merged over the embedded catalog.
+pub const USER_TOOLS_FILE: &str = ".antislop/hygiene_tools.toml";
+
 /// Load tool definitions from embedded TOML.
 fn load_tools_config() -> ToolsConfig {
     toml::from_str(TOOLS_TOML).expect("Failed to parse hygiene_tools.toml")
 }
 
-/// Run a hygiene survey on the given paths.
+impl ToolsConfig {
+    /// Merge user definitions over these. Tools replace same-named tools of
+    /// the same language; new languages and detection patterns are added.
+    fn merge(&mut self, user: ToolsConfig) {
+        for (key, lang) in user.languages {
+            let Some(existing) = self.languages.get_mut(&key) else {
+                self.languages.insert(key, lang);
+                continue;
+            };
+            for marker in lang.marker_files {
+                if !existing.marker_files.contains(&marker) {
+                    existing.marker_files.push(marker);
+                }
+            }
+            for ext in lang.detect_extensions {
+                if !existing.detect_extensions.contains(&ext) {
+                    existing.detect_extensions.push(ext);
+                }
+            }
+            merge_tools(&mut existing.linters, lang.linters);
+            merge_tools(&mut existing.formatters, lang.formatters);
+        }
+        self.detection_patterns.extend(user.detection_patterns);
+    }
+}
+
+fn merge_tools(tools: &mut Vec<ToolDef>, user: Vec<ToolDef>) {
+    for tool in user {
+        match tools.iter_mut().find(|t| t.name == tool.name) {
+            Some(existing) => *existing = tool,
+            None => tools.push(tool),
+        }
+    }
+}
+
+/// Run a hygiene survey on the given paths using the embedded tool catalog.
 pub fn run_survey(paths: &[PathBuf]) -> HygieneSurvey {
     let root = paths.first().cloned().unwrap_or_else(|| PathBuf::from("."));
-    let config = load_tools_config();
+    survey(root, load_tools_config())
+}
+
+/// Run a hygiene survey with user tool definitions merged over the embedded
+/// catalog.
+///
+/// `tools_file` takes precedence; otherwise [`USER_TOOLS_FILE`] under the
+/// surveyed root is used if it exists.
+pub fn run_survey_with_tools(
+    paths: &[PathBuf],
+    tools_file: Option<&Path>,
+) -> crate::Result<HygieneSurvey> {
+    let root = paths.first().cloned().unwrap_or_else(|| PathBuf::from("."));
+    let mut config = load_tools_config();
+
+    let discovered = root.join(USER_TOOLS_FILE);
+    let user_file = match tools_file {
+        Some(path) => Some(path.to_path_buf()),
+        None => discovered.is_file().then_some(discovered),
+    };
+    if let Some(path) = user_file {
+        let content = fs::read_to_string(&path).map_err(|e| {
+            crate::Error::ConfigInvalid(format!(
+                "Failed to read hygiene tools '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let user: ToolsConfig = toml::from_str(&content).map_err(|e| {
+            crate::Error::ConfigInvalid(format!(
+                "Invalid hygiene tools '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+        config.merge(user);
+    }
 
+    Ok(survey(root, config))
+}
+
+fn survey(root: PathBuf, config: ToolsConfig) -> HygieneSurvey {
     let mut project_types: Vec<(String, String)> = Vec::new();
     let mut linters_found: HashSet<String> = HashSet::new();
     let mut formatters_found: HashSet<String> = HashSet::new();
@@ -416,14 +544,16 @@ fn print_report_to(handle: &mut impl Write, survey: &HygieneSurvey) -> io::Resul
         writeln!(h, "{}└{}┘{}", amber, "─".repeat(box_width), reset)
     };
     let draw_box_section = |h: &mut dyn Write, title: &str| -> io::Result<()> {
-        let padding = box_width - title.len() - 3;
+        let padding = box_width.saturating_sub(display_width(title) + 3);
         writeln!(h, "{}┌─ {} {}┐{}", amber, title, "─".repeat(padding), reset)
     };
+    // Spaces filling the rest of a box line after `used` columns
+    let fill = |used: usize| " ".repeat(box_width.saturating_sub(used));
 
     // Title box
     draw_box_top(handle as &mut dyn Write)?;
     let title = "CODE   HYGIENE   SURVEY";
-    let pad_left = (box_width - title.len()) / 2;
+    let pad_left = (box_width - display_width(title)) / 2;
     writeln!(
         handle,
         "{}│{}{}{}{}│{}",
@@ -431,19 +561,19 @@ fn print_report_to(handle: &mut impl Write, survey: &HygieneSurvey) -> io::Resul
         " ".repeat(pad_left),
         bold,
         title,
-        " ".repeat(box_width - pad_left - title.len()),
+        fill(pad_left + display_width(title)),
         reset
     )?;
     let path_str = survey.root_path.display().to_string();
     let path_line = format!("for {}", path_str);
-    let pad_left = (box_width - path_line.len()) / 2;
+    let pad_left = box_width.saturating_sub(display_width(&path_line)) / 2;
     writeln!(
         handle,
         "{}│{}{}{}│{}",
         amber,
         " ".repeat(pad_left),
         path_line.dimmed(),
-        " ".repeat(box_width - pad_left - path_line.len()),
+        fill(pad_left + display_width(&path_line)),
         reset
     )?;
     draw_box_bottom(handle as &mut dyn Write)?;
@@ -451,23 +581,24 @@ fn print_report_to(handle: &mut impl Write, survey: &HygieneSurvey) -> io::Resul
 
     // Project Detection
     draw_box_section(handle as &mut dyn Write, "PROJECT DETECTION")?;
-    writeln!(handle, "{}│{}│{}", amber, " ".repeat(box_width), reset)?;
+    writeln!(handle, "{}│{}│{}", amber, fill(0), reset)?;
 
     if survey.project_types.is_empty() {
+        let line = "  No project markers found. Add Cargo.toml, package.json, etc.";
         writeln!(
             handle,
-            "{}│  {}No project markers found. Add Cargo.toml, package.json, etc.{}{}│{}",
+            "{}│{}{}{}{}│{}",
             amber,
             "\x1b[2m",
+            line,
             reset,
-            " ".repeat(box_width - 60),
+            fill(display_width(line)),
             reset
         )?;
     } else {
         for (lang_name, marker) in &survey.project_types {
             let line = format!("  {} PROJECT", lang_name.to_uppercase());
             let marker_str = format!("{} found", marker);
-            let padding = box_width - line.len() - marker_str.len() - 2;
             writeln!(
                 handle,
                 "{}│{}{}{}{}  {}│{}",
@@ -475,7 +606,7 @@ fn print_report_to(handle: &mut impl Write, survey: &HygieneSurvey) -> io::Resul
                 bold,
                 line,
                 reset,
-                " ".repeat(padding),
+                fill(display_width(&line) + display_width(&marker_str) + 2),
                 marker_str.dimmed(),
                 reset
             )?;
@@ -484,7 +615,6 @@ fn print_report_to(handle: &mut impl Write, survey: &HygieneSurvey) -> io::Resul
             let bar_width = 48;
             let bar = "█".repeat(bar_width);
             let percent = "100%";
-            let pad = box_width - bar_width - 10 - percent.len();
             writeln!(
                 handle,
                 "{}│  {}{}{}  {}{}│{}",
@@ -493,157 +623,140 @@ fn print_report_to(handle: &mut impl Write, survey: &HygieneSurvey) -> io::Resul
                 bar,
                 reset,
                 percent,
-                " ".repeat(pad),
+                fill(bar_width + display_width(percent) + 4),
                 reset
             )?;
         }
     }
-    writeln!(handle, "{}│{}│{}", amber, " ".repeat(box_width), reset)?;
+    writeln!(handle, "{}│{}│{}", amber, fill(0), reset)?;
     draw_box_bottom(handle as &mut dyn Write)?;
     writeln!(handle)?;
 
     // Linters & Formatters
     draw_box_section(handle as &mut dyn Write, "LINTERS & FORMATTERS")?;
-    writeln!(handle, "{}│{}│{}", amber, " ".repeat(box_width), reset)?;
+    writeln!(handle, "{}│{}│{}", amber, fill(0), reset)?;
 
     if survey.linters_found.is_empty() && survey.formatters_found.is_empty() {
+        let line = "  ✗ No linter or formatter configs found";
         writeln!(
             handle,
-            "{}│  {}✗ No linter or formatter configs found{}{}│{}",
+            "{}│{}{}{}{}│{}",
             amber,
             "\x1b[31m",
+            line,
             reset,
-            " ".repeat(box_width - 43),
+            fill(display_width(line)),
             reset
         )?;
     } else {
-        for linter in &survey.linters_found {
-            let line = format!("  ✓ {}", linter);
-            let pad = box_width - line.len() - 20;
-            writeln!(
-                handle,
-                "{}│{}\x1b[32m{}{}{} {}│{}",
-                amber,
-                "",
-                line,
-                reset,
-                " ".repeat(pad),
-                "linter".dimmed(),
-                reset
-            )?;
-        }
-        for formatter in &survey.formatters_found {
-            let line = format!("  ✓ {}", formatter);
-            let pad = box_width - line.len() - 20;
+        let tools = survey
+            .linters_found
+            .iter()
+            .map(|name| (name, "linter"))
+            .chain(
+                survey
+                    .formatters_found
+                    .iter()
+                    .map(|name| (name, "formatter")),
+            );
+        for (name, kind) in tools {
+            let line = format!("  ✓ {}", name);
             writeln!(
                 handle,
-                "{}│{}\x1b[32m{}{}{} {}│{}",
+                "{}│\x1b[32m{}{}{} {} │{}",
                 amber,
-                "",
                 line,
                 reset,
-                " ".repeat(pad),
-                "formatter".dimmed(),
+                fill(display_width(&line) + display_width(kind) + 2),
+                kind.dimmed(),
                 reset
             )?;
         }
     }
 
-    writeln!(handle, "{}│{}│{}", amber, " ".repeat(box_width), reset)?;
+    writeln!(handle, "{}│{}│{}", amber, fill(0), reset)?;
     draw_box_bottom(handle as &mut dyn Write)?;
     writeln!(handle)?;
 
     // CI/CD Pipelines
     draw_box_section(handle as &mut dyn Write, "CI/CD PIPELINES")?;
-    writeln!(handle, "{}│{}│{}", amber, " ".repeat(box_width), reset)?;
+    writeln!(handle, "{}│{}│{}", amber, fill(0), reset)?;
 
     if survey.ci_pipelines.is_empty() {
+        let line = "  ✗ No CI/CD configuration found";
         writeln!(
             handle,
-            "{}│  {}✗ No CI/CD configuration found{}{}│{}",
+            "{}│{}{}{}{}│{}",
             amber,
             "\x1b[31m",
+            line,
             reset,
-            " ".repeat(box_width - 36),
+            fill(display_width(line)),
             reset
         )?;
     } else {
         for ci in &survey.ci_pipelines {
             let line = format!("  {}", ci.platform);
             let config = &ci.config_file;
-            let pad = box_width - line.len() - config.len() - 4;
             writeln!(
                 handle,
-                "{}│{}{}{}{}  {}│{}",
+                "{}│{}{}{}{}  {} │{}",
                 amber,
                 bold,
                 line,
                 reset,
-                " ".repeat(pad),
+                fill(display_width(&line) + display_width(config) + 3),
                 config.dimmed(),
                 reset
             )?;
         }
     }
 
-    writeln!(handle, "{}│{}│{}", amber, " ".repeat(box_width), reset)?;
+    writeln!(handle, "{}│{}│{}", amber, fill(0), reset)?;
     draw_box_bottom(handle as &mut dyn Write)?;
     writeln!(handle)?;
 
     // Pre-commit Hooks
     draw_box_section(handle as &mut dyn Write, "PRE-COMMIT HOOKS")?;
-    writeln!(handle, "{}│{}│{}", amber, " ".repeat(box_width), reset)?;
+    writeln!(handle, "{}│{}│{}", amber, fill(0), reset)?;
 
-    if let Some(ref config) = survey.precommit_found {
-        let line = format!("  ✓ {}", config);
-        let pad = box_width - line.len() - 2;
-        writeln!(
-            handle,
-            "{}│{}\x1b[32m{}{}{}│{}",
-            amber,
-            "",
-            line,
-            reset,
-            " ".repeat(pad),
-            reset
-        )?;
-    } else {
-        writeln!(
-            handle,
-            "{}│  {}✗ No pre-commit hooks found{}{}│{}",
-            amber,
-            "\x1b[31m",
-            reset,
-            " ".repeat(box_width - 32),
-            reset
-        )?;
-    }
+    let (color, line) = match &survey.precommit_found {
+        Some(config) => ("\x1b[32m", format!("  ✓ {}", config)),
+        None => ("\x1b[31m", "  ✗ No pre-commit hooks found".to_string()),
+    };
+    writeln!(
+        handle,
+        "{}│{}{}{}{}│{}",
+        amber,
+        color,
+        line,
+        reset,
+        fill(display_width(&line)),
+        reset
+    )?;
 
-    writeln!(handle, "{}│{}│{}", amber, " ".repeat(box_width), reset)?;
+    writeln!(handle, "{}│{}│{}", amber, fill(0), reset)?;
     draw_box_bottom(handle as &mut dyn Write)?;
     writeln!(handle)?;
 
     // Recommendations
     draw_box_section(handle as &mut dyn Write, "RECOMMENDATIONS")?;
-    writeln!(handle, "{}│{}│{}", amber, " ".repeat(box_width), reset)?;
+    writeln!(handle, "{}│{}│{}", amber, fill(0), reset)?;
 
     if survey.recommendations.is_empty() {
         let line = "  ✓ All recommended tools configured!";
-        let pad = box_width - line.len() - 2;
         writeln!(
             handle,
-            "{}│{}\x1b[32m{}{}{}│{}",
+            "{}│\x1b[32m{}{}{}│{}",
             amber,
-            "",
             line,
             reset,
-            " ".repeat(pad),
+            fill(display_width(line)),
             reset
         )?;
     } else {
         for (lang_name, tools) in &survey.recommendations {
             let header = format!("  {}", lang_name);
-            let pad = box_width - header.len() - 2;
             writeln!(
                 handle,
                 "{}│{}{}{}{}│{}",
@@ -651,7 +764,7 @@ fn print_report_to(handle: &mut impl Write, survey: &HygieneSurvey) -> io::Resul
                 bold,
                 header,
                 reset,
-                " ".repeat(pad),
+                fill(display_width(&header)),
                 reset
             )?;
 
@@ -662,51 +775,37 @@ fn print_report_to(handle: &mut impl Write, survey: &HygieneSurvey) -> io::Resul
                     tool.name,
                     tool.description.to_lowercase()
                 );
-                let line = if line.len() > box_width - 4 {
-                    format!("{}...", &line[..box_width - 7])
-                } else {
-                    line
-                };
-                let pad = box_width - line.len() - 2;
+                let line = truncate_to_width(&line, box_width - 2);
                 writeln!(
                     handle,
                     "{}│{}{}{}│{}",
                     amber,
                     line.dimmed(),
                     reset,
-                    " ".repeat(pad),
+                    fill(display_width(&line)),
                     reset
                 )?;
             }
-            writeln!(handle, "{}│{}│{}", amber, " ".repeat(box_width), reset)?;
+            writeln!(handle, "{}│{}│{}", amber, fill(0), reset)?;
         }
     }
 
     // Tips for empty setups
+    let mut tips = Vec::new();
     if survey.precommit_found.is_none() && !survey.project_types.is_empty() {
-        let line = "  💡 Consider adding pre-commit hooks for automated checks";
-        let pad = box_width - line.len() - 2;
-        writeln!(
-            handle,
-            "{}│{}{}{}│{}",
-            amber,
-            line.dimmed(),
-            reset,
-            " ".repeat(pad),
-            reset
-        )?;
+        tips.push("  💡 Consider adding pre-commit hooks for automated checks");
     }
-
     if survey.ci_pipelines.is_empty() && !survey.project_types.is_empty() {
-        let line = "  💡 Consider adding GitHub Actions or other CI/CD";
-        let pad = box_width - line.len() - 2;
+        tips.push("  💡 Consider adding GitHub Actions or other CI/CD");
+    }
+    for line in tips {
         writeln!(
             handle,
             "{}│{}{}{}│{}",
             amber,
             line.dimmed(),
             reset,
-            " ".repeat(pad),
+            fill(display_width(line)),
             reset
         )?;
     }
@@ -716,6 +815,31 @@ fn print_report_to(handle: &mut impl Write, survey: &HygieneSurvey) -> io::Resul
     Ok(())
 }
 
+/// Number of terminal columns `s` occupies. Unlike `str::len`, this counts
+/// box-drawing characters as one column and emoji as two.
+fn display_width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
+
+/// Shorten `s` to at most `max_width` columns, ending with "..." if cut.
+fn truncate_to_width(s: &str, max_width: usize) -> String {
+    if display_width(s) <= max_width {
+        return s.to_string();
+    }
+    let mut out = String::new();
+    let mut width = 0;
+    for c in s.chars() {
+        let w = UnicodeWidthChar::width(c).unwrap_or(0);
+        if width + w + 3 > max_width {
+            break;
+        }
+        out.push(c);
+        width += w;
+    }
+    out.push_str("...");
+    out
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -761,6 +885,98 @@ mod tests {
         assert!(python.formatters.iter().any(|t| t.name == "isort"));
     }
 
+    #[test]
+    fn test_display_width_counts_columns() {
+        let line = "  💡 Consider adding GitHub Actions";
+        assert_eq!(line.len(), 37);
+        assert_eq!(display_width(line), 35);
+        assert_eq!(display_width("✓ ruff"), 6);
+        assert_eq!(truncate_to_width("• abcdef", 6), "• a...");
+    }
+
+    #[test]
+    fn test_report_box_lines_align() {
+        let survey = HygieneSurvey {
+            root_path: PathBuf::from("/tmp/project"),
+            project_types: vec![("rust".to_string(), "Cargo.toml".to_string())],
+            linters_found: vec!["clippy".to_string()],
+            formatters_found: vec![],
+            ci_pipelines: vec![],
+            precommit_found: None,
+            recommendations: HashMap::new(),
+        };
+        let mut out = Vec::new();
+        print_report_to(&mut out, &survey).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let ansi = regex::Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+        for line in text.lines().filter(|l| !l.is_empty()) {
+            let plain = ansi.replace_all(line, "");
+            assert_eq!(display_width(&plain), 70, "misaligned: {:?}", plain);
+        }
+    }
+
+    #[test]
+    fn test_user_tools_appear_in_recommendations() {
+        let temp = tempfile::TempDir::new().unwrap();
+        fs::write(temp.path().join("Cargo.toml"), "[package]\n").unwrap();
+        fs::create_dir(temp.path().join(".antislop")).unwrap();
+        fs::write(
+            temp.path().join(USER_TOOLS_FILE),
+            r#"
+[rust]
+name = "Rust"
+
+[[rust.linters]]
+name = "acme-lint"
+install = "cargo install acme-lint"
+description = "Internal lint rules"
+config_files = ["acme-lint.toml"]
+"#,
+        )
+        .unwrap();
+
+        let survey = run_survey_with_tools(&[temp.path().to_path_buf()], None).unwrap();
+        let rust = &survey.recommendations["Rust"];
+        assert!(rust.iter().any(|t| t.name == "acme-lint"));
+        // Embedded tools are kept
+        assert!(rust.iter().any(|t| t.name == "rustfmt"));
+
+        fs::write(temp.path().join("acme-lint.toml"), "").unwrap();
+        let survey = run_survey_with_tools(&[temp.path().to_path_buf()], None).unwrap();
+        assert!(survey.linters_found.contains(&"acme-lint".to_string()));
+    }
+
+    #[test]
+    fn test_invalid_user_tools_file() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let tools = temp.path().join("tools.toml");
+        fs::write(&tools, "[rust\n").unwrap();
+        let result = run_survey_with_tools(&[temp.path().to_path_buf()], Some(&tools));
+        assert!(matches!(result, Err(crate::Error::ConfigInvalid(_))));
+    }
+
+    #[test]
+    fn test_unmet_requirements() {
+        let mut survey = HygieneSurvey {
+            root_path: PathBuf::from("."),
+            project_types: vec![("Rust".to_string(), "Cargo.toml".to_string())],
+            linters_found: vec!["clippy".to_string()],
+            formatters_found: vec![],
+            ci_pipelines: vec![],
+            precommit_found: None,
+            recommendations: HashMap::new(),
+        };
+        let required = [HygieneRequirement::Linter, HygieneRequirement::Formatter];
+        assert_eq!(
+            survey.unmet_requirements(&required),
+            vec![HygieneRequirement::Formatter]
+        );
+
+        // Nothing is required of an unrecognized directory
+        survey.project_types.clear();
+        assert!(survey.unmet_requirements(&required).is_empty());
+    }
+
     #[test]
     fn test_tool_creation() {
         let tool = Tool::new("test", "install cmd", "description");
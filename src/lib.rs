@@ -24,25 +24,37 @@
 //! - **Hedging**: "hopefully", "should work", "this is a simple"
 //! - **Stub**: Empty functions near placeholder comments
 
+#[cfg(feature = "async")]
+pub mod async_scan;
+pub mod baseline;
+pub mod blame;
 pub mod config;
 pub mod detector;
+pub mod docstring_checker;
 pub mod filename_checker;
 pub mod hygiene;
 pub mod profile;
 pub mod report;
+pub mod review;
 pub mod walker;
 
 #[doc(inline)]
-pub use config::{Config, Pattern, PatternCategory, Severity};
+pub use config::{Config, Pattern, PatternCategory, PatternScope, Severity};
 
 #[doc(inline)]
-pub use detector::{Comment, FileScanResult, Finding, ScanSummary, Scanner};
+pub use detector::{
+    Comment, CommentKind, FileScanResult, Finding, FindingTransformer, PatternStat, ScanSummary,
+    Scanner, ScannerBuilder, Span,
+};
+
+#[doc(inline)]
+pub use docstring_checker::DocstringChecker;
 
 #[doc(inline)]
 pub use filename_checker::{FilenameCheckConfig, FilenameChecker};
 
 #[doc(inline)]
-pub use report::{Format, Reporter};
+pub use report::{Format, Reporter, SummaryDetail};
 
 #[doc(inline)]
 pub use walker::Walker;
@@ -50,6 +62,10 @@ pub use walker::Walker;
 #[doc(inline)]
 pub use profile::{Profile, ProfileLoader, ProfileSource};
 
+#[cfg(feature = "async")]
+#[doc(inline)]
+pub use async_scan::scan_paths_async;
+
 /// Result type for antislop operations.
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -60,7 +76,6 @@ pub enum Error {
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
 
-    /// Config parsing error.
     /// Config parsing error.
     #[error("Config error: {0}")]
     Config(#[from] toml::de::Error),
@@ -69,10 +84,42 @@ pub enum Error {
     #[error("Configuration invalid: {0}")]
     ConfigInvalid(String),
 
+    /// Fetching a remote profile failed.
+    #[error("Failed to fetch profile from '{url}': {reason}")]
+    ProfileFetch {
+        /// URL that was requested.
+        url: String,
+        /// Why the fetch failed.
+        reason: String,
+    },
+
+    /// A profile could not be parsed or failed validation.
+    #[error("Invalid profile: {0}")]
+    ProfileParse(String),
+
+    /// Serializing output (JSON, SARIF, TOML) failed.
+    #[error("Serialization error: {0}")]
+    Serialization(String),
+
+    /// Reading or writing a profile or cache file failed.
+    #[error("Profile I/O error for '{}': {source}", path.display())]
+    CacheIo {
+        /// File that could not be accessed.
+        path: std::path::PathBuf,
+        /// Underlying I/O error.
+        #[source]
+        source: std::io::Error,
+    },
+
     /// Regex compilation error.
     #[error("Invalid regex: {0}")]
     Regex(#[from] regex::Error),
 
+    /// Writing the SQLite export failed.
+    #[cfg(feature = "sqlite")]
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
     /// Tree-sitter parsing error.
     #[cfg(feature = "tree-sitter")]
     #[error("Parse error: {0}")]
@@ -83,4 +130,14 @@ pub enum Error {
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// Default configuration file names.
-pub const CONFIG_FILES: &[&str] = &["antislop.toml", ".antislop.toml", ".antislop"];
+pub const CONFIG_FILES: &[&str] = &[
+    "antislop.toml",
+    ".antislop.toml",
+    ".antislop",
+    "antislop.yaml",
+    "antislop.yml",
+    ".antislop.yaml",
+    ".antislop.yml",
+    "antislop.json",
+    ".antislop.json",
+];
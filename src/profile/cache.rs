@@ -17,33 +17,36 @@ pub fn fetch_url(url: &str) -> Result<String> {
             .timeout(Duration::from_secs(30))
             .build();
 
-        let response = client.get(url).call().map_err(|e| {
-            Error::ConfigInvalid(format!("Failed to fetch profile from '{}': {}", url, e))
+        let response = client.get(url).call().map_err(|e| Error::ProfileFetch {
+            url: url.to_string(),
+            reason: e.to_string(),
         })?;
 
         let status = response.status();
         if !(200..300).contains(&status) {
-            return Err(Error::ConfigInvalid(format!(
-                "Failed to fetch profile from '{}': HTTP {}",
-                url, status
-            )));
+            return Err(Error::ProfileFetch {
+                url: url.to_string(),
+                reason: format!("HTTP {}", status),
+            });
         }
 
-        response.into_string().map_err(|e| {
-            Error::ConfigInvalid(format!("Failed to read response from '{}': {}", url, e))
+        response.into_string().map_err(|e| Error::ProfileFetch {
+            url: url.to_string(),
+            reason: format!("failed to read response: {}", e),
         })
     }
 
     #[cfg(not(feature = "ureq"))]
     {
-        let _url = url; // Suppress unused warning
-                        // Without ureq, provide a helpful error message
-        Err(Error::ConfigInvalid(
-            "Remote profile fetching requires the 'ureq' feature or 'remote-profiles' feature. \
+        // Without ureq, provide a helpful error message
+        Err(Error::ProfileFetch {
+            url: url.to_string(),
+            reason:
+                "remote profile fetching requires the 'ureq' feature or 'remote-profiles' feature. \
             Enable it with: cargo build --features remote-profiles\n\
             Or download the profile manually and use --profile <path>"
-                .to_string(),
-        ))
+                    .to_string(),
+        })
     }
 }
 
@@ -83,6 +86,18 @@ mod tests {
     #[test]
     fn test_fetch_url_invalid() {
         let result = fetch_url("https://this-url-does-not-exist-12345.com");
-        assert!(result.is_err());
+        assert!(matches!(result, Err(Error::ProfileFetch { .. })));
+    }
+
+    #[cfg(not(feature = "ureq"))]
+    #[test]
+    fn test_fetch_url_without_ureq() {
+        let result = fetch_url("https://example.com/profile.toml");
+        match result {
+            Err(Error::ProfileFetch { url, .. }) => {
+                assert_eq!(url, "https://example.com/profile.toml")
+            }
+            other => panic!("Expected ProfileFetch error, got {:?}", other),
+        }
     }
 }
@@ -7,12 +7,45 @@
 pub mod cache;
 pub mod validate;
 
-use crate::config::{Pattern, PatternCategory};
+use crate::config::{MagicNumbers, Pattern, PatternCategory};
 use crate::{Error, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Profiles shipped inside the binary, loadable by name.
+const EMBEDDED_PROFILES: &[(&str, &str)] = &[
+    (
+        "antislop-standard",
+        include_str!("../../config/profiles/antislop-standard.toml"),
+    ),
+    (
+        "antislop-strict",
+        include_str!("../../config/profiles/antislop-strict.toml"),
+    ),
+    (
+        "security",
+        include_str!("../../config/profiles/security.toml"),
+    ),
+    (
+        "security-placeholder",
+        include_str!("../../config/profiles/security-placeholder.toml"),
+    ),
+    (
+        "magic-numbers",
+        include_str!("../../config/profiles/magic-numbers.toml"),
+    ),
+    (
+        "chat-leakage",
+        include_str!("../../config/profiles/chat-leakage.toml"),
+    ),
+    (
+        "hedging-filler",
+        include_str!("../../config/profiles/hedging-filler.toml"),
+    ),
+];
 
 /// Profile metadata.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,6 +70,10 @@ pub struct ProfileMetadata {
     /// Profiles this profile extends (inherits patterns from).
     #[serde(default)]
     pub extends: Vec<String>,
+    /// SHA-256 of the profile's patterns (see [`Profile::checksum`]). When
+    /// present, loading fails unless it matches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
 }
 
 impl Default for ProfileMetadata {
@@ -49,6 +86,7 @@ impl Default for ProfileMetadata {
             url: None,
             requires_version: None,
             extends: Vec::new(),
+            checksum: None,
         }
     }
 }
@@ -62,6 +100,13 @@ pub struct Profile {
     /// Detection patterns.
     #[serde(default)]
     pub patterns: Vec<Pattern>,
+    /// Enables the magic-number pass with these settings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub magic_numbers: Option<MagicNumbers>,
+    /// Non-fatal problems found by [`ProfileLoader::load`], such as a newer
+    /// `requires_version`, for the caller to report.
+    #[serde(skip)]
+    pub warnings: Vec<String>,
 }
 
 impl Profile {
@@ -73,26 +118,44 @@ impl Profile {
                 ..Default::default()
             },
             patterns: Vec::new(),
+            magic_numbers: None,
+            warnings: Vec::new(),
         }
     }
 
     /// Load a profile from a TOML file.
     pub fn from_file(path: &Path) -> Result<Self> {
-        let content = fs::read_to_string(path).map_err(|e| {
-            Error::ConfigInvalid(format!(
-                "Failed to read profile file '{}': {}",
-                path.display(),
-                e
-            ))
+        let content = fs::read_to_string(path).map_err(|source| Error::CacheIo {
+            path: path.to_path_buf(),
+            source,
         })?;
 
         Self::from_toml(&content)
     }
 
+    /// Load a profile from a TOML string, rejecting orthogonality violations.
+    ///
+    /// See [`validate::validate_mece_compliance`] for what counts as a violation.
+    pub fn from_toml_strict(content: &str) -> Result<Self> {
+        let profile = Self::from_toml(content)?;
+        validate::enforce_mece_compliance(&profile)?;
+        Ok(profile)
+    }
+
     /// Load a profile from a TOML string.
     pub fn from_toml(content: &str) -> Result<Self> {
         let profile: Self = toml::from_str(content)
-            .map_err(|e| Error::ConfigInvalid(format!("Failed to parse profile TOML: {}", e)))?;
+            .map_err(|e| Error::ProfileParse(format!("Failed to parse profile TOML: {}", e)))?;
+
+        if let Some(expected) = &profile.metadata.checksum {
+            let actual = profile.checksum()?;
+            if !expected.eq_ignore_ascii_case(&actual) {
+                return Err(Error::ProfileParse(format!(
+                    "Checksum mismatch for profile '{}': expected {}, got {}",
+                    profile.metadata.name, expected, actual
+                )));
+            }
+        }
 
         // Validate the profile
         validate::validate_profile(&profile)?;
@@ -100,15 +163,28 @@ impl Profile {
         Ok(profile)
     }
 
+    /// SHA-256 of the profile's patterns, as lowercase hex.
+    ///
+    /// The patterns are hashed in their canonical JSON form, so formatting
+    /// and comments in the TOML file do not affect the checksum, but any
+    /// change to a pattern does.
+    pub fn checksum(&self) -> Result<String> {
+        use sha2::{Digest, Sha256};
+
+        let canonical = serde_json::to_vec(&self.patterns)
+            .map_err(|e| Error::Serialization(format!("Failed to serialize patterns: {}", e)))?;
+        Ok(Sha256::digest(&canonical)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect())
+    }
+
     /// Save a profile to a TOML file.
     pub fn to_file(&self, path: &Path) -> Result<()> {
         let content = self.to_toml()?;
-        fs::write(path, content).map_err(|e| {
-            Error::ConfigInvalid(format!(
-                "Failed to write profile file '{}': {}",
-                path.display(),
-                e
-            ))
+        fs::write(path, content).map_err(|source| Error::CacheIo {
+            path: path.to_path_buf(),
+            source,
         })?;
         Ok(())
     }
@@ -116,7 +192,7 @@ impl Profile {
     /// Convert the profile to TOML format.
     pub fn to_toml(&self) -> Result<String> {
         toml::to_string_pretty(self)
-            .map_err(|e| Error::ConfigInvalid(format!("Failed to serialize profile: {}", e)))
+            .map_err(|e| Error::Serialization(format!("Failed to serialize profile: {}", e)))
     }
 
     /// Merge another profile's patterns into this one.
@@ -146,6 +222,11 @@ impl Profile {
                 seen.insert((pattern.regex.to_string(), pattern.category.clone()));
             }
         }
+
+        if self.magic_numbers.is_none() {
+            self.magic_numbers = other.magic_numbers.clone();
+        }
+        self.warnings.extend(other.warnings.iter().cloned());
     }
 
     /// Get all patterns from this profile.
@@ -177,6 +258,7 @@ impl ProfileSource {
     /// Parse a profile source from a string.
     ///
     /// - If it starts with "http://" or "https://", it's a Remote source.
+    /// - If it starts with "gh:", it's expanded to a raw GitHub URL (Remote).
     /// - If it exists as a file, it's a Local source.
     /// - Otherwise, it's a Builtin source (name only).
     pub fn parse(input: &str) -> Result<Self> {
@@ -184,6 +266,10 @@ impl ProfileSource {
             return Ok(ProfileSource::Remote(input.to_string()));
         }
 
+        if let Some(shorthand) = input.strip_prefix("gh:") {
+            return Ok(ProfileSource::Remote(expand_github_shorthand(shorthand)?));
+        }
+
         let path = PathBuf::from(input);
         if path.exists() {
             return Ok(ProfileSource::Local(path));
@@ -201,6 +287,8 @@ pub struct ProfileLoader {
     project_dir: PathBuf,
     /// User config profile directory.
     user_dir: PathBuf,
+    /// Reject profiles that fail orthogonality validation.
+    strict: bool,
 }
 
 impl ProfileLoader {
@@ -224,6 +312,7 @@ impl ProfileLoader {
             cache_dir,
             project_dir,
             user_dir,
+            strict: false,
         })
     }
 
@@ -233,9 +322,17 @@ impl ProfileLoader {
             cache_dir,
             project_dir,
             user_dir,
+            strict: false,
         }
     }
 
+    /// Reject loaded profiles (including extended ones) that fail
+    /// orthogonality validation.
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
     /// Load a profile from the given source.
     ///
     /// Resolution order:
@@ -264,10 +361,24 @@ impl ProfileLoader {
             ProfileSource::Builtin(name) => self.load_builtin(name),
         }?;
 
+        if let Some(warning) = validate::required_version_warning(&profile) {
+            if self.strict {
+                return Err(Error::ProfileParse(format!(
+                    "Profile '{}' {}",
+                    profile.metadata.name, warning
+                )));
+            }
+            let warning = format!("profile '{}': {}", profile.metadata.name, warning);
+            profile.warnings.push(warning);
+        }
+        if self.strict {
+            validate::enforce_mece_compliance(&profile)?;
+        }
+
         // Check for circular extends
         let profile_id = profile.metadata.name.clone();
         if visited.contains(&profile_id) {
-            return Err(Error::ConfigInvalid(format!(
+            return Err(Error::ProfileParse(format!(
                 "Circular extends detected: '{}'",
                 profile_id
             )));
@@ -346,25 +457,34 @@ impl ProfileLoader {
     /// Load a built-in profile by name.
     fn load_builtin(&self, name: &str) -> Result<Profile> {
         if name == "core" {
-            // Create virtual "core" profile from embedded default patterns
-            let config = crate::config::Config::default();
-            return Ok(Profile {
-                metadata: ProfileMetadata {
-                    name: "core".to_string(),
-                    description: "Built-in core antislop patterns".to_string(),
-                    version: env!("CARGO_PKG_VERSION").to_string(),
-                    author: "AntiSlop Team".to_string(),
-                    url: None,
-                    requires_version: None,
-                    extends: vec![],
-                },
-                patterns: config.patterns,
-            });
+            return Ok(core_profile());
+        }
+        if let Some((_, content)) = EMBEDDED_PROFILES.iter().find(|(n, _)| *n == name) {
+            return Profile::from_toml(content);
         }
         // First try loading by name (searches project, user, cache dirs)
         self.load_by_name(name)
     }
 
+    /// Profiles shipped inside the binary: the virtual `core` profile and
+    /// the embedded profiles.
+    pub fn list_builtin() -> Vec<ProfileInfo> {
+        let embedded = EMBEDDED_PROFILES
+            .iter()
+            .filter_map(|(_, content)| Profile::from_toml(content).ok());
+        std::iter::once(core_profile())
+            .chain(embedded)
+            .map(|profile| ProfileInfo {
+                name: profile.metadata.name,
+                description: profile.metadata.description,
+                version: profile.metadata.version,
+                source: PathBuf::new(),
+                path: PathBuf::new(),
+                builtin: true,
+            })
+            .collect()
+    }
+
     /// Get the cache path for a URL.
     fn cache_path_for_url(&self, url: &str) -> PathBuf {
         // Create a hash of the URL for the filename
@@ -410,6 +530,40 @@ impl ProfileLoader {
         Ok(updated)
     }
 
+    /// Remove cached profiles older than `max_age`.
+    ///
+    /// Staleness uses the same mtime check as [`cache::is_cache_fresh`].
+    /// Returns the file stems of the removed profiles.
+    pub fn prune_cache(&self, max_age: Duration) -> Result<Vec<String>> {
+        let mut removed = Vec::new();
+
+        let entries = match fs::read_dir(&self.cache_dir) {
+            Ok(entries) => entries,
+            Err(_) => return Ok(removed),
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_none_or(|e| e != "toml") {
+                continue;
+            }
+            if cache::is_cache_fresh(&path, max_age) {
+                continue;
+            }
+
+            fs::remove_file(&path).map_err(|source| Error::CacheIo {
+                path: path.clone(),
+                source,
+            })?;
+            if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                removed.push(stem.to_string());
+            }
+        }
+
+        removed.sort();
+        Ok(removed)
+    }
+
     /// List all available profiles (project-local, user, and cached).
     pub fn list_available(&self) -> Vec<ProfileInfo> {
         let mut profiles = Vec::new();
@@ -434,6 +588,7 @@ impl ProfileLoader {
                             version: profile.metadata.version.clone(),
                             source: dir.to_path_buf(),
                             path,
+                            builtin: false,
                         });
                     }
                 }
@@ -450,12 +605,13 @@ impl Default for ProfileLoader {
             cache_dir: PathBuf::from(".cache/profiles"),
             project_dir: PathBuf::from(".antislop/profiles"),
             user_dir: PathBuf::from(".config/profiles"),
+            strict: false,
         })
     }
 }
 
 /// Information about an available profile.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ProfileInfo {
     /// Profile name.
     pub name: String,
@@ -463,10 +619,69 @@ pub struct ProfileInfo {
     pub description: String,
     /// Profile version.
     pub version: String,
-    /// Source directory.
+    /// Source directory; empty for built-in profiles.
+    #[serde(skip_serializing_if = "is_empty_path")]
     pub source: PathBuf,
-    /// Full path to the profile file.
+    /// Full path to the profile file; empty for built-in profiles.
+    #[serde(skip_serializing_if = "is_empty_path")]
     pub path: PathBuf,
+    /// Whether the profile ships inside the binary.
+    pub builtin: bool,
+}
+
+fn is_empty_path(path: &Path) -> bool {
+    path.as_os_str().is_empty()
+}
+
+/// The virtual `core` profile holding the embedded default patterns.
+fn core_profile() -> Profile {
+    Profile {
+        metadata: ProfileMetadata {
+            name: "core".to_string(),
+            description: "Built-in core antislop patterns".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            author: "AntiSlop Team".to_string(),
+            url: None,
+            requires_version: None,
+            extends: vec![],
+            checksum: None,
+        },
+        patterns: crate::config::Config::default().patterns,
+        magic_numbers: None,
+        warnings: Vec::new(),
+    }
+}
+
+/// Expand a `owner/repo/path.toml@ref` shorthand into a raw GitHub URL.
+///
+/// The ref defaults to `main` when omitted.
+fn expand_github_shorthand(shorthand: &str) -> Result<String> {
+    let (location, git_ref) = match shorthand.rsplit_once('@') {
+        Some((location, git_ref)) if !git_ref.is_empty() => (location, git_ref),
+        Some(_) => {
+            return Err(Error::ConfigInvalid(format!(
+                "Invalid GitHub profile shorthand 'gh:{}': empty ref after '@'",
+                shorthand
+            )))
+        }
+        None => (shorthand, "main"),
+    };
+
+    let mut parts = location.splitn(3, '/');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(owner), Some(repo), Some(path))
+            if !owner.is_empty() && !repo.is_empty() && !path.is_empty() =>
+        {
+            Ok(format!(
+                "https://raw.githubusercontent.com/{}/{}/{}/{}",
+                owner, repo, git_ref, path
+            ))
+        }
+        _ => Err(Error::ConfigInvalid(format!(
+            "Invalid GitHub profile shorthand 'gh:{}': expected gh:owner/repo/path.toml[@ref]",
+            shorthand
+        ))),
+    }
 }
 
 /// Sanitize a name for use in a filename.
@@ -479,7 +694,7 @@ fn sanitize_name(name: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{RegexPattern, Severity};
+    use crate::config::{PatternScope, RegexPattern, Severity};
 
     #[test]
     fn test_profile_new() {
@@ -511,6 +726,253 @@ mod tests {
         assert_eq!(profile.patterns[0].message, "TODO found");
     }
 
+    #[test]
+    fn test_profile_checksum_verification() {
+        let toml = |checksum: &str| {
+            format!(
+                r#"
+                [metadata]
+                name = "signed"
+                checksum = "{}"
+
+                [[patterns]]
+                regex = "(?i)TODO:"
+                severity = "medium"
+                message = "TODO found"
+                category = "placeholder"
+            "#,
+                checksum
+            )
+        };
+        let unsigned = toml("").replace("checksum = \"\"", "");
+        let checksum = Profile::from_toml(&unsigned).unwrap().checksum().unwrap();
+        assert_eq!(checksum.len(), 64);
+
+        let profile = Profile::from_toml(&toml(&checksum)).unwrap();
+        assert_eq!(
+            profile.metadata.checksum.as_deref(),
+            Some(checksum.as_str())
+        );
+
+        let tampered = toml(&checksum).replace("TODO found", "TODO");
+        let err = Profile::from_toml(&tampered).unwrap_err();
+        assert!(matches!(err, Error::ProfileParse(ref msg) if msg.contains("Checksum mismatch")));
+    }
+
+    #[test]
+    fn test_security_placeholder_profile() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let loader = ProfileLoader::with_dirs(
+            temp.path().join("cache"),
+            temp.path().join("project"),
+            temp.path().join("user"),
+        );
+        let profile = loader
+            .load(&ProfileSource::Builtin("security-placeholder".to_string()))
+            .unwrap();
+        assert!(profile
+            .patterns
+            .iter()
+            .all(|p| p.scope == PatternScope::Code
+                && p.severity == Severity::High
+                && p.category == PatternCategory::SecurityPlaceholder));
+
+        let scanner = crate::Scanner::new(profile.patterns).unwrap();
+        let code = "password = \"changeme\"\napi_key = \"YOUR_KEY_HERE\"\npassword = hashed\n";
+        let result = scanner.scan_file("settings.py", code);
+        let lines: Vec<usize> = result.findings.iter().map(|f| f.line).collect();
+        assert_eq!(lines, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_list_builtin_includes_standard_profile() {
+        let builtin = ProfileLoader::list_builtin();
+        let names: Vec<&str> = builtin.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names.len(), EMBEDDED_PROFILES.len() + 1);
+        assert!(names.contains(&"core"));
+        assert!(names.contains(&"antislop-standard"));
+        assert!(builtin
+            .iter()
+            .all(|p| p.builtin && p.path.as_os_str().is_empty()));
+
+        // Built-in profiles load without any profile directories
+        let temp = tempfile::TempDir::new().unwrap();
+        let loader = ProfileLoader::with_dirs(
+            temp.path().join("cache"),
+            temp.path().join("project"),
+            temp.path().join("user"),
+        );
+        let standard = loader
+            .load(&ProfileSource::Builtin("antislop-standard".to_string()))
+            .unwrap();
+        assert!(standard.patterns.len() > core_profile().patterns.len());
+    }
+
+    #[test]
+    fn test_strict_profile_resolves_builtin_extends() {
+        // No profile directories exist, so every extended profile must be
+        // embedded for its patterns to be merged
+        let temp = tempfile::TempDir::new().unwrap();
+        let loader = ProfileLoader::with_dirs(
+            temp.path().join("cache"),
+            temp.path().join("project"),
+            temp.path().join("user"),
+        );
+        let load = |name: &str| {
+            loader
+                .load(&ProfileSource::Builtin(name.to_string()))
+                .unwrap()
+        };
+        let strict = load("antislop-strict");
+        for extended in ["antislop-standard", "security"] {
+            let extended = load(extended);
+            assert!(extended
+                .patterns
+                .iter()
+                .all(|p| strict.patterns.iter().any(|s| s.regex == p.regex)));
+        }
+    }
+
+    #[test]
+    fn test_magic_numbers_profile_enables_pass() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let loader = ProfileLoader::with_dirs(
+            temp.path().join("cache"),
+            temp.path().join("project"),
+            temp.path().join("user"),
+        );
+        let profile = loader
+            .load(&ProfileSource::Builtin("magic-numbers".to_string()))
+            .unwrap();
+        assert!(profile.patterns.is_empty());
+        let settings = profile.magic_numbers.expect("magic_numbers section");
+        assert!(settings.allowlist.contains(&"0".to_string()));
+    }
+
+    #[test]
+    fn test_chat_leakage_profile_flags_pasted_apology() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let loader = ProfileLoader::with_dirs(
+            temp.path().join("cache"),
+            temp.path().join("project"),
+            temp.path().join("user"),
+        );
+        let profile = loader
+            .load(&ProfileSource::Builtin("chat-leakage".to_string()))
+            .unwrap();
+        assert!(profile
+            .patterns
+            .iter()
+            .all(|p| p.category == PatternCategory::Boilerplate && p.severity == Severity::Medium));
+
+        let scanner = crate::Scanner::new(profile.patterns).unwrap();
+        let code = r#"def parse(data):
+    """I apologize for the confusion. Here is the corrected code.
+
+    Parses the payload.
+    """
+    return json.loads(data)
+"#;
+        let result = scanner.scan_file("parser.py", code);
+        let lines: Vec<usize> = result.findings.iter().map(|f| f.line).collect();
+        assert_eq!(lines, vec![2, 2]);
+    }
+
+    #[test]
+    fn test_hedging_filler_profile_matches_standalone_qualifiers() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let loader = ProfileLoader::with_dirs(
+            temp.path().join("cache"),
+            temp.path().join("project"),
+            temp.path().join("user"),
+        );
+        let profile = loader
+            .load(&ProfileSource::Builtin("hedging-filler".to_string()))
+            .unwrap();
+        assert!(profile
+            .patterns
+            .iter()
+            .all(|p| p.category == PatternCategory::Hedging && p.severity == Severity::Low));
+
+        let scanner = crate::Scanner::new(profile.patterns).unwrap();
+        let code = "# Just a wrapper around the client
+# adjust the value before sending
+# then we simply call flush
+# basically the same as load
+# justified by the spec
+";
+        let result = scanner.scan_file("client.py", code);
+        let lines: Vec<usize> = result.findings.iter().map(|f| f.line).collect();
+        assert_eq!(lines, vec![1, 3, 4]);
+    }
+
+    #[test]
+    fn test_requires_newer_version_warns() {
+        let toml = r#"
+            [metadata]
+            name = "future"
+            requires_version = "99.0.0"
+        "#;
+        let profile = Profile::from_toml(toml).unwrap();
+        let warning = validate::required_version_warning(&profile).expect("version warning");
+        assert!(warning.contains("99.0.0") && warning.contains(crate::VERSION));
+
+        let current = Profile::from_toml(&toml.replace("99.0.0", crate::VERSION)).unwrap();
+        assert!(validate::required_version_warning(&current).is_none());
+
+        // Strict loading refuses the profile instead of warning
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("future.toml");
+        fs::write(&path, toml).unwrap();
+        let loader = ProfileLoader::with_dirs(
+            temp.path().join("cache"),
+            temp.path().join("project"),
+            temp.path().join("user"),
+        );
+        let source = ProfileSource::Local(path);
+        let loaded = loader.load(&source).unwrap();
+        assert_eq!(loaded.warnings.len(), 1);
+        assert!(loaded.warnings[0].starts_with("profile 'future': "));
+        assert!(matches!(
+            loader.with_strict(true).load(&source),
+            Err(Error::ProfileParse(_))
+        ));
+    }
+
+    #[test]
+    fn test_profile_from_toml_rejects_invalid_regex() {
+        let toml = r#"
+            [metadata]
+            name = "bad-regex"
+
+            [[patterns]]
+            regex = "(?i)TODO:("
+            category = "placeholder"
+        "#;
+
+        let result = Profile::from_toml(toml);
+        assert!(matches!(result, Err(Error::ProfileParse(_))));
+    }
+
+    #[test]
+    fn test_profile_from_toml_strict_rejects_whitespace_pattern() {
+        let toml = r#"
+            [metadata]
+            name = "style-profile"
+            version = "1.0.0"
+
+            [[patterns]]
+            regex = "[ ]+$"
+            severity = "low"
+            message = "Trailing whitespace"
+            category = "placeholder"
+        "#;
+
+        assert!(Profile::from_toml(toml).is_ok());
+        let strict = Profile::from_toml_strict(toml);
+        assert!(matches!(strict, Err(Error::ProfileParse(_))));
+    }
+
     #[test]
     fn test_profile_merge_with() {
         let mut base = Profile {
@@ -525,7 +987,10 @@ mod tests {
                 category: PatternCategory::Placeholder,
                 ast_query: None,
                 languages: vec![],
+                ..Default::default()
             }],
+            magic_numbers: None,
+            warnings: Vec::new(),
         };
 
         let extension = Profile {
@@ -540,11 +1005,27 @@ mod tests {
                 category: PatternCategory::Placeholder,
                 ast_query: None,
                 languages: vec![],
+                ..Default::default()
             }],
+            magic_numbers: Some(MagicNumbers::default()),
+            warnings: Vec::new(),
         };
 
         base.merge_with(&extension);
         assert_eq!(base.patterns.len(), 2);
+        assert_eq!(base.magic_numbers, Some(MagicNumbers::default()));
+    }
+
+    #[test]
+    fn test_profile_error_kinds() {
+        let missing = Profile::from_file(Path::new("/nonexistent/profile.toml"));
+        assert!(matches!(missing, Err(Error::CacheIo { .. })));
+
+        let malformed = Profile::from_toml("[metadata\nname = ");
+        assert!(matches!(malformed, Err(Error::ProfileParse(_))));
+
+        let unnamed = Profile::from_toml("[metadata]\nname = \"\"\n");
+        assert!(matches!(unnamed, Err(Error::ProfileParse(_))));
     }
 
     #[test]
@@ -572,6 +1053,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_profile_source_parse_github_shorthand() {
+        let source = ProfileSource::parse("gh:acme/standards/profiles/strict.toml@v1.2").unwrap();
+        match source {
+            ProfileSource::Remote(url) => assert_eq!(
+                url,
+                "https://raw.githubusercontent.com/acme/standards/v1.2/profiles/strict.toml"
+            ),
+            _ => panic!("Expected Remote source"),
+        }
+    }
+
+    #[test]
+    fn test_profile_source_parse_github_shorthand_default_ref() {
+        let source = ProfileSource::parse("gh:acme/standards/strict.toml").unwrap();
+        match source {
+            ProfileSource::Remote(url) => assert_eq!(
+                url,
+                "https://raw.githubusercontent.com/acme/standards/main/strict.toml"
+            ),
+            _ => panic!("Expected Remote source"),
+        }
+    }
+
+    #[test]
+    fn test_profile_source_parse_github_shorthand_invalid() {
+        assert!(ProfileSource::parse("gh:acme/strict.toml").is_err());
+        assert!(ProfileSource::parse("gh:acme/standards/strict.toml@").is_err());
+        assert!(ProfileSource::parse("gh://standards/strict.toml").is_err());
+    }
+
     #[test]
     fn test_profile_source_parse_builtin() {
         let source = ProfileSource::parse("my-profile").unwrap();
@@ -581,6 +1093,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_prune_cache_removes_stale_profiles() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let cache_dir = temp.path().join("cache");
+        fs::create_dir_all(&cache_dir).unwrap();
+
+        let old = cache_dir.join("old-profile.toml");
+        let fresh = cache_dir.join("fresh-profile.toml");
+        fs::write(&old, "[metadata]\nname = \"old\"\n").unwrap();
+        fs::write(&fresh, "[metadata]\nname = \"fresh\"\n").unwrap();
+
+        let two_days_ago = std::time::SystemTime::now() - Duration::from_secs(48 * 60 * 60);
+        fs::File::options()
+            .write(true)
+            .open(&old)
+            .unwrap()
+            .set_modified(two_days_ago)
+            .unwrap();
+
+        let loader = ProfileLoader::with_dirs(
+            cache_dir,
+            temp.path().join("project"),
+            temp.path().join("user"),
+        );
+        let removed = loader.prune_cache(cache::DEFAULT_CACHE_TTL).unwrap();
+
+        assert_eq!(removed, vec!["old-profile".to_string()]);
+        assert!(!old.exists());
+        assert!(fresh.exists());
+    }
+
     #[test]
     fn test_sanitize_name() {
         assert_eq!(sanitize_name("test-profile"), "test_profile");
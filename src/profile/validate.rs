@@ -18,14 +18,14 @@ fn validate_metadata(profile: &super::Profile) -> Result<()> {
 
     // Name is required
     if meta.name.is_empty() {
-        return Err(Error::ConfigInvalid(
+        return Err(Error::ProfileParse(
             "Profile metadata.name is required".to_string(),
         ));
     }
 
     // Validate version format if provided
     if !meta.version.is_empty() && Version::parse(&meta.version).is_err() {
-        return Err(Error::ConfigInvalid(format!(
+        return Err(Error::ProfileParse(format!(
             "Invalid semantic version: {}",
             meta.version
         )));
@@ -34,7 +34,7 @@ fn validate_metadata(profile: &super::Profile) -> Result<()> {
     // Validate requires_version if provided
     if let Some(ref req_ver) = meta.requires_version {
         if Version::parse(req_ver).is_err() {
-            return Err(Error::ConfigInvalid(format!(
+            return Err(Error::ProfileParse(format!(
                 "Invalid requires_version format: {}",
                 req_ver
             )));
@@ -44,6 +44,19 @@ fn validate_metadata(profile: &super::Profile) -> Result<()> {
     Ok(())
 }
 
+/// A warning if the profile's `requires_version` is newer than the running
+/// antislop, whose patterns or settings it may then rely on.
+pub fn required_version_warning(profile: &super::Profile) -> Option<String> {
+    let required = Version::parse(profile.metadata.requires_version.as_deref()?).ok()?;
+    let running = Version::parse(crate::VERSION).ok()?;
+    (required > running).then(|| {
+        format!(
+            "requires antislop {} but this is {}; upgrade antislop or some patterns may not behave as intended",
+            required, running
+        )
+    })
+}
+
 /// Validate all patterns in the profile.
 fn validate_patterns(profile: &super::Profile) -> Result<()> {
     let mut seen_regex = HashSet::new();
@@ -52,23 +65,21 @@ fn validate_patterns(profile: &super::Profile) -> Result<()> {
         // Check for duplicate regexes
         let regex_str = pattern.regex.to_string();
         if seen_regex.contains(&regex_str) {
-            return Err(Error::ConfigInvalid(format!(
+            return Err(Error::ProfileParse(format!(
                 "Duplicate pattern at index {}: regex '{}' is already used",
                 idx, regex_str
             )));
         }
         seen_regex.insert(regex_str);
 
-        // Validate regex (already validated by RegexPattern type)
-        // Validate severity
-        let _ = format!("{:?}", pattern.severity);
-
-        // Validate category
-        let _ = format!("{:?}", pattern.category);
+        // Regex itself is validated by RegexPattern; check examples here
+        pattern
+            .validate()
+            .map_err(|e| Error::ProfileParse(format!("Pattern at index {}: {}", idx, e)))?;
 
         // Validate message
         if pattern.message.is_empty() {
-            return Err(Error::ConfigInvalid(format!(
+            return Err(Error::ProfileParse(format!(
                 "Pattern at index {} has empty message",
                 idx
             )));
@@ -76,7 +87,7 @@ fn validate_patterns(profile: &super::Profile) -> Result<()> {
 
         // If AST query is provided, languages must also be provided
         if pattern.ast_query.is_some() && pattern.languages.is_empty() {
-            return Err(Error::ConfigInvalid(format!(
+            return Err(Error::ProfileParse(format!(
                 "Pattern at index {} has ast_query but no languages specified",
                 idx
             )));
@@ -86,7 +97,7 @@ fn validate_patterns(profile: &super::Profile) -> Result<()> {
         if let Some(ref query) = pattern.ast_query {
             for lang in &pattern.languages {
                 if !is_valid_language(lang) {
-                    return Err(Error::ConfigInvalid(format!(
+                    return Err(Error::ProfileParse(format!(
                         "Pattern at index {} has unknown language '{}'. \
                         Valid languages: Python, JavaScript, TypeScript, Rust, Go, Java, C++, C#, Ruby, Haskell, Lua, Scala",
                         idx, lang
@@ -152,7 +163,7 @@ fn validate_ast_query(query: &str) -> Result<()> {
             ')' => {
                 depth -= 1;
                 if depth < 0 {
-                    return Err(Error::ConfigInvalid(
+                    return Err(Error::ProfileParse(
                         "Unbalanced parentheses in AST query".to_string(),
                     ));
                 }
@@ -162,14 +173,14 @@ fn validate_ast_query(query: &str) -> Result<()> {
     }
 
     if depth != 0 {
-        return Err(Error::ConfigInvalid(
+        return Err(Error::ProfileParse(
             "Unbalanced parentheses in AST query".to_string(),
         ));
     }
 
     // Check for basic pattern: (node_type) @capture
     if !query.contains('@') {
-        return Err(Error::ConfigInvalid(
+        return Err(Error::ProfileParse(
             "AST query must contain at least one capture (@capture)".to_string(),
         ));
     }
@@ -236,6 +247,27 @@ pub fn validate_mece_compliance(profile: &super::Profile) -> Result<MeceReport>
     })
 }
 
+/// Reject profiles that violate the orthogonality principle.
+///
+/// Warnings are printed to stderr; any violation is returned as an error.
+pub fn enforce_mece_compliance(profile: &super::Profile) -> Result<()> {
+    let report = validate_mece_compliance(profile)?;
+
+    for warning in &report.warnings {
+        eprintln!("warning: profile '{}': {}", profile.metadata.name, warning);
+    }
+
+    if !report.is_compliant {
+        return Err(Error::ProfileParse(format!(
+            "Profile '{}' failed strict validation: {}",
+            profile.metadata.name,
+            report.violations.join("; ")
+        )));
+    }
+
+    Ok(())
+}
+
 /// Report from orthogonality compliance validation.
 #[derive(Debug, Clone)]
 pub struct MeceReport {
@@ -266,7 +298,10 @@ mod tests {
                 category: PatternCategory::Placeholder,
                 ast_query: None,
                 languages: vec![],
+                ..Default::default()
             }],
+            magic_numbers: None,
+            warnings: Vec::new(),
         }
     }
 
@@ -290,6 +325,17 @@ mod tests {
         assert!(validate_patterns(&profile).is_err());
     }
 
+    #[test]
+    fn test_validate_patterns_examples() {
+        let mut profile = test_profile();
+        profile.patterns[0].examples = vec!["# TODO: fix".to_string()];
+        profile.patterns[0].counter_examples = vec!["todo list".to_string()];
+        assert!(validate_patterns(&profile).is_ok());
+
+        profile.patterns[0].counter_examples = vec!["todo: lowercase".to_string()];
+        assert!(validate_patterns(&profile).is_err());
+    }
+
     #[test]
     fn test_validate_ast_query_valid() {
         assert!(validate_ast_query("(function_declaration) @func").is_ok());
@@ -315,6 +361,14 @@ mod tests {
         assert!(report.is_compliant);
     }
 
+    #[test]
+    fn test_strict_rejects_style_pattern() {
+        let mut profile = test_profile();
+        profile.patterns[0].message = "Trailing whitespace".to_string();
+        assert!(enforce_mece_compliance(&profile).is_err());
+        assert!(enforce_mece_compliance(&test_profile()).is_ok());
+    }
+
     #[test]
     fn test_is_valid_language() {
         assert!(is_valid_language("Python"));
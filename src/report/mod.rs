@@ -1,14 +1,24 @@
 //! Reporting and output formatting.
 
+use crate::baseline::{BaselineDiff, BaselineEntry};
+use crate::blame::{BlameInfo, BlameMap};
 use crate::config::{PatternCategory, Severity};
-use crate::detector::{Finding, ScanSummary};
+use crate::detector::{Finding, PatternStat, ScanSummary};
 use crate::Error;
 use crate::Result;
 use owo_colors::OwoColorize;
 use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
 use std::io::{self, Write};
+use std::path::Path;
 
 mod sarif;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+pub use sarif::SarifWriter;
+#[cfg(feature = "sqlite")]
+pub use sqlite::write_sqlite;
 
 /// Output format.
 #[derive(Debug, Clone, Copy, clap::ValueEnum, PartialEq, Eq)]
@@ -32,11 +42,34 @@ impl Format {
     }
 }
 
+/// How much of the human summary footer to print.
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum, PartialEq, Eq)]
+pub enum SummaryDetail {
+    /// Files, findings, score and verdict only.
+    Minimal,
+    /// Also the per-KLOC rates and the severity and category breakdowns.
+    #[default]
+    Full,
+}
+
 /// JSON output structure.
 #[derive(Debug, Serialize)]
 struct JsonOutput {
-    summary: JsonSummary,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<JsonSummary>,
     findings: Vec<JsonFinding>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pattern_stats: Option<Vec<PatternStat>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    baseline: Option<JsonBaselineDiff>,
+}
+
+/// How the reported findings compare with the baseline.
+#[derive(Debug, Serialize)]
+struct JsonBaselineDiff {
+    new: usize,
+    still_present: usize,
+    resolved: Vec<BaselineEntry>,
 }
 
 #[derive(Debug, Serialize)]
@@ -45,6 +78,9 @@ struct JsonSummary {
     files_with_findings: usize,
     total_findings: usize,
     total_score: u32,
+    lines_scanned: usize,
+    score_per_kloc: f64,
+    findings_per_kloc: f64,
     by_severity: serde_json::Value,
     by_category: serde_json::Value,
 }
@@ -58,32 +94,222 @@ struct JsonFinding {
     category: String,
     message: String,
     match_text: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    informational: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    suggestion: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cell: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pattern_regex: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blame: Option<BlameInfo>,
 }
 
 /// Reporter for scan results.
 pub struct Reporter {
     format: Format,
+    group_by_dir: bool,
+    collapse: bool,
+    top_files: Option<usize>,
+    summary_detail: SummaryDetail,
+    tab_width: usize,
+    hyperlinks: bool,
+    context_line_numbers: bool,
+    show_pattern: bool,
+    score_weights: HashMap<Severity, u32>,
+    severity_map: HashMap<PatternCategory, Severity>,
+    blame: BlameMap,
+    message_template: Option<String>,
+    pattern_stats: Option<Vec<PatternStat>>,
+    baseline_diff: Option<BaselineDiff>,
 }
 
 impl Reporter {
     /// Create a new reporter.
     pub fn new(format: Format) -> Self {
-        Self { format }
+        Self {
+            format,
+            group_by_dir: false,
+            collapse: false,
+            top_files: None,
+            summary_detail: SummaryDetail::Full,
+            tab_width: 1,
+            hyperlinks: false,
+            context_line_numbers: true,
+            show_pattern: false,
+            score_weights: HashMap::new(),
+            severity_map: HashMap::new(),
+            blame: BlameMap::new(),
+            message_template: None,
+            pattern_stats: None,
+            baseline_diff: None,
+        }
+    }
+
+    /// Print each distinct `(category, message)` pair once in human output,
+    /// followed by the `file:line:col` of every occurrence, instead of a
+    /// context block per finding.
+    pub fn with_collapse(mut self, collapse: bool) -> Self {
+        self.collapse = collapse;
+        self
+    }
+
+    /// Group human-readable findings under per-directory headers.
+    pub fn with_group_by_dir(mut self, group_by_dir: bool) -> Self {
+        self.group_by_dir = group_by_dir;
+        self
+    }
+
+    /// Expand tabs to this many columns when printing source lines, so carets
+    /// line up under matches in tab-indented code.
+    pub fn with_tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width.max(1);
+        self
+    }
+
+    /// Attribute findings to commits using the given `git blame` results.
+    pub fn with_blame(mut self, blame: BlameMap) -> Self {
+        self.blame = blame;
+        self
+    }
+
+    /// Include per-pattern hit counts in JSON output.
+    pub fn with_pattern_stats(mut self, stats: Vec<PatternStat>) -> Self {
+        self.pattern_stats = Some(stats);
+        self
+    }
+
+    /// Report findings as new since the baseline: human output gets a
+    /// headline count and a list of resolved entries, JSON a `baseline`
+    /// object.
+    pub fn with_baseline_diff(mut self, diff: Option<BaselineDiff>) -> Self {
+        self.baseline_diff = diff;
+        self
+    }
+
+    /// Render help URLs in human output as OSC 8 hyperlinks. Only enable
+    /// this for terminals that support them; otherwise URLs print as text.
+    pub fn with_hyperlinks(mut self, hyperlinks: bool) -> Self {
+        self.hyperlinks = hyperlinks;
+        self
+    }
+
+    /// Number context lines as the lines adjacent to the finding. Disable
+    /// when the scanner skipped blank lines for context, since the context
+    /// may then come from further away.
+    pub fn with_context_line_numbers(mut self, numbered: bool) -> Self {
+        self.context_line_numbers = numbered;
+        self
+    }
+
+    /// Show the regex of the pattern behind each finding in the human
+    /// header and as `pattern_regex` in JSON, to trace false positives.
+    pub fn with_pattern_shown(mut self, show: bool) -> Self {
+        self.show_pattern = show;
+        self
+    }
+
+    /// Per-severity score overrides for the directory subtotals, matching
+    /// the scanner's.
+    pub fn with_score_weights(mut self, weights: HashMap<Severity, u32>) -> Self {
+        self.score_weights = weights;
+        self
+    }
+
+    /// Report findings of the mapped categories at a fixed SARIF level,
+    /// leaving their severity in human and JSON output alone.
+    pub fn with_severity_map(mut self, map: HashMap<PatternCategory, Severity>) -> Self {
+        self.severity_map = map;
+        self
+    }
+
+    fn blame_for(&self, finding: &Finding) -> Option<&BlameInfo> {
+        self.blame.get(&finding.file)?.get(&finding.line)
+    }
+
+    /// Render finding messages through a template in human and JSON output.
+    ///
+    /// Supported placeholders are `{message}`, `{category}`, `{severity}`,
+    /// `{file}`, `{line}`, and `{id}` (the matching pattern's regex).
+    pub fn with_message_template(mut self, template: Option<String>) -> Self {
+        self.message_template = template;
+        self
+    }
+
+    fn message_for(&self, finding: &Finding) -> String {
+        match &self.message_template {
+            Some(template) => render_message_template(template, finding),
+            None => finding.message.clone(),
+        }
+    }
+
+    /// List the `n` sloppiest files and the clean-file count in the human summary.
+    pub fn with_top_files(mut self, top_files: Option<usize>) -> Self {
+        self.top_files = top_files;
+        self
+    }
+
+    /// Choose how much of the human summary footer to print.
+    pub fn with_summary_detail(mut self, detail: SummaryDetail) -> Self {
+        self.summary_detail = detail;
+        self
+    }
+
+    /// Gutter label for a context line: its number, or `⋮` when context
+    /// lines may not be adjacent.
+    fn context_gutter(&self, line: usize) -> String {
+        if self.context_line_numbers {
+            line.to_string()
+        } else {
+            "⋮".to_string()
+        }
     }
 
     /// Report findings and summary.
     pub fn report(&self, results: Vec<Finding>, summary: ScanSummary) -> Result<()> {
         match self.format {
-            Format::Human => self.report_human(&results, &summary),
-            Format::Json => self.report_json(&results, &summary),
-            Format::Sarif => sarif::report_sarif(&results, &summary),
+            Format::Human => self.report_human(&results, Some(&summary)),
+            Format::Json => self.report_json(&results, Some(&summary)),
+            Format::Sarif => sarif::report_sarif(&results, &summary, &self.severity_map),
+        }
+    }
+
+    /// Report findings without the summary footer (human) or `summary`
+    /// object (JSON), for tools that compute their own totals.
+    pub fn report_findings_only(&self, results: Vec<Finding>) -> Result<()> {
+        match self.format {
+            Format::Human => self.report_human(&results, None),
+            Format::Json => self.report_json(&results, None),
+            Format::Sarif => {
+                sarif::report_sarif(&results, &ScanSummary::default(), &self.severity_map)
+            }
         }
     }
 
     /// Human-readable terminal output.
-    fn report_human(&self, results: &[Finding], summary: &ScanSummary) -> Result<()> {
+    fn report_human(&self, results: &[Finding], summary: Option<&ScanSummary>) -> Result<()> {
         let stdout = io::stdout();
         let mut handle = io::BufWriter::new(stdout.lock());
+        self.write_human(&mut handle, results, summary)
+    }
+
+    /// Write human-readable output to the given handle.
+    fn write_human(
+        &self,
+        handle: &mut impl Write,
+        results: &[Finding],
+        summary: Option<&ScanSummary>,
+    ) -> Result<()> {
+        let Some(summary) = summary else {
+            return self.write_findings(handle, results);
+        };
+
+        if let Some(diff) = &self.baseline_diff {
+            return self.write_baseline_diff(handle, results, summary, diff);
+        }
 
         if results.is_empty() {
             writeln!(
@@ -94,11 +320,144 @@ impl Reporter {
             return Ok(());
         }
 
+        self.write_findings(handle, results)?;
+        self.print_summary(handle, summary)?;
+        Ok(())
+    }
+
+    /// Write the findings as new since the baseline, followed by the
+    /// baselined findings that were resolved or are still present.
+    fn write_baseline_diff(
+        &self,
+        handle: &mut impl Write,
+        results: &[Finding],
+        summary: &ScanSummary,
+        diff: &BaselineDiff,
+    ) -> Result<()> {
+        let headline = format!("★ {} new finding(s) since baseline", results.len());
+        if results.is_empty() {
+            writeln!(handle, "{}", headline.green().bold())?;
+        } else {
+            writeln!(handle, "{}", headline.yellow().bold())?;
+            writeln!(handle)?;
+            self.write_findings(handle, results)?;
+            self.print_summary(handle, summary)?;
+        }
+
+        writeln!(handle)?;
+        if !diff.resolved.is_empty() {
+            writeln!(
+                handle,
+                "{}",
+                format!("✓ {} baselined finding(s) resolved:", diff.resolved.len()).green()
+            )?;
+            for entry in &diff.resolved {
+                writeln!(
+                    handle,
+                    "    {} [{}] {}",
+                    entry.file,
+                    entry.category,
+                    entry.match_text.dimmed()
+                )?;
+            }
+        }
+        writeln!(
+            handle,
+            "{}",
+            format!("{} baselined finding(s) still present", diff.still_present).dimmed()
+        )?;
+        Ok(())
+    }
+
+    /// Write each finding, grouped by directory if requested.
+    fn write_findings(&self, handle: &mut impl Write, results: &[Finding]) -> Result<()> {
+        if self.group_by_dir {
+            self.write_grouped_by_dir(handle, results)
+        } else {
+            let findings: Vec<&Finding> = results.iter().collect();
+            self.write_list(handle, &findings)
+        }
+    }
+
+    /// Write a run of findings, collapsed if requested.
+    fn write_list(&self, handle: &mut impl Write, findings: &[&Finding]) -> Result<()> {
+        if self.collapse {
+            return self.write_collapsed(handle, findings);
+        }
+        for finding in findings {
+            self.write_finding(handle, finding)?;
+        }
+        Ok(())
+    }
+
+    /// Write each distinct `(category, message)` pair once with a compact
+    /// list of its locations, in order of first occurrence.
+    fn write_collapsed(&self, handle: &mut impl Write, findings: &[&Finding]) -> Result<()> {
+        let mut groups: Vec<((&PatternCategory, &str), Vec<&Finding>)> = Vec::new();
+        for finding in findings {
+            let key = (&finding.category, finding.message.as_str());
+            match groups.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, group)) => group.push(finding),
+                None => groups.push((key, vec![finding])),
+            }
+        }
+
+        for ((category, message), group) in &groups {
+            let severity = group
+                .iter()
+                .map(|f| &f.severity)
+                .max_by_key(|s| s.score())
+                .unwrap_or(&Severity::Low);
+            writeln!(
+                handle,
+                "{} [{}] {} {}",
+                severity.as_str().bold(),
+                category,
+                message,
+                format!("({} occurrences)", group.len()).dimmed()
+            )?;
+            for finding in group {
+                writeln!(
+                    handle,
+                    "  {} {}:{}:{}",
+                    "│".dimmed(),
+                    finding.file.cyan(),
+                    finding.line,
+                    finding.column
+                )?;
+            }
+            writeln!(handle)?;
+        }
+        Ok(())
+    }
+
+    /// Write findings grouped under directory headers with per-directory subtotals.
+    fn write_grouped_by_dir(&self, handle: &mut impl Write, results: &[Finding]) -> Result<()> {
+        let mut by_dir: BTreeMap<String, Vec<&Finding>> = BTreeMap::new();
         for finding in results {
-            self.write_finding(&mut handle, finding)?;
+            let dir = Path::new(&finding.file)
+                .parent()
+                .map(|p| p.display().to_string())
+                .filter(|d| !d.is_empty())
+                .unwrap_or_else(|| ".".to_string());
+            by_dir.entry(dir).or_default().push(finding);
         }
 
-        self.print_summary(&mut handle, summary)?;
+        for (dir, findings) in &by_dir {
+            let score: u32 = findings
+                .iter()
+                .map(|f| f.weighted_score(&self.score_weights))
+                .sum();
+            writeln!(
+                handle,
+                "{} {} {}",
+                "▸".cyan(),
+                dir.bold(),
+                format!("({} findings, score {})", findings.len(), score).dimmed()
+            )?;
+            writeln!(handle)?;
+            self.write_list(handle, findings)?;
+        }
         Ok(())
     }
 
@@ -113,16 +472,6 @@ impl Reporter {
             }
         };
 
-        let category_color = |c: &PatternCategory| -> &'static str {
-            match c {
-                PatternCategory::Placeholder => "\x1b[96m", // bright cyan
-                PatternCategory::Deferral => "\x1b[95m",    // bright magenta
-                PatternCategory::Hedging => "\x1b[93m",     // bright yellow
-                PatternCategory::Stub => "\x1b[91m",        // bright red
-                PatternCategory::NamingConvention => "\x1b[38;5;214m", // orange
-            }
-        };
-
         let reset = "\x1b[0m";
         let dim = "\x1b[2m";
         let bold = "\x1b[1m";
@@ -145,16 +494,57 @@ impl Reporter {
             finding.severity.as_str(),
             reset
         )?;
-        writeln!(
+        write!(
             handle,
             "{}[{}]{}",
             category_color(&finding.category),
-            format!("{:?}", finding.category).to_lowercase(),
+            finding.category,
             reset
         )?;
+        if let Some(cell) = finding.cell {
+            write!(handle, " {}", format!("(cell {})", cell).dimmed())?;
+        }
+        if finding.informational {
+            write!(handle, " {}", "(info)".dimmed())?;
+        }
+        if self.show_pattern {
+            write!(
+                handle,
+                " {}",
+                format!("/{}/", finding.pattern_regex).dimmed()
+            )?;
+        }
+        writeln!(handle)?;
 
         // Message
-        writeln!(handle, "  {} {}", "│".dimmed(), finding.message.dimmed())?;
+        writeln!(
+            handle,
+            "  {} {}",
+            "│".dimmed(),
+            self.message_for(finding).dimmed()
+        )?;
+        if let Some(url) = &finding.help_url {
+            let link = if self.hyperlinks {
+                format!("\x1b]8;;{url}\x1b\\{url}\x1b]8;;\x1b\\")
+            } else {
+                url.clone()
+            };
+            writeln!(handle, "  {} {} {}", "│".dimmed(), "help:".dimmed(), link)?;
+        }
+        if let Some(blame) = self.blame_for(finding) {
+            writeln!(
+                handle,
+                "  {} {}",
+                "│".dimmed(),
+                format!(
+                    "introduced in {} by {} on {}",
+                    blame.short_commit(),
+                    blame.author,
+                    blame.date
+                )
+                .dimmed()
+            )?;
+        }
         writeln!(handle, "  {}", "│".dimmed())?;
 
         // Calculate line number width for padding
@@ -167,9 +557,9 @@ impl Reporter {
                 handle,
                 "{}{:>width$} │{} {}",
                 dim,
-                prev_line,
+                self.context_gutter(prev_line),
                 reset,
-                before.dimmed(),
+                self.expand_tabs(before).dimmed(),
                 width = line_width
             )?;
         }
@@ -182,14 +572,14 @@ impl Reporter {
                 bold,
                 finding.line,
                 reset,
-                source.yellow(),
+                self.expand_tabs(source).yellow(),
                 width = line_width
             )?;
 
             // Caret line pointing to the match
             let col = finding.column.saturating_sub(1);
             let match_len = finding.match_text.len().max(1);
-            let padding = " ".repeat(col);
+            let padding = " ".repeat(self.display_column(source, col));
             let caret = "^".repeat(match_len);
             writeln!(
                 handle,
@@ -213,9 +603,9 @@ impl Reporter {
                 handle,
                 "{}{:>width$} │{} {}",
                 dim,
-                next_line,
+                self.context_gutter(next_line),
                 reset,
-                after.dimmed(),
+                self.expand_tabs(after).dimmed(),
                 width = line_width
             )?;
         }
@@ -224,6 +614,33 @@ impl Reporter {
         Ok(())
     }
 
+    /// Expand tabs to tab stops of `tab_width` columns.
+    ///
+    /// With the default width of 1, lines are printed unchanged.
+    fn expand_tabs(&self, line: &str) -> String {
+        if self.tab_width <= 1 || !line.contains('\t') {
+            return line.to_string();
+        }
+        let mut out = String::with_capacity(line.len());
+        for c in line.chars() {
+            if c == '\t' {
+                let width = out.chars().count();
+                out.push_str(&" ".repeat(self.tab_width - width % self.tab_width));
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    }
+
+    /// Display column of the given byte offset in `line` after tab expansion.
+    fn display_column(&self, line: &str, byte_offset: usize) -> usize {
+        match line.get(..byte_offset) {
+            Some(prefix) => self.expand_tabs(prefix).chars().count(),
+            None => byte_offset,
+        }
+    }
+
     /// Print summary statistics.
     fn print_summary(&self, handle: &mut impl Write, summary: &ScanSummary) -> Result<()> {
         writeln!(handle, "{}", "─".repeat(60).dimmed())?;
@@ -250,7 +667,18 @@ impl Reporter {
             summary.total_score.to_string().bold()
         )?;
 
-        if !summary.by_severity.is_empty() {
+        if self.summary_detail == SummaryDetail::Full && summary.lines_scanned > 0 {
+            writeln!(
+                handle,
+                "{} {:.1} score / {:.1} findings per KLOC ({} lines)",
+                "📏".cyan(),
+                summary.score_per_kloc(),
+                summary.findings_per_kloc(),
+                summary.lines_scanned
+            )?;
+        }
+
+        if self.summary_detail == SummaryDetail::Full && !summary.by_severity.is_empty() {
             writeln!(handle)?;
             write!(handle, "  By severity: ")?;
             for severity in [
@@ -272,36 +700,41 @@ impl Reporter {
             writeln!(handle)?;
         }
 
-        if !summary.by_category.is_empty() {
+        if self.summary_detail == SummaryDetail::Full && !summary.by_category.is_empty() {
             writeln!(handle)?;
             write!(handle, "  By category: ")?;
-            for category in [
-                PatternCategory::Placeholder,
-                PatternCategory::Stub,
-                PatternCategory::Deferral,
-                PatternCategory::Hedging,
-                PatternCategory::NamingConvention,
-            ] {
-                if let Some(&count) = summary.by_category.get(&category) {
-                    let color = match category {
-                        PatternCategory::Placeholder => "\x1b[96m",
-                        PatternCategory::Stub => "\x1b[91m",
-                        PatternCategory::Deferral => "\x1b[95m",
-                        PatternCategory::Hedging => "\x1b[93m",
-                        PatternCategory::NamingConvention => "\x1b[38;5;214m",
-                    };
+            for category in PatternCategory::ALL {
+                if let Some(&count) = summary.by_category.get(category) {
                     write!(
                         handle,
                         "{}{} {} \x1b[0m",
-                        color,
+                        category_color(category),
                         count,
-                        format!("{:?}", category).to_lowercase()
+                        category
                     )?;
                 }
             }
             writeln!(handle)?;
         }
 
+        if let Some(n) = self.top_files {
+            let sloppiest = summary.sloppiest_files(n);
+            if !sloppiest.is_empty() {
+                writeln!(handle)?;
+                writeln!(handle, "  Sloppiest files:")?;
+                for (path, score) in sloppiest {
+                    writeln!(handle, "    {:>5}  {}", score.to_string().bold(), path)?;
+                }
+            }
+            writeln!(handle)?;
+            writeln!(
+                handle,
+                "  {} {} clean files",
+                "✓".green(),
+                summary.clean_files()
+            )?;
+        }
+
         writeln!(handle)?;
 
         let verdict = match summary.total_score {
@@ -317,57 +750,120 @@ impl Reporter {
     }
 
     /// JSON output.
-    fn report_json(&self, results: &[Finding], summary: &ScanSummary) -> Result<()> {
-        use serde_json::Value;
+    fn report_json(&self, results: &[Finding], summary: Option<&ScanSummary>) -> Result<()> {
+        let output = self.json_output(results, summary);
 
-        let by_severity: Value = summary
-            .by_severity
-            .iter()
-            .map(|(k, v)| (k.as_str().to_lowercase(), Value::from(*v)))
-            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&output)
+                .map_err(|e| Error::Serialization(e.to_string()))?
+        );
+        Ok(())
+    }
 
-        let by_category: Value = summary
-            .by_category
-            .iter()
-            .map(|(k, v)| (format!("{:?}", k).to_lowercase(), Value::from(*v)))
-            .collect();
+    /// Build the JSON document for the given findings and optional summary.
+    fn json_output(&self, results: &[Finding], summary: Option<&ScanSummary>) -> JsonOutput {
+        use serde_json::Value;
 
-        let output = JsonOutput {
-            summary: JsonSummary {
+        JsonOutput {
+            summary: summary.map(|summary| JsonSummary {
                 files_scanned: summary.files_scanned,
                 files_with_findings: summary.files_with_findings,
                 total_findings: summary.total_findings,
                 total_score: summary.total_score,
-                by_severity,
-                by_category,
-            },
+                lines_scanned: summary.lines_scanned,
+                score_per_kloc: summary.score_per_kloc(),
+                findings_per_kloc: summary.findings_per_kloc(),
+                by_severity: summary
+                    .by_severity
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), Value::from(*v)))
+                    .collect(),
+                by_category: summary
+                    .by_category
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), Value::from(*v)))
+                    .collect(),
+            }),
             findings: results
                 .iter()
                 .map(|f| JsonFinding {
                     file: f.file.clone(),
                     line: f.line,
                     column: f.column,
-                    severity: f.severity.as_str().to_string().to_lowercase(),
-                    category: format!("{:?}", f.category).to_lowercase(),
-                    message: f.message.clone(),
+                    severity: f.severity.to_string(),
+                    category: f.category.to_string(),
+                    message: self.message_for(f),
                     match_text: f.match_text.clone(),
+                    informational: f.informational,
+                    help_url: f.help_url.clone(),
+                    suggestion: f.suggestion.clone(),
+                    cell: f.cell,
+                    pattern_regex: self.show_pattern.then(|| f.pattern_regex.clone()),
+                    blame: self.blame_for(f).cloned(),
                 })
                 .collect(),
-        };
+            pattern_stats: self.pattern_stats.clone(),
+            baseline: self.baseline_diff.as_ref().map(|diff| JsonBaselineDiff {
+                new: results.len(),
+                still_present: diff.still_present,
+                resolved: diff.resolved.clone(),
+            }),
+        }
+    }
+}
 
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&output)
-                .map_err(|e| Error::ConfigInvalid(e.to_string()))?
-        );
-        Ok(())
+/// ANSI color for a category in human output.
+fn category_color(category: &PatternCategory) -> &'static str {
+    match category {
+        PatternCategory::Placeholder => "\x1b[96m", // bright cyan
+        PatternCategory::Deferral => "\x1b[95m",    // bright magenta
+        PatternCategory::Hedging => "\x1b[93m",     // bright yellow
+        PatternCategory::Stub => "\x1b[91m",        // bright red
+        PatternCategory::NamingConvention => "\x1b[38;5;214m", // orange
+        PatternCategory::StaleComment => "\x1b[94m", // bright blue
+        PatternCategory::SecurityPlaceholder => "\x1b[31m", // red
+        PatternCategory::Boilerplate => "\x1b[35m", // magenta
+        PatternCategory::MergeConflict => "\x1b[1;31m", // bold red
+    }
+}
+
+/// Substitute `{placeholder}`s in a message template in a single pass, so
+/// braces inside substituted values are never expanded. Unknown placeholders
+/// are left as written.
+fn render_message_template(template: &str, finding: &Finding) -> String {
+    let mut out = String::with_capacity(template.len() + finding.message.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+        let value = match &after[..end] {
+            "message" => Some(finding.message.clone()),
+            "category" => Some(finding.category.to_string()),
+            "severity" => Some(finding.severity.to_string()),
+            "file" => Some(finding.file.clone()),
+            "line" => Some(finding.line.to_string()),
+            "id" => Some(finding.pattern_regex.clone()),
+            _ => None,
+        };
+        match value {
+            Some(value) => out.push_str(&value),
+            None => out.push_str(&rest[start..start + end + 2]),
+        }
+        rest = &after[end + 1..];
     }
+    out.push_str(rest);
+    out
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
 
     // Helper to create test findings
     // Note: Consider moving to common test utilities if duplicated elsewhere
@@ -391,6 +887,7 @@ mod tests {
             source_line: None,
             context_before: None,
             context_after: None,
+            ..Default::default()
         }
     }
 
@@ -404,8 +901,10 @@ mod tests {
             files_with_findings: if findings_count > 0 { 1 } else { 0 },
             total_findings: findings_count,
             total_score,
+            lines_scanned: 0,
             by_severity,
             by_category,
+            file_scores: vec![("test.rs".to_string(), total_score)],
         }
     }
 
@@ -437,7 +936,7 @@ mod tests {
 
         // Verify report_json doesn't panic
         // report_json writes to stdout; capturing it is complex in unit tests
-        let _ = reporter.report_json(&results, &summary);
+        let _ = reporter.report_json(&results, Some(&summary));
     }
 
     #[test]
@@ -447,7 +946,7 @@ mod tests {
         let summary = make_summary(0, 0);
 
         // Verify empty results don't panic
-        let _ = reporter.report_json(&results, &summary);
+        let _ = reporter.report_json(&results, Some(&summary));
     }
 
     #[test]
@@ -492,6 +991,357 @@ mod tests {
         let _ = reporter.report(results, summary);
     }
 
+    #[test]
+    fn test_reporter_group_by_dir_headers() {
+        let reporter = Reporter::new(Format::Human).with_group_by_dir(true);
+        let results = vec![
+            make_finding(
+                "src/api/a.py",
+                1,
+                Severity::Medium,
+                PatternCategory::Stub,
+                "Test message",
+                "TODO",
+            ),
+            make_finding(
+                "src/api/b.py",
+                2,
+                Severity::Medium,
+                PatternCategory::Stub,
+                "Test message",
+                "TODO",
+            ),
+            make_finding(
+                "src/db/c.py",
+                3,
+                Severity::High,
+                PatternCategory::Stub,
+                "Test message",
+                "TODO",
+            ),
+        ];
+        let summary = make_summary(25, 3);
+
+        let mut out = Vec::new();
+        reporter
+            .write_human(&mut out, &results, Some(&summary))
+            .unwrap();
+        let text = String::from_utf8_lossy(&out);
+
+        assert_eq!(text.matches("src/api").count(), 3);
+        assert!(text.contains("(2 findings, score 10)"));
+        assert!(text.contains("(1 findings, score 15)"));
+        assert!(text.find("src/api").unwrap() < text.find("src/db").unwrap());
+    }
+
+    #[test]
+    fn test_reporter_collapse_lists_locations() {
+        let reporter = Reporter::new(Format::Human).with_collapse(true);
+        let results = vec![
+            make_finding(
+                "a.py",
+                1,
+                Severity::Medium,
+                PatternCategory::Stub,
+                "Test message",
+                "TODO",
+            ),
+            make_finding(
+                "b.py",
+                7,
+                Severity::High,
+                PatternCategory::Stub,
+                "Test message",
+                "TODO",
+            ),
+            make_finding(
+                "a.py",
+                3,
+                Severity::Low,
+                PatternCategory::Hedging,
+                "Other message",
+                "maybe",
+            ),
+        ];
+
+        let mut out = Vec::new();
+        reporter.write_findings(&mut out, &results).unwrap();
+        let text = String::from_utf8_lossy(&out);
+        let ansi = regex::Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+        let plain = ansi.replace_all(&text, "");
+
+        assert_eq!(plain.matches("Test message").count(), 1);
+        assert!(plain.contains("HIGH [stub] Test message (2 occurrences)"));
+        assert!(plain.contains("│ a.py:1:"));
+        assert!(plain.contains("│ b.py:7:"));
+        assert!(plain.find("b.py").unwrap() < plain.find("Other message").unwrap());
+        assert!(!plain.contains('^'));
+    }
+
+    #[test]
+    fn test_reporter_help_url_hyperlink() {
+        let mut finding = make_finding(
+            "a.py",
+            1,
+            Severity::Medium,
+            PatternCategory::Placeholder,
+            "Test message",
+            "TODO",
+        );
+        finding.help_url = Some("https://example.com/todo".to_string());
+
+        let mut plain = Vec::new();
+        let reporter = Reporter::new(Format::Human);
+        reporter
+            .write_findings(&mut plain, &[finding.clone()])
+            .unwrap();
+        let plain = String::from_utf8(plain).unwrap();
+        assert!(plain.contains("https://example.com/todo"));
+        assert!(!plain.contains("\x1b]8;;"));
+
+        let mut linked = Vec::new();
+        let reporter = Reporter::new(Format::Human).with_hyperlinks(true);
+        reporter.write_findings(&mut linked, &[finding]).unwrap();
+        let linked = String::from_utf8(linked).unwrap();
+        assert!(linked.contains("\x1b]8;;https://example.com/todo\x1b\\"));
+    }
+
+    #[test]
+    fn test_show_pattern_includes_regex() {
+        let mut finding = make_finding(
+            "a.py",
+            1,
+            Severity::Medium,
+            PatternCategory::Placeholder,
+            "Test message",
+            "TODO",
+        );
+        finding.pattern_regex = r"(?i)\bTODO\b".to_string();
+
+        let json = serde_json::to_value(
+            Reporter::new(Format::Json).json_output(std::slice::from_ref(&finding), None),
+        )
+        .unwrap();
+        assert!(json["findings"][0].get("pattern_regex").is_none());
+
+        let reporter = Reporter::new(Format::Json).with_pattern_shown(true);
+        let json = serde_json::to_value(reporter.json_output(std::slice::from_ref(&finding), None))
+            .unwrap();
+        assert_eq!(json["findings"][0]["pattern_regex"], r"(?i)\bTODO\b");
+
+        let mut human = Vec::new();
+        Reporter::new(Format::Human)
+            .with_pattern_shown(true)
+            .write_findings(&mut human, &[finding])
+            .unwrap();
+        assert!(String::from_utf8(human)
+            .unwrap()
+            .contains(r"/(?i)\bTODO\b/"));
+    }
+
+    #[test]
+    fn test_message_template() {
+        let finding = make_finding(
+            "src/app.py",
+            12,
+            Severity::High,
+            PatternCategory::Hedging,
+            "Hedging: {uncertain}",
+            "hopefully",
+        );
+        let reporter = Reporter::new(Format::Json).with_message_template(Some(
+            "{message} [{severity}/{id}] {file}:{line} (see https://wiki/slop/{category}) {unknown}"
+                .to_string(),
+        ));
+
+        let output = reporter.json_output(std::slice::from_ref(&finding), None);
+        assert_eq!(
+            output.findings[0].message,
+            "Hedging: {uncertain} [high/test] src/app.py:12 (see https://wiki/slop/hedging) {unknown}"
+        );
+
+        let plain = Reporter::new(Format::Json).json_output(&[finding], None);
+        assert_eq!(plain.findings[0].message, "Hedging: {uncertain}");
+    }
+
+    #[test]
+    fn test_caret_aligns_with_tab_indented_match() {
+        let reporter = Reporter::new(Format::Human).with_tab_width(4);
+        let mut finding = make_finding(
+            "tabs.py",
+            2,
+            Severity::Medium,
+            PatternCategory::Placeholder,
+            "TODO marker",
+            "TODO:",
+        );
+        finding.source_line = Some("\t\t# TODO: implement".to_string());
+        finding.column = 5;
+
+        let mut out = Vec::new();
+        reporter.write_finding(&mut out, &finding).unwrap();
+        let text = String::from_utf8_lossy(&out);
+        let ansi = regex::Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+        let plain = ansi.replace_all(&text, "");
+
+        let source = plain.lines().find(|l| l.contains("# TODO")).unwrap();
+        let caret = plain.lines().find(|l| l.contains('^')).unwrap();
+        assert!(!source.contains('\t'));
+        // Compare char positions; the gutter contains multi-byte box characters
+        let char_pos = |line: &str, byte: usize| line[..byte].chars().count();
+        assert_eq!(
+            char_pos(caret, caret.find('^').unwrap()),
+            char_pos(source, source.find("TODO").unwrap())
+        );
+        assert_eq!(caret.matches('^').count(), 5);
+    }
+
+    #[test]
+    fn test_reporter_findings_only() {
+        let reporter = Reporter::new(Format::Human);
+        let results = vec![make_finding(
+            "test.rs",
+            1,
+            Severity::Medium,
+            PatternCategory::Stub,
+            "Test message",
+            "TODO",
+        )];
+
+        let mut out = Vec::new();
+        reporter.write_human(&mut out, &results, None).unwrap();
+        let text = String::from_utf8_lossy(&out);
+        assert!(text.contains("Test message"));
+        assert!(!text.contains("sloppy score"));
+
+        let json = serde_json::to_value(reporter.json_output(&results, None)).unwrap();
+        assert!(json.get("summary").is_none());
+        assert_eq!(json["findings"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_reporter_top_files() {
+        let reporter = Reporter::new(Format::Human).with_top_files(Some(5));
+        let results = vec![make_finding(
+            "test.rs",
+            1,
+            Severity::Medium,
+            PatternCategory::Stub,
+            "Test message",
+            "TODO",
+        )];
+        let mut summary = make_summary(5, 1);
+        summary.files_scanned = 3;
+        summary.file_scores.push(("clean.rs".to_string(), 0));
+
+        let mut out = Vec::new();
+        reporter
+            .write_human(&mut out, &results, Some(&summary))
+            .unwrap();
+        let text = String::from_utf8_lossy(&out);
+
+        assert!(text.contains("Sloppiest files:"));
+        assert!(text.contains("test.rs"));
+        assert!(!text.contains("clean.rs"));
+        assert!(text.contains("2 clean files"));
+    }
+
+    #[test]
+    fn test_summary_lists_every_category() {
+        let mut summary = make_summary(5, 1);
+        for category in PatternCategory::ALL {
+            summary.by_category.insert(category.clone(), 1);
+        }
+
+        let mut out = Vec::new();
+        Reporter::new(Format::Human)
+            .print_summary(&mut out, &summary)
+            .unwrap();
+        let text = String::from_utf8_lossy(&out);
+        for category in PatternCategory::ALL {
+            assert!(
+                text.contains(&format!("1 {} ", category)),
+                "missing {}",
+                category
+            );
+        }
+    }
+
+    #[test]
+    fn test_minimal_summary_omits_breakdowns() {
+        let results = vec![make_finding(
+            "test.rs",
+            1,
+            Severity::Medium,
+            PatternCategory::Stub,
+            "Test message",
+            "TODO",
+        )];
+        let mut summary = make_summary(5, 1);
+        summary.lines_scanned = 100;
+
+        let render = |detail| {
+            let mut out = Vec::new();
+            Reporter::new(Format::Human)
+                .with_summary_detail(detail)
+                .write_human(&mut out, &results, Some(&summary))
+                .unwrap();
+            String::from_utf8_lossy(&out).into_owned()
+        };
+
+        let full = render(SummaryDetail::Full);
+        assert!(full.contains("By severity:"));
+        assert!(full.contains("By category:"));
+        assert!(full.contains("per KLOC"));
+
+        let minimal = render(SummaryDetail::Minimal);
+        assert!(!minimal.contains("By severity:"));
+        assert!(!minimal.contains("By category:"));
+        assert!(!minimal.contains("per KLOC"));
+        assert!(minimal.contains("total findings"));
+        assert!(minimal.contains("sloppy score"));
+        assert!(minimal.contains("Minor slop detected"));
+    }
+
+    #[test]
+    fn test_reporter_baseline_diff() {
+        let resolved = BaselineEntry {
+            file: "old.py".to_string(),
+            category: PatternCategory::Deferral,
+            pattern_regex: "later".to_string(),
+            match_text: "fix later".to_string(),
+            source_line: "# fix later".to_string(),
+        };
+        let reporter = Reporter::new(Format::Human).with_baseline_diff(Some(BaselineDiff {
+            still_present: 2,
+            resolved: vec![resolved],
+        }));
+        let results = vec![make_finding(
+            "new.py",
+            3,
+            Severity::Medium,
+            PatternCategory::Placeholder,
+            "Test message",
+            "TODO",
+        )];
+
+        let mut out = Vec::new();
+        reporter
+            .write_human(&mut out, &results, Some(&make_summary(5, 1)))
+            .unwrap();
+        let text = String::from_utf8_lossy(&out);
+        assert!(text.contains("1 new finding(s) since baseline"));
+        assert!(text.contains("Test message"));
+        assert!(text.contains("1 baselined finding(s) resolved"));
+        assert!(text.contains("old.py [deferral]"));
+        assert!(text.contains("2 baselined finding(s) still present"));
+
+        let json = serde_json::to_value(reporter.json_output(&results, None)).unwrap();
+        assert_eq!(json["baseline"]["new"], 1);
+        assert_eq!(json["baseline"]["still_present"], 2);
+        assert_eq!(json["baseline"]["resolved"][0]["file"], "old.py");
+    }
+
     #[test]
     fn test_reporter_report_sarif() {
         let reporter = Reporter::new(Format::Sarif);
@@ -1,67 +1,321 @@
+//! SARIF 2.1.0 output.
+//!
+//! [`SarifWriter`] streams the log: each result is serialized as soon as it
+//! is written, and the rules, which summarize every result, follow the
+//! results array. Memory stays bounded by the number of categories rather
+//! than the number of findings.
+
+use crate::config::{PatternCategory, Severity};
 use crate::detector::{Finding, ScanSummary};
 use crate::Result;
 use serde_sarif::sarif::{
-    ArtifactLocation, Location, Message, PhysicalLocation, Region, Result as SarifResult,
-    ResultLevel, Run, Sarif, Tool, ToolComponent,
+    ArtifactLocation, Location, Message, MultiformatMessageString, PhysicalLocation, Region,
+    ReportingConfiguration, ReportingDescriptor, Result as SarifResult, ResultLevel, Sarif, Tool,
+    ToolComponent,
 };
+use std::collections::HashMap;
+use std::io::Write;
 
-pub fn report_sarif(results: &[Finding], _summary: &ScanSummary) -> Result<()> {
-    let mut sarif_results = Vec::new();
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
 
+pub fn report_sarif(
+    results: &[Finding],
+    _summary: &ScanSummary,
+    severity_map: &HashMap<PatternCategory, Severity>,
+) -> Result<()> {
+    let stdout = std::io::stdout();
+    let mut writer = SarifWriter::new(std::io::BufWriter::new(stdout.lock()))?
+        .with_severity_map(severity_map.clone());
     for finding in results {
-        let rule_id = format!("{:?}", finding.category).to_lowercase();
-
-        let artifact_location = ArtifactLocation::builder()
-            .uri(finding.file.clone())
-            .build();
-        let region = Region::builder()
-            .start_line(finding.line as i64)
-            .start_column(finding.column as i64)
-            .end_line(finding.line as i64)
-            .end_column((finding.column + finding.match_text.len()) as i64)
-            .build();
-        let physical_location = PhysicalLocation::builder()
-            .artifact_location(artifact_location)
-            .region(region)
-            .build();
-        let location = Location::builder()
-            .physical_location(physical_location)
-            .build();
-
-        let level = match finding.severity.as_str() {
-            "CRITICAL" | "HIGH" => ResultLevel::Error,
-            "MEDIUM" => ResultLevel::Warning,
-            _ => ResultLevel::Note,
+        writer.write_finding(finding)?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+/// Incremental SARIF writer with a single run.
+pub struct SarifWriter<W: Write> {
+    out: W,
+    rules: RuleTable,
+    results_written: usize,
+    severity_map: HashMap<PatternCategory, Severity>,
+}
+
+impl<W: Write> SarifWriter<W> {
+    /// Start a SARIF log on `out`, writing everything up to the results array.
+    pub fn new(mut out: W) -> Result<Self> {
+        // Serialize the top-level fields through serde-sarif so their keys
+        // (notably `$schema`) match the buffered document
+        let header = serde_json::to_value(log(Vec::new()))
+            .map_err(|e| crate::Error::Serialization(e.to_string()))?;
+        writeln!(out, "{{")?;
+        if let serde_json::Value::Object(fields) = header {
+            for (key, value) in fields.iter().filter(|(key, _)| *key != "runs") {
+                writeln!(
+                    out,
+                    "  {}: {},",
+                    serde_json::Value::from(key.as_str()),
+                    value
+                )?;
+            }
+        }
+        write!(out, "  \"runs\": [\n    {{\n      \"results\": [")?;
+        Ok(Self {
+            out,
+            rules: RuleTable::default(),
+            results_written: 0,
+            severity_map: HashMap::new(),
+        })
+    }
+
+    /// Report findings of the mapped categories at a fixed severity, and so
+    /// SARIF level, instead of their pattern's.
+    pub fn with_severity_map(mut self, map: HashMap<PatternCategory, Severity>) -> Self {
+        self.severity_map = map;
+        self
+    }
+
+    /// Append one result, declaring its category's rule if it is new.
+    pub fn write_finding(&mut self, finding: &Finding) -> Result<()> {
+        let severity = self
+            .severity_map
+            .get(&finding.category)
+            .unwrap_or(&finding.severity);
+        let rule_index = self.rules.observe(finding, severity);
+        let separator = if self.results_written == 0 {
+            "\n"
+        } else {
+            ",\n"
         };
+        write!(self.out, "{}        ", separator)?;
+        serde_json::to_writer(&mut self.out, &sarif_result(finding, severity, rule_index))
+            .map_err(|e| crate::Error::Serialization(e.to_string()))?;
+        self.results_written += 1;
+        Ok(())
+    }
+
+    /// Close the results array, write the tool and its rules, and return
+    /// the flushed output.
+    pub fn finish(mut self) -> Result<W> {
+        let indent = if self.results_written == 0 {
+            ""
+        } else {
+            "\n      "
+        };
+        write!(self.out, "{}],\n      \"tool\": ", indent)?;
+        serde_json::to_writer(&mut self.out, &tool(self.rules.descriptors()))
+            .map_err(|e| crate::Error::Serialization(e.to_string()))?;
+        writeln!(self.out, "\n    }}\n  ]\n}}")?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+/// Rules in order of first appearance; each keeps the highest severity
+/// seen for its category as the default level.
+#[derive(Default)]
+struct RuleTable {
+    order: Vec<PatternCategory>,
+    severity: HashMap<PatternCategory, Severity>,
+    /// The help URL shared by every finding of a category, or `None` once
+    /// its patterns disagree, since one URL would then misdocument the rule.
+    help: HashMap<PatternCategory, Option<String>>,
+}
 
-        let result = SarifResult::builder()
-            .rule_id(rule_id)
-            .message(Message::builder().text(finding.message.clone()).build())
-            .level(level)
-            .locations(vec![location])
-            .build();
+impl RuleTable {
+    /// Record a finding reported at `severity`, returning the index of its
+    /// category's rule.
+    fn observe(&mut self, finding: &Finding, severity: &Severity) -> usize {
+        let help = self
+            .help
+            .entry(finding.category.clone())
+            .or_insert_with(|| finding.help_url.clone());
+        if *help != finding.help_url {
+            *help = None;
+        }
+        match self.severity.get_mut(&finding.category) {
+            Some(highest) => {
+                if severity.score() > highest.score() {
+                    *highest = severity.clone();
+                }
+            }
+            None => {
+                self.order.push(finding.category.clone());
+                self.severity
+                    .insert(finding.category.clone(), severity.clone());
+            }
+        }
+        self.order
+            .iter()
+            .position(|c| *c == finding.category)
+            .unwrap_or_default()
+    }
 
-        sarif_results.push(result);
+    fn descriptors(&self) -> Vec<ReportingDescriptor> {
+        self.order
+            .iter()
+            .map(|category| {
+                let mut rule = rule_descriptor(category, &self.severity[category]);
+                rule.help_uri = self.help.get(category).cloned().flatten();
+                rule
+            })
+            .collect()
     }
+}
+
+/// Build the whole SARIF document in memory, for comparison with the
+/// streamed output.
+#[cfg(test)]
+fn build_sarif(results: &[Finding]) -> Sarif {
+    use serde_sarif::sarif::Run;
+
+    let mut rules = RuleTable::default();
+    let sarif_results: Vec<SarifResult> = results
+        .iter()
+        .map(|finding| {
+            sarif_result(
+                finding,
+                &finding.severity,
+                rules.observe(finding, &finding.severity),
+            )
+        })
+        .collect();
+    let run = Run::builder()
+        .tool(tool(rules.descriptors()))
+        .results(sarif_results)
+        .build();
+
+    log(vec![run])
+}
+
+/// The top-level SARIF log around `runs`.
+fn log(runs: Vec<serde_sarif::sarif::Run>) -> Sarif {
+    Sarif::builder()
+        .version("2.1.0")
+        .schema(SARIF_SCHEMA)
+        .runs(runs)
+        .build()
+}
+
+fn sarif_result(finding: &Finding, severity: &Severity, rule_index: usize) -> SarifResult {
+    let artifact_location = ArtifactLocation::builder()
+        .uri(finding.file.clone())
+        .build();
+    let region = Region::builder()
+        .start_line(finding.line as i64)
+        .start_column(finding.column as i64)
+        .end_line(finding.line as i64)
+        .end_column((finding.column + finding.match_text.len()) as i64)
+        .build();
+    let physical_location = PhysicalLocation::builder()
+        .artifact_location(artifact_location)
+        .region(region)
+        .build();
+    let location = Location::builder()
+        .physical_location(physical_location)
+        .build();
+
+    SarifResult::builder()
+        .rule_id(rule_id(&finding.category))
+        .rule_index(rule_index as i64)
+        .message(Message::builder().text(finding.message.clone()).build())
+        .level(result_level(severity))
+        .locations(vec![location])
+        .build()
+}
 
+fn tool(rules: Vec<ReportingDescriptor>) -> Tool {
     let tool_component = ToolComponent::builder()
         .name("antislop")
         .information_uri("https://github.com/skew202/antislop")
+        .rules(rules)
         .build();
-    let tool = Tool::builder().driver(tool_component).build();
-    let run = Run::builder().tool(tool).results(sarif_results).build();
+    Tool::builder().driver(tool_component).build()
+}
 
-    let sarif = Sarif::builder()
-        .version("2.1.0")
-        .schema("https://json.schemastore.org/sarif-2.1.0.json")
-        .runs(vec![run])
-        .build();
+fn rule_id(category: &PatternCategory) -> String {
+    category.to_string()
+}
+
+fn result_level(severity: &Severity) -> ResultLevel {
+    match severity {
+        Severity::Critical | Severity::High => ResultLevel::Error,
+        Severity::Medium => ResultLevel::Warning,
+        Severity::Low => ResultLevel::Note,
+    }
+}
 
-    let json = serde_json::to_string_pretty(&sarif)
-        .map_err(|e| crate::Error::ConfigInvalid(e.to_string()))?;
+/// SARIF level name for a severity, for `defaultConfiguration.level`, which
+/// serde-sarif types as plain JSON rather than [`ResultLevel`].
+fn level_str(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::Critical | Severity::High => "error",
+        Severity::Medium => "warning",
+        Severity::Low => "note",
+    }
+}
 
-    println!("{}", json);
-    Ok(())
+/// SARIF rule metadata for a category.
+fn rule_descriptor(category: &PatternCategory, severity: &Severity) -> ReportingDescriptor {
+    let (name, short, full) = match category {
+        PatternCategory::Placeholder => (
+            "Placeholder",
+            "Placeholder comment",
+            "Placeholder comments such as TODO, FIXME, or HACK mark unfinished work.",
+        ),
+        PatternCategory::Deferral => (
+            "Deferral",
+            "Deferral language",
+            "Deferral language such as \"for now\" or \"temporary\" signals work put off indefinitely.",
+        ),
+        PatternCategory::Hedging => (
+            "Hedging",
+            "Hedging language",
+            "Hedging language such as \"hopefully\" or \"should work\" signals unverified code.",
+        ),
+        PatternCategory::Stub => (
+            "Stub",
+            "Stub implementation",
+            "Stub code such as empty bodies or unimplemented markers stands in for real logic.",
+        ),
+        PatternCategory::NamingConvention => (
+            "NamingConvention",
+            "Filename convention violation",
+            "Filenames that break the project's naming convention or use suspicious suffixes.",
+        ),
+        PatternCategory::StaleComment => (
+            "StaleComment",
+            "Stale comment",
+            "Comments that contradict the code they describe.",
+        ),
+        PatternCategory::SecurityPlaceholder => (
+            "SecurityPlaceholder",
+            "Placeholder credential",
+            "Secret-like identifiers assigned placeholder values such as \"changeme\" or \"YOUR_KEY_HERE\".",
+        ),
+        PatternCategory::Boilerplate => (
+            "Boilerplate",
+            "Chat boilerplate",
+            "Assistant chat phrases such as \"I apologize for the confusion\" pasted into code.",
+        ),
+        PatternCategory::MergeConflict => (
+            "MergeConflict",
+            "Merge conflict marker",
+            "Unresolved merge conflict markers such as \"<<<<<<< HEAD\" left in source.",
+        ),
+    };
+
+    ReportingDescriptor::builder()
+        .id(rule_id(category))
+        .name(name)
+        .short_description(MultiformatMessageString::builder().text(short).build())
+        .full_description(MultiformatMessageString::builder().text(full).build())
+        .default_configuration(
+            ReportingConfiguration::builder()
+                .level(serde_json::json!(level_str(severity)))
+                .build(),
+        )
+        .build()
 }
 
 #[cfg(test)]
@@ -90,6 +344,7 @@ mod tests {
             source_line: None,
             context_before: None,
             context_after: None,
+            ..Default::default()
         }
     }
 
@@ -101,12 +356,14 @@ mod tests {
             files_with_findings: 0,
             total_findings: 0,
             total_score: 0,
+            lines_scanned: 0,
             by_severity: Default::default(),
             by_category: Default::default(),
+            file_scores: Vec::new(),
         };
 
         // Just check it doesn't error
-        let _ = report_sarif(&results, &summary);
+        let _ = report_sarif(&results, &summary, &HashMap::new());
     }
 
     #[test]
@@ -158,12 +415,14 @@ mod tests {
             files_with_findings: 1,
             total_findings: 4,
             total_score: 71,
+            lines_scanned: 0,
             by_severity: Default::default(),
             by_category: Default::default(),
+            file_scores: Vec::new(),
         };
 
         // Should not panic
-        let _ = report_sarif(&results, &summary);
+        let _ = report_sarif(&results, &summary, &HashMap::new());
     }
 
     #[test]
@@ -186,4 +445,148 @@ mod tests {
         assert_eq!(finding.message, "Test message");
         assert_eq!(finding.match_text, "TODO");
     }
+
+    #[test]
+    fn test_sarif_rules_and_indices() {
+        let results = vec![
+            make_finding(
+                "a.py",
+                1,
+                1,
+                Severity::Medium,
+                PatternCategory::Stub,
+                "Stub",
+                "pass",
+            ),
+            make_finding(
+                "a.py",
+                2,
+                1,
+                Severity::Low,
+                PatternCategory::Hedging,
+                "Hedge",
+                "maybe",
+            ),
+            make_finding(
+                "b.py",
+                3,
+                1,
+                Severity::Critical,
+                PatternCategory::Stub,
+                "Stub",
+                "todo!()",
+            ),
+        ];
+
+        let json = serde_json::to_value(build_sarif(&results)).unwrap();
+        let run = &json["runs"][0];
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0]["id"], "stub");
+        assert_eq!(rules[0]["defaultConfiguration"]["level"], "error");
+        assert!(rules[0]["shortDescription"]["text"].is_string());
+
+        for result in run["results"].as_array().unwrap() {
+            let index = result["ruleIndex"].as_u64().unwrap() as usize;
+            assert!(index < rules.len());
+            assert_eq!(rules[index]["id"], result["ruleId"]);
+        }
+    }
+
+    #[test]
+    fn test_sarif_rule_help_uri() {
+        let mut documented = make_finding(
+            "a.py",
+            1,
+            1,
+            Severity::Medium,
+            PatternCategory::Placeholder,
+            "TODO",
+            "TODO:",
+        );
+        documented.help_url = Some("https://example.com/rules/todo".to_string());
+        let deferral = |line, url: &str| Finding {
+            help_url: Some(url.to_string()),
+            ..make_finding(
+                "a.py",
+                line,
+                1,
+                Severity::Low,
+                PatternCategory::Deferral,
+                "Deferral",
+                "for now",
+            )
+        };
+        let results = vec![
+            documented.clone(),
+            make_finding(
+                "a.py",
+                2,
+                1,
+                Severity::Low,
+                PatternCategory::Hedging,
+                "Hedge",
+                "maybe",
+            ),
+            documented,
+            // Patterns of one category documented at different URLs
+            deferral(3, "https://example.com/rules/for-now"),
+            deferral(4, "https://example.com/rules/temporary"),
+        ];
+
+        let json = serde_json::to_value(build_sarif(&results)).unwrap();
+        let rules = json["runs"][0]["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap();
+        assert_eq!(rules[0]["helpUri"], "https://example.com/rules/todo");
+        assert!(rules[1].get("helpUri").is_none());
+        assert_eq!(rules[2]["id"], "deferral");
+        assert!(rules[2].get("helpUri").is_none());
+    }
+
+    #[test]
+    fn test_streamed_sarif_matches_buffered() {
+        let results = [
+            make_finding(
+                "a.py",
+                1,
+                3,
+                Severity::Medium,
+                PatternCategory::Placeholder,
+                "TODO \"quoted\"",
+                "TODO",
+            ),
+            make_finding(
+                "b.py",
+                7,
+                1,
+                Severity::High,
+                PatternCategory::Stub,
+                "Stub",
+                "pass",
+            ),
+            make_finding(
+                "a.py",
+                9,
+                1,
+                Severity::Critical,
+                PatternCategory::Placeholder,
+                "FIXME",
+                "FIXME",
+            ),
+        ];
+
+        for findings in [&results[..], &[]] {
+            let mut writer = SarifWriter::new(Vec::new()).unwrap();
+            for finding in findings {
+                writer.write_finding(finding).unwrap();
+            }
+            let streamed: serde_json::Value =
+                serde_json::from_slice(&writer.finish().unwrap()).unwrap();
+            let buffered = serde_json::to_value(build_sarif(findings)).unwrap();
+            assert_eq!(streamed, buffered);
+            assert_eq!(streamed["$schema"], SARIF_SCHEMA);
+            assert!(streamed.get("schema").is_none());
+        }
+    }
 }
@@ -0,0 +1,142 @@
+//! SQLite export of findings for querying trends across runs.
+//!
+//! Each call appends one row to `runs` and one row per finding to
+//! `findings`, joined on `findings.run_id = runs.id`.
+
+use crate::detector::{Finding, ScanSummary};
+use crate::Result;
+use rusqlite::{params, Connection};
+use std::path::Path;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    version TEXT NOT NULL,
+    files_scanned INTEGER NOT NULL,
+    files_with_findings INTEGER NOT NULL,
+    total_findings INTEGER NOT NULL,
+    total_score INTEGER NOT NULL,
+    lines_scanned INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS findings (
+    id INTEGER PRIMARY KEY,
+    run_id INTEGER NOT NULL REFERENCES runs(id),
+    file TEXT NOT NULL,
+    line INTEGER NOT NULL,
+    column INTEGER NOT NULL,
+    severity TEXT NOT NULL,
+    category TEXT NOT NULL,
+    message TEXT NOT NULL,
+    match_text TEXT NOT NULL,
+    informational INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS findings_run_id ON findings(run_id);
+";
+
+/// Append a run and its findings to the database at `path`, creating the
+/// file and tables if needed. Returns the new run's id.
+pub fn write_sqlite(path: &Path, results: &[Finding], summary: &ScanSummary) -> Result<i64> {
+    let mut conn = Connection::open(path)?;
+    insert_run(&mut conn, results, summary)
+}
+
+fn insert_run(conn: &mut Connection, results: &[Finding], summary: &ScanSummary) -> Result<i64> {
+    conn.execute_batch(SCHEMA)?;
+
+    // One transaction keeps large runs fast and never leaves a partial run
+    let tx = conn.transaction()?;
+    tx.execute(
+        "INSERT INTO runs (version, files_scanned, files_with_findings, total_findings, total_score, lines_scanned)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            crate::VERSION,
+            summary.files_scanned as i64,
+            summary.files_with_findings as i64,
+            summary.total_findings as i64,
+            summary.total_score,
+            summary.lines_scanned as i64,
+        ],
+    )?;
+    let run_id = tx.last_insert_rowid();
+
+    {
+        let mut insert = tx.prepare(
+            "INSERT INTO findings (run_id, file, line, column, severity, category, message, match_text, informational)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        )?;
+        for finding in results {
+            insert.execute(params![
+                run_id,
+                finding.file,
+                finding.line as i64,
+                finding.column as i64,
+                finding.severity.to_string(),
+                finding.category.to_string(),
+                finding.message,
+                finding.match_text,
+                finding.informational,
+            ])?;
+        }
+    }
+    tx.commit()?;
+
+    Ok(run_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{PatternCategory, Severity};
+
+    fn make_finding(file: &str, line: usize, category: PatternCategory) -> Finding {
+        Finding {
+            file: file.to_string(),
+            line,
+            column: 1,
+            severity: Severity::Medium,
+            category,
+            message: "Test message".to_string(),
+            match_text: "TODO".to_string(),
+            pattern_regex: "TODO".to_string(),
+            source_line: None,
+            context_before: None,
+            context_after: None,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_sqlite_round_trip() {
+        let results = vec![
+            make_finding("a.py", 1, PatternCategory::Placeholder),
+            make_finding("a.py", 4, PatternCategory::Hedging),
+            make_finding("b.py", 2, PatternCategory::Placeholder),
+        ];
+        let summary = ScanSummary::default();
+
+        let mut conn = Connection::open_in_memory().unwrap();
+        let first = insert_run(&mut conn, &results, &summary).unwrap();
+        let second = insert_run(&mut conn, &results[..1], &summary).unwrap();
+        assert_ne!(first, second);
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT f.file, COUNT(*) FROM findings f JOIN runs r ON f.run_id = r.id
+                 WHERE r.id = ?1 AND f.category = 'placeholder'
+                 GROUP BY f.file ORDER BY f.file",
+            )
+            .unwrap();
+        let rows: Vec<(String, i64)> = stmt
+            .query_map([first], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<std::result::Result<_, _>>()
+            .unwrap();
+        assert_eq!(rows, vec![("a.py".to_string(), 1), ("b.py".to_string(), 1)]);
+
+        let total: i64 = conn
+            .query_row("SELECT COUNT(*) FROM findings", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(total, 4);
+    }
+}
@@ -0,0 +1,248 @@
+//! Interactive triage of findings, one at a time.
+//!
+//! [`Review`] holds the navigation state and the decision made for each
+//! finding; the terminal UI in `tui` (behind the `review` feature) drives
+//! it from key presses. Acknowledged findings are meant for the baseline
+//! and suppressed ones for [`suppress_lines`], once [`can_suppress`] has
+//! checked that their line is safe to wrap.
+
+#[cfg(feature = "review")]
+mod tui;
+
+#[cfg(feature = "review")]
+pub use tui::run;
+
+use crate::detector::{Comment, Finding};
+
+/// What to do with a reviewed finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    /// Accept the finding by adding it to the baseline.
+    Acknowledge,
+    /// Silence the finding with an ignore comment in the source.
+    Suppress,
+    /// Leave the finding as it is.
+    Skip,
+}
+
+/// Findings under review, the position in them and the decisions so far.
+#[derive(Debug)]
+pub struct Review {
+    findings: Vec<Finding>,
+    decisions: Vec<Option<Decision>>,
+    position: usize,
+}
+
+impl Review {
+    /// Start reviewing `findings` from the first one.
+    pub fn new(findings: Vec<Finding>) -> Self {
+        let decisions = vec![None; findings.len()];
+        Self {
+            findings,
+            decisions,
+            position: 0,
+        }
+    }
+
+    /// The finding being reviewed, or `None` once every finding has had
+    /// a decision.
+    pub fn current(&self) -> Option<&Finding> {
+        self.findings.get(self.position)
+    }
+
+    /// Index of the current finding (equal to [`len`](Self::len) when done).
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Number of findings under review.
+    pub fn len(&self) -> usize {
+        self.findings.len()
+    }
+
+    /// Returns true if there is nothing to review.
+    pub fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// Returns true once every finding has been stepped through.
+    pub fn is_done(&self) -> bool {
+        self.position >= self.findings.len()
+    }
+
+    /// The decision recorded for the current finding, if any.
+    pub fn current_decision(&self) -> Option<Decision> {
+        self.decisions.get(self.position).copied().flatten()
+    }
+
+    /// Record `decision` for the current finding and move to the next one.
+    pub fn decide(&mut self, decision: Decision) {
+        if let Some(slot) = self.decisions.get_mut(self.position) {
+            *slot = Some(decision);
+            self.position += 1;
+        }
+    }
+
+    /// Go back to the previous finding to change its decision.
+    pub fn back(&mut self) {
+        self.position = self.position.saturating_sub(1);
+    }
+
+    /// Findings given `decision`, in review order.
+    pub fn decided(&self, decision: Decision) -> Vec<&Finding> {
+        self.findings
+            .iter()
+            .zip(&self.decisions)
+            .filter(|(_, d)| **d == Some(decision))
+            .map(|(finding, _)| finding)
+            .collect()
+    }
+}
+
+/// Whether `finding` lies in a `marker` line comment of `source`, so its
+/// line can be wrapped in directive comments without changing the code.
+///
+/// `comments` are the comments extracted from `source`. Findings outside
+/// comments, in block comments or docstrings, and on lines joined by a
+/// trailing backslash are not suppressible this way.
+pub fn can_suppress(finding: &Finding, source: &str, comments: &[Comment], marker: &str) -> bool {
+    let lines: Vec<&str> = source.lines().collect();
+    let Some(idx) = finding.line.checked_sub(1) else {
+        return false;
+    };
+    let Some(text) = lines.get(idx) else {
+        return false;
+    };
+    let continues = |i: usize| lines.get(i).is_some_and(|l| l.trim_end().ends_with('\\'));
+    if continues(idx) || idx > 0 && continues(idx - 1) {
+        return false;
+    }
+    comments.iter().any(|c| {
+        c.line == finding.line
+            && c.column <= finding.column
+            && text
+                .get(c.column.saturating_sub(1)..)
+                .is_some_and(|rest| rest.starts_with(marker))
+    })
+}
+
+/// Wrap each of the 1-indexed `lines` of `source` in `antislop:disable` /
+/// `antislop:enable` directives, written as `comment` line comments with
+/// the line's own indentation. Line endings are preserved.
+pub fn suppress_lines(source: &str, lines: &[usize], comment: &str) -> String {
+    let newline = if source.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut out = String::with_capacity(source.len());
+    for (idx, line) in source.split_inclusive('\n').enumerate() {
+        if !lines.contains(&(idx + 1)) {
+            out.push_str(line);
+            continue;
+        }
+        let indent = &line[..line.len() - line.trim_start().len()];
+        out.push_str(&format!("{indent}{comment} antislop:disable{newline}"));
+        out.push_str(line);
+        if !line.ends_with('\n') {
+            out.push_str(newline);
+        }
+        out.push_str(&format!("{indent}{comment} antislop:enable"));
+        if line.ends_with('\n') {
+            out.push_str(newline);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{PatternCategory, Severity};
+    use crate::detector::CommentKind;
+
+    fn make_finding(line: usize) -> Finding {
+        Finding {
+            file: "app.py".to_string(),
+            line,
+            column: 1,
+            severity: Severity::Medium,
+            category: PatternCategory::Placeholder,
+            message: "Test message".to_string(),
+            match_text: "TODO".to_string(),
+            pattern_regex: "TODO".to_string(),
+            source_line: None,
+            context_before: None,
+            context_after: None,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_review_navigation() {
+        let mut review = Review::new(vec![make_finding(1), make_finding(2), make_finding(3)]);
+        assert_eq!(review.current().map(|f| f.line), Some(1));
+
+        review.decide(Decision::Acknowledge);
+        review.decide(Decision::Skip);
+        // Going back shows the earlier decision and lets it be changed
+        review.back();
+        assert_eq!(review.current_decision(), Some(Decision::Skip));
+        review.decide(Decision::Suppress);
+        assert!(!review.is_done());
+        review.decide(Decision::Skip);
+
+        assert!(review.is_done());
+        assert!(review.current().is_none());
+        let lines = |d| review.decided(d).iter().map(|f| f.line).collect::<Vec<_>>();
+        assert_eq!(lines(Decision::Acknowledge), vec![1]);
+        assert_eq!(lines(Decision::Suppress), vec![2]);
+        assert_eq!(lines(Decision::Skip), vec![3]);
+
+        // Deciding past the end is a no-op
+        review.decide(Decision::Acknowledge);
+        assert_eq!(review.position(), review.len());
+    }
+
+    #[test]
+    fn test_can_suppress() {
+        let source = "x = 1  # TODO: later\ns = \"\"\"\n# TODO: in a string\n\"\"\"\ny = 2 + \\\n    3  # TODO: continued\n/* TODO */\n";
+        let comment = |line, column| Comment {
+            line,
+            column,
+            content: "TODO".to_string(),
+            prefix_len: 2,
+            kind: CommentKind::Line,
+        };
+        let comments = [comment(1, 8), comment(6, 8), comment(7, 1)];
+        let at = |line, column| Finding {
+            column,
+            ..make_finding(line)
+        };
+
+        assert!(can_suppress(&at(1, 10), source, &comments, "#"));
+        // Code before the comment on the same line
+        assert!(!can_suppress(&at(1, 1), source, &comments, "#"));
+        // Inside a string literal
+        assert!(!can_suppress(&at(3, 3), source, &comments, "#"));
+        // A line joined to the previous one
+        assert!(!can_suppress(&at(6, 10), source, &comments, "#"));
+        // Not a line comment
+        assert!(!can_suppress(&at(7, 4), source, &comments, "//"));
+        // Filename findings have no line
+        assert!(!can_suppress(&at(0, 1), source, &comments, "#"));
+    }
+
+    #[test]
+    fn test_suppress_lines() {
+        let source = "def f():\n    # TODO: later\n    pass\n# XXX";
+        assert_eq!(
+            suppress_lines(source, &[2, 4], "#"),
+            "def f():\n    # antislop:disable\n    # TODO: later\n    # antislop:enable\n    pass\n# antislop:disable\n# XXX\n# antislop:enable"
+        );
+        assert_eq!(
+            suppress_lines("a\r\nb\r\n", &[1], "//"),
+            "// antislop:disable\r\na\r\n// antislop:enable\r\nb\r\n"
+        );
+    }
+}
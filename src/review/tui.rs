@@ -0,0 +1,117 @@
+//! Full-screen terminal UI driving a [`Review`].
+
+use super::{Decision, Review};
+use crate::config::Severity;
+use crate::detector::Finding;
+use crate::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use std::io;
+
+const KEYS: &str = "[a] acknowledge  [s] suppress  [n] skip  [b] back  [q] quit";
+
+/// Step through `findings` one at a time and return the decisions made.
+///
+/// Quitting early leaves the remaining findings undecided. The terminal is
+/// restored before returning, also on error.
+pub fn run(findings: Vec<Finding>) -> Result<Review> {
+    let mut review = Review::new(findings);
+    let mut terminal = ratatui::init();
+    let outcome = event_loop(&mut terminal, &mut review);
+    ratatui::restore();
+    outcome?;
+    Ok(review)
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, review: &mut Review) -> io::Result<()> {
+    while !review.is_done() {
+        terminal.draw(|frame| draw(frame, review))?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('a') => review.decide(Decision::Acknowledge),
+            KeyCode::Char('s') => review.decide(Decision::Suppress),
+            KeyCode::Char('n') | KeyCode::Char(' ') | KeyCode::Right => {
+                review.decide(Decision::Skip)
+            }
+            KeyCode::Char('b') | KeyCode::Left | KeyCode::Backspace => review.back(),
+            KeyCode::Char('q') | KeyCode::Esc => break,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn draw(frame: &mut Frame, review: &Review) {
+    let Some(finding) = review.current() else {
+        return;
+    };
+    let [header, source, footer] = Layout::vertical([
+        Constraint::Length(4),
+        Constraint::Min(5),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let title = format!(
+        " antislop review {}/{} ",
+        review.position() + 1,
+        review.len()
+    );
+    let details = vec![
+        Line::from(format!("{}:{}:{}", finding.file, finding.line, finding.column).bold()),
+        Line::from(vec![
+            Span::styled(
+                finding.severity.to_string().to_uppercase(),
+                Style::new().fg(severity_color(&finding.severity)).bold(),
+            ),
+            Span::raw(format!(" {}  {}", finding.category, finding.message)),
+        ]),
+    ];
+    frame.render_widget(
+        Paragraph::new(details).block(Block::bordered().title(title)),
+        header,
+    );
+
+    // Tabs would render at the terminal's own stops and shift the text
+    let untab = |line: &str| line.replace('\t', "    ");
+    let mut context = Vec::new();
+    if let Some(before) = &finding.context_before {
+        context.push(Line::from(format!("  {}", untab(before))).dim());
+    }
+    let line = finding
+        .source_line
+        .as_deref()
+        .unwrap_or(&finding.match_text);
+    context.push(Line::from(format!("> {}", untab(line))).bold());
+    if let Some(after) = &finding.context_after {
+        context.push(Line::from(format!("  {}", untab(after))).dim());
+    }
+    frame.render_widget(
+        Paragraph::new(context).block(Block::bordered().title(format!(" {} ", finding.match_text))),
+        source,
+    );
+
+    let status = match review.current_decision() {
+        Some(decision) => format!("{}   (currently: {:?})", KEYS, decision),
+        None => KEYS.to_string(),
+    };
+    frame.render_widget(Paragraph::new(status).dim(), footer);
+}
+
+fn severity_color(severity: &Severity) -> Color {
+    match severity {
+        Severity::Critical => Color::Red,
+        Severity::High => Color::LightRed,
+        Severity::Medium => Color::Yellow,
+        Severity::Low => Color::Blue,
+    }
+}
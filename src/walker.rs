@@ -1,8 +1,14 @@
 //! Parallel file traversal with gitignore support.
 
-use crate::Config;
+use crate::{Config, Result};
 use ignore::WalkBuilder;
+use std::fs::Metadata;
+use std::io::{self, BufRead};
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Bytes searched for a NUL when deciding whether a file is binary.
+const BINARY_SNIFF_LEN: usize = 8 * 1024;
 
 /// A file entry from walking the directory tree.
 #[derive(Debug, Clone)]
@@ -11,6 +17,38 @@ pub struct FileEntry {
     pub path: PathBuf,
     /// File extension with leading dot.
     pub extension: Option<String>,
+    /// File size in bytes, if metadata collection is enabled.
+    pub size: Option<u64>,
+    /// Last modification time, if metadata collection is enabled.
+    pub modified: Option<SystemTime>,
+}
+
+impl FileEntry {
+    fn new(path: PathBuf, metadata: Option<Metadata>) -> Self {
+        let extension = Walker::get_extension(&path);
+        Self {
+            path,
+            extension,
+            size: metadata.as_ref().map(|m| m.len()),
+            modified: metadata.and_then(|m| m.modified().ok()),
+        }
+    }
+
+    /// Read the file as UTF-8 text, or `None` if it looks binary (a NUL
+    /// byte in its first 8 KB) despite its extension.
+    pub fn read_text(&self) -> io::Result<Option<String>> {
+        decode_text(std::fs::read(&self.path)?)
+    }
+}
+
+/// Decode file bytes as UTF-8 text, or `None` if they look binary.
+pub(crate) fn decode_text(bytes: Vec<u8>) -> io::Result<Option<String>> {
+    if bytes[..bytes.len().min(BINARY_SNIFF_LEN)].contains(&0) {
+        return Ok(None);
+    }
+    String::from_utf8(bytes)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
 }
 
 /// Parallel file walker.
@@ -19,6 +57,12 @@ pub struct Walker {
     extensions: Vec<String>,
     /// Maximum file size in bytes.
     max_file_size: u64,
+    /// Maximum directory depth, or `None` for unlimited.
+    max_depth: Option<usize>,
+    /// Populate `size` and `modified` on each entry.
+    collect_metadata: bool,
+    /// Skip files last modified before this time.
+    modified_since: Option<SystemTime>,
 }
 
 impl Walker {
@@ -27,9 +71,26 @@ impl Walker {
         Self {
             extensions: config.file_extensions.clone(),
             max_file_size: config.max_file_size_kb * 1024,
+            max_depth: config.max_depth,
+            collect_metadata: false,
+            modified_since: None,
         }
     }
 
+    /// Populate file size and modification time on returned entries.
+    pub fn with_metadata(mut self, enabled: bool) -> Self {
+        self.collect_metadata = enabled;
+        self
+    }
+
+    /// Only return files modified at or after `cutoff`. Files whose
+    /// modification time cannot be read are skipped. Filtering reads
+    /// metadata, so entries also carry `size` and `modified`.
+    pub fn with_modified_since(mut self, cutoff: Option<SystemTime>) -> Self {
+        self.modified_since = cutoff;
+        self
+    }
+
     /// Walk a directory and return matching files.
     pub fn walk(&self, paths: &[PathBuf]) -> Vec<FileEntry> {
         let mut entries = Vec::new();
@@ -41,10 +102,14 @@ impl Walker {
 
             if base.is_file() {
                 if self.matches_extension(base) {
-                    entries.push(FileEntry {
-                        path: base.clone(),
-                        extension: Self::get_extension(base),
-                    });
+                    let metadata = if self.needs_metadata() {
+                        base.metadata().ok()
+                    } else {
+                        None
+                    };
+                    if self.is_recent(metadata.as_ref()) {
+                        entries.push(FileEntry::new(base.clone(), metadata));
+                    }
                 }
                 continue;
             }
@@ -55,6 +120,7 @@ impl Walker {
                 .git_exclude(true)
                 .hidden(false)
                 .max_filesize(Some(self.max_file_size))
+                .max_depth(self.max_depth)
                 .build()
                 .filter_map(|e| e.ok())
             {
@@ -65,10 +131,15 @@ impl Walker {
                 }
 
                 if self.matches_extension(path) {
-                    entries.push(FileEntry {
-                        path: path.to_path_buf(),
-                        extension: Self::get_extension(path),
-                    });
+                    // `DirEntry::metadata` reuses what the walker already read where it can
+                    let metadata = if self.needs_metadata() {
+                        entry.metadata().ok()
+                    } else {
+                        None
+                    };
+                    if self.is_recent(metadata.as_ref()) {
+                        entries.push(FileEntry::new(path.to_path_buf(), metadata));
+                    }
                 }
             }
         }
@@ -76,6 +147,61 @@ impl Walker {
         entries
     }
 
+    /// Read newline-delimited file paths and return the matching files,
+    /// without any directory traversal.
+    ///
+    /// Only the line ending (`\n` or `\r\n`) is stripped, so paths may start
+    /// or end with spaces. Empty lines are ignored. Paths that do not exist
+    /// or are not regular files (e.g. deleted files in `git diff --name-only`
+    /// output) are skipped with a warning.
+    pub fn walk_list<R: BufRead>(&self, list: R) -> Result<Vec<FileEntry>> {
+        let mut entries = Vec::new();
+
+        for line in list.lines() {
+            let line = line?;
+            // `lines` leaves the `\r` of a final CRLF line without its `\n`
+            let line = line.strip_suffix('\r').unwrap_or(&line);
+            if line.is_empty() {
+                continue;
+            }
+
+            let path = PathBuf::from(line);
+            let Ok(metadata) = path.metadata() else {
+                tracing::warn!("Skipping '{}': file not found", path.display());
+                continue;
+            };
+            if !metadata.is_file() {
+                tracing::warn!("Skipping '{}': not a file", path.display());
+                continue;
+            }
+            if metadata.len() > self.max_file_size
+                || !self.matches_extension(&path)
+                || !self.is_recent(Some(&metadata))
+            {
+                continue;
+            }
+
+            let metadata = self.needs_metadata().then_some(metadata);
+            entries.push(FileEntry::new(path, metadata));
+        }
+
+        Ok(entries)
+    }
+
+    fn needs_metadata(&self) -> bool {
+        self.collect_metadata || self.modified_since.is_some()
+    }
+
+    /// Whether a file passes the `modified_since` cutoff.
+    fn is_recent(&self, metadata: Option<&Metadata>) -> bool {
+        let Some(cutoff) = self.modified_since else {
+            return true;
+        };
+        metadata
+            .and_then(|m| m.modified().ok())
+            .is_some_and(|modified| modified >= cutoff)
+    }
+
     /// Check if a path matches the configured extensions.
     fn matches_extension(&self, path: &Path) -> bool {
         if self.extensions.contains(&"*".to_string()) {
@@ -102,6 +228,7 @@ mod tests {
     use super::*;
     use std::fs::File;
     use std::io::Write;
+    use std::time::Duration;
     use tempfile::TempDir;
 
     #[test]
@@ -134,6 +261,38 @@ mod tests {
         assert_eq!(files[0].extension.as_deref(), Some(".rs"));
     }
 
+    #[test]
+    fn test_walker_respects_max_depth() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        std::fs::create_dir_all(dir.join("a/b")).unwrap();
+        for rel in ["top.rs", "a/mid.rs", "a/b/deep.rs"] {
+            File::create(dir.join(rel))
+                .unwrap()
+                .write_all(b"fn main() {}")
+                .unwrap();
+        }
+
+        let names = |max_depth| {
+            let config = Config {
+                file_extensions: vec![".rs".to_string()],
+                max_depth,
+                ..Default::default()
+            };
+            let mut names: Vec<String> = Walker::new(&config)
+                .walk(&[dir.to_path_buf()])
+                .into_iter()
+                .map(|e| e.path.file_name().unwrap().to_string_lossy().into_owned())
+                .collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(names(None), vec!["deep.rs", "mid.rs", "top.rs"]);
+        assert_eq!(names(Some(2)), vec!["mid.rs", "top.rs"]);
+        assert_eq!(names(Some(1)), vec!["top.rs"]);
+    }
+
     #[test]
     fn test_single_file() {
         let temp = TempDir::new().unwrap();
@@ -149,5 +308,78 @@ mod tests {
 
         assert_eq!(files.len(), 1);
         assert_eq!(files[0].path, file);
+        assert!(files[0].size.is_none());
+    }
+
+    #[test]
+    fn test_walk_list_scans_only_listed_files() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        for name in ["a.rs", "b.rs", "c.rs", " d.rs"] {
+            File::create(dir.join(name))
+                .unwrap()
+                .write_all(b"fn main() {}")
+                .unwrap();
+        }
+
+        let list = format!(
+            "{}\n\n{}\r\n{}\n{}\n{}\r",
+            dir.join("a.rs").display(),
+            dir.join("c.rs").display(),
+            dir.join("deleted.rs").display(),
+            dir.display(),
+            dir.join(" d.rs").display(),
+        );
+
+        let config = Config::default();
+        let walker = Walker::new(&config);
+        let files = walker.walk_list(list.as_bytes()).unwrap();
+
+        let paths: Vec<_> = files.iter().map(|f| f.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![dir.join("a.rs"), dir.join("c.rs"), dir.join(" d.rs")]
+        );
+    }
+
+    #[test]
+    fn test_walker_collects_metadata() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+
+        File::create(dir.join("test.rs"))
+            .unwrap()
+            .write_all(b"fn main() {}")
+            .unwrap();
+
+        let config = Config::default();
+        let walker = Walker::new(&config).with_metadata(true);
+        let files = walker.walk(&[dir.to_path_buf()]);
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].size, Some(12));
+        assert!(files[0].modified.is_some());
+    }
+
+    #[test]
+    fn test_walker_modified_since() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+
+        let old = File::create(dir.join("old.rs")).unwrap();
+        old.set_modified(SystemTime::now() - Duration::from_secs(2 * 24 * 60 * 60))
+            .unwrap();
+        File::create(dir.join("new.rs")).unwrap();
+
+        let config = Config::default();
+        let walker = Walker::new(&config)
+            .with_modified_since(Some(SystemTime::now() - Duration::from_secs(60 * 60)));
+
+        let files = walker.walk(&[dir.to_path_buf()]);
+        let paths: Vec<_> = files.iter().map(|f| f.path.clone()).collect();
+        assert_eq!(paths, vec![dir.join("new.rs")]);
+
+        let list = format!("{}\n", dir.join("old.rs").display());
+        assert!(walker.walk_list(list.as_bytes()).unwrap().is_empty());
     }
 }
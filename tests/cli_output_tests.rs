@@ -46,6 +46,7 @@ fn test_list_languages_includes_all_supported() {
     assert!(text.contains("PHP"), "Should list PHP");
     assert!(text.contains("Kotlin"), "Should list Kotlin");
     assert!(text.contains("Swift"), "Should list Swift");
+    assert!(text.contains("Dart"), "Should list Dart");
     assert!(text.contains("Shell"), "Should list Shell");
     assert!(text.contains("JSX"), "Should list JSX");
     assert!(text.contains("TSX"), "Should list TSX");
@@ -2,6 +2,7 @@
 
 use std::fs;
 use std::process::Command;
+use std::time::{Duration, SystemTime};
 use tempfile::TempDir;
 
 /// Get the path to the antislop binary.
@@ -163,8 +164,11 @@ fn test_sarif_output_flag() {
     // Validate SARIF structure by parsing as JSON
     let json: serde_json::Value =
         serde_json::from_str(&text).expect("SARIF output should be valid JSON");
-    // Check SARIF version
+    // Check SARIF version and schema
     assert_eq!(json["version"], "2.1.0");
+    assert!(json["$schema"]
+        .as_str()
+        .is_some_and(|s| s.contains("sarif")));
     // Check runs array exists and has at least one run
     assert!(json["runs"].as_array().is_some_and(|r| !r.is_empty()));
     // Check that results exist
@@ -172,6 +176,515 @@ fn test_sarif_output_flag() {
     assert!(run["results"].as_array().is_some_and(|r| !r.is_empty()));
 }
 
+#[test]
+fn test_severity_map_remaps_sarif_level() {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("main.py"), "# deal with this later\n").unwrap();
+
+    let run = |format: &str, extra: &[&str]| {
+        let output = Command::new(antislop_bin())
+            .current_dir(temp.path())
+            .args(["--no-default-patterns", "--format", format])
+            .args(["--pattern", "later:high:deferral:Deferred work"])
+            .args(extra)
+            .arg("main.py")
+            .output()
+            .unwrap();
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()
+    };
+
+    let level = |json: serde_json::Value| json["runs"][0]["results"][0]["level"].clone();
+    assert_eq!(level(run("sarif", &[])), "error");
+    assert_eq!(
+        level(run("sarif", &["--severity-map", "deferral=note"])),
+        "note"
+    );
+
+    // Only the reported level changes, not the finding's severity or score
+    let json = run("json", &["--severity-map", "deferral=note"]);
+    assert_eq!(json["findings"][0]["severity"], "high");
+    assert_eq!(json["summary"]["total_score"], 15);
+}
+
+#[test]
+fn test_severity_map_applies_to_filename_findings() {
+    let temp = TempDir::new().unwrap();
+    for name in [
+        "user_service",
+        "data_store",
+        "http_client",
+        "file_utils",
+        "config_loader",
+        "dataLoader",
+    ] {
+        fs::write(temp.path().join(format!("{}.py", name)), "x = 1\n").unwrap();
+    }
+
+    let levels = |extra: &[&str]| {
+        let output = Command::new(antislop_bin())
+            .current_dir(temp.path())
+            .args(["--format", "sarif"])
+            .args(extra)
+            .arg(".")
+            .output()
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["runs"][0]["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|r| r["ruleId"] == "namingconvention")
+            .map(|r| r["level"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    assert!(!levels(&[]).is_empty());
+    assert!(levels(&[]).iter().all(|level| level != "note"));
+    let remapped = levels(&["--severity-map", "namingconvention=note"]);
+    assert!(!remapped.is_empty());
+    assert!(remapped.iter().all(|level| level == "note"));
+}
+
+#[test]
+fn test_disabled_pattern_produces_no_findings() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("antislop.toml"),
+        r#"
+[[patterns]]
+regex = "ZORP"
+category = "placeholder"
+
+[[patterns]]
+regex = "BLIP"
+category = "placeholder"
+enabled = false
+
+[[patterns]]
+regex = "QUUX"
+category = "deferral"
+"#,
+    )
+    .unwrap();
+    fs::write(
+        temp.path().join("main.py"),
+        "# ZORP a\n# BLIP b\n# QUUX c\n",
+    )
+    .unwrap();
+
+    let matched = |extra: &[&str]| -> Vec<String> {
+        let output = Command::new(antislop_bin())
+            .current_dir(temp.path())
+            .args(["--no-filename-check", "--json"])
+            .args(extra)
+            .arg("main.py")
+            .output()
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["findings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["match_text"].as_str().unwrap().to_string())
+            .filter(|m| ["ZORP", "BLIP", "QUUX"].contains(&m.as_str()))
+            .collect()
+    };
+
+    assert_eq!(matched(&[]), vec!["ZORP", "QUUX"]);
+    assert_eq!(matched(&["--disable-pattern", "ZORP"]), vec!["QUUX"]);
+    assert_eq!(
+        matched(&["--enable-pattern", "BLIP"]),
+        vec!["ZORP", "BLIP", "QUUX"]
+    );
+
+    // Disabled patterns are left out of the dump, with sources kept aligned
+    let output = Command::new(antislop_bin())
+        .current_dir(temp.path())
+        .args(["--dump-patterns", "--json", "--disable-pattern", "ZORP"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let dumped: Vec<(&str, &str)> = json["patterns"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| (p["regex"].as_str().unwrap(), p["source"].as_str().unwrap()))
+        .filter(|(regex, _)| ["ZORP", "BLIP", "QUUX"].contains(regex))
+        .collect();
+    assert_eq!(dumped.len(), 1, "{:?}", dumped);
+    assert_eq!(dumped[0].0, "QUUX");
+    assert!(dumped[0].1.starts_with("config"), "{:?}", dumped);
+}
+
+#[test]
+fn test_filename_and_content_findings_share_stable_order() {
+    let temp = TempDir::new().unwrap();
+    for name in [
+        "user_service",
+        "data_store",
+        "http_client",
+        "file_utils",
+        "config_loader",
+    ] {
+        fs::write(temp.path().join(format!("{}.py", name)), "x = 1\n").unwrap();
+    }
+    fs::write(
+        temp.path().join("dataLoader.py"),
+        "# TODO: fix this\nx = 1\n",
+    )
+    .unwrap();
+
+    let run = || {
+        let output = Command::new(antislop_bin())
+            .current_dir(temp.path())
+            .args(["--json", "."])
+            .output()
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["findings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| {
+                (
+                    f["file"].as_str().unwrap().to_string(),
+                    f["line"].as_u64().unwrap(),
+                    f["category"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let findings = run();
+    let categories: Vec<&str> = findings.iter().map(|(_, _, c)| c.as_str()).collect();
+    assert!(findings
+        .iter()
+        .all(|(file, line, _)| file.ends_with("dataLoader.py") && *line == 1));
+    // Same line, so column decides: the filename issue at column 1 comes
+    // before the content TODO
+    assert_eq!(categories.first(), Some(&"namingconvention"));
+    assert_eq!(categories.last(), Some(&"placeholder"));
+    assert_eq!(run(), findings);
+
+    // Filename findings of streamed files do not count the files twice
+    let output = Command::new(antislop_bin())
+        .current_dir(temp.path())
+        .args(["--format", "sarif", "--parseable-summary", "."])
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("files=6 "), "{}", stderr);
+}
+
+#[test]
+fn test_list_findings_files() {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("dirty.py"), "# TODO: fix\n# TODO: again\n").unwrap();
+    fs::write(temp.path().join("clean.py"), "x = 1\n").unwrap();
+    fs::write(temp.path().join("other.rs"), "// FIXME: later\n").unwrap();
+
+    let output = Command::new(antislop_bin())
+        .current_dir(temp.path())
+        .args(["--list-findings-files", "--no-filename-check", "."])
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let files: Vec<&str> = stdout.lines().collect();
+    assert_eq!(files, vec!["./dirty.py", "./other.rs"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_generated_files_skipped() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("schema.py"),
+        "# @generated by schemagen\n# TODO: fix\n",
+    )
+    .unwrap();
+    fs::write(temp.path().join("app.py"), "# TODO: fix\n").unwrap();
+
+    let output = Command::new(antislop_bin())
+        .current_dir(temp.path())
+        .args(["--list-findings-files", "--no-filename-check", "-v", "."])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().collect::<Vec<_>>(), vec!["./app.py"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Skipping generated file (generated marker): ./schema.py"));
+
+    let output = Command::new(antislop_bin())
+        .current_dir(temp.path())
+        .args([
+            "--list-findings-files",
+            "--no-filename-check",
+            "--include-generated",
+            ".",
+        ])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 2);
+}
+
+#[test]
+fn test_fail_fast_stops_at_first_qualifying_finding() {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("a.py"), "# TODO: minor\n").unwrap();
+    fs::write(temp.path().join("b.py"), "x = 1\n# BOOM: first\n").unwrap();
+    fs::write(temp.path().join("c.py"), "# BOOM: second\n").unwrap();
+    fs::write(temp.path().join("files.txt"), "a.py\nb.py\nc.py\n").unwrap();
+
+    let output = Command::new(antislop_bin())
+        .current_dir(temp.path())
+        .args([
+            "--pattern",
+            "BOOM:critical:stub:Explosive stub",
+            "--files-from",
+            "files.txt",
+            "--json",
+            "-vv",
+            "--fail-fast=critical",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let findings = json["findings"].as_array().unwrap();
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0]["file"], "b.py");
+    assert_eq!(findings[0]["severity"], "critical");
+
+    // c.py is never scanned
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Scanning: b.py"));
+    assert!(!stderr.contains("c.py"));
+
+    // Without `=`, the next argument is a path rather than a level
+    let output = Command::new(antislop_bin())
+        .current_dir(temp.path())
+        .args(["--no-filename-check", "--json", "--fail-fast", "a.py"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["findings"][0]["file"], "a.py");
+}
+
+#[test]
+fn test_binary_files_skipped() {
+    let temp = TempDir::new().unwrap();
+    // A compiled artifact that happens to carry a source extension
+    let mut blob = b"\x7fELF\x02\x01\x01\x00\x00".to_vec();
+    blob.extend_from_slice(&[0xff, 0xfe, 0x00, 0x80]);
+    blob.extend_from_slice(b"TODO: not a comment");
+    fs::write(temp.path().join("libfoo.h"), blob).unwrap();
+    fs::write(temp.path().join("app.py"), "x = 1\n").unwrap();
+
+    let output = Command::new(antislop_bin())
+        .current_dir(temp.path())
+        .args(["--no-filename-check", "-v", "."])
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Skipping binary file: ./libfoo.h"));
+    assert!(!stderr.contains("Error reading file"));
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_show_pattern_in_json() {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("app.py"), "# BOOM: here\n").unwrap();
+
+    let output = Command::new(antislop_bin())
+        .current_dir(temp.path())
+        .args([
+            "--pattern",
+            "BOOM:high:stub:Explosive stub",
+            "--json",
+            "--show-pattern",
+            "app.py",
+        ])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let finding = json["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|f| f["message"] == "Explosive stub")
+        .unwrap();
+    assert_eq!(finding["pattern_regex"], "BOOM");
+}
+
+#[test]
+fn test_weight_overrides_severity_score() {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("app.py"), "# BOOM: here\n").unwrap();
+
+    let total_score = |extra: &[&str]| {
+        let output = Command::new(antislop_bin())
+            .current_dir(temp.path())
+            .args(["--pattern", "BOOM:critical:stub:Explosive stub", "--json"])
+            .args(extra)
+            .arg("app.py")
+            .output()
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["summary"]["total_score"].as_u64().unwrap()
+    };
+
+    let default = total_score(&[]);
+    let weighted = total_score(&["--weight", "critical=100,high=20"]);
+    assert_eq!(weighted, default + 50);
+}
+
+#[test]
+fn test_since_skips_old_files() {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("old.py"), "# TODO: old\n").unwrap();
+    fs::write(temp.path().join("new.py"), "# TODO: new\n").unwrap();
+    fs::File::options()
+        .write(true)
+        .open(temp.path().join("old.py"))
+        .unwrap()
+        .set_modified(SystemTime::now() - Duration::from_secs(2 * 24 * 60 * 60))
+        .unwrap();
+
+    let output = Command::new(antislop_bin())
+        .current_dir(temp.path())
+        .args(["--since", "1h", "--json", "."])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let files: Vec<&str> = json["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["file"].as_str().unwrap())
+        .collect();
+    assert!(files.iter().any(|f| f.ends_with("new.py")));
+    assert!(!files.iter().any(|f| f.ends_with("old.py")));
+}
+
+#[test]
+fn test_since_rejects_huge_durations() {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("a.py"), "x = 1\n").unwrap();
+
+    let run = |since: &str| {
+        Command::new(antislop_bin())
+            .current_dir(temp.path())
+            .args(["--since", since, "a.py"])
+            .output()
+            .unwrap()
+    };
+
+    assert!(run("3000w").status.success());
+    // Overflows the duration, then the system clock
+    for since in ["99999999999999999w", "20000000000000w"] {
+        let output = run(since);
+        assert!(!output.status.success(), "{}", since);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("--since"), "{}", stderr);
+        assert!(!stderr.contains("panicked"), "{}", stderr);
+    }
+}
+
+#[test]
+fn test_repeated_docstrings_reported() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("antislop.toml"),
+        "repeated_docstrings = 3\n",
+    )
+    .unwrap();
+    fs::write(
+        temp.path().join("a.py"),
+        "def f():\n    \"\"\"Returns the result.\"\"\"\n\ndef g():\n    \"\"\"Returns the result.\"\"\"\n",
+    )
+    .unwrap();
+    fs::write(
+        temp.path().join("b.py"),
+        "def h():\n    \"\"\"Returns the result.\"\"\"\n\ndef k():\n    \"\"\"Sums the prices.\"\"\"\n",
+    )
+    .unwrap();
+
+    let output = Command::new(antislop_bin())
+        .current_dir(temp.path())
+        .args(["--format", "json", "--no-filename-check", "."])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let boilerplate: Vec<(String, u64)> = json["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|f| f["category"] == "boilerplate")
+        .map(|f| {
+            (
+                f["file"].as_str().unwrap().to_string(),
+                f["line"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        boilerplate,
+        vec![
+            ("./a.py".to_string(), 2),
+            ("./a.py".to_string(), 5),
+            ("./b.py".to_string(), 2)
+        ]
+    );
+}
+
+#[test]
+fn test_incremental_categories_cache() {
+    let temp = TempDir::new().unwrap();
+    let config = |deferral: &str| {
+        format!(
+            "incremental_categories = \".antislop-cache.json\"\n\n\
+             [[patterns]]\nregex = \"ZORP\"\ncategory = \"placeholder\"\n\n\
+             [[patterns]]\nregex = \"{}\"\ncategory = \"deferral\"\n",
+            deferral
+        )
+    };
+    fs::write(temp.path().join("antislop.toml"), config("QUUX")).unwrap();
+    fs::write(
+        temp.path().join("main.py"),
+        "# ZORP a\n# QUUX b\n# BLIP c\n",
+    )
+    .unwrap();
+
+    let matched = || -> Vec<String> {
+        let output = Command::new(antislop_bin())
+            .current_dir(temp.path())
+            .args(["--no-filename-check", "--json", "main.py"])
+            .output()
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["findings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["match_text"].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(matched(), vec!["ZORP", "QUUX"]);
+    assert!(temp.path().join(".antislop-cache.json").exists());
+    assert_eq!(matched(), vec!["ZORP", "QUUX"]);
+
+    // A changed pattern and changed content are both picked up
+    fs::write(temp.path().join("antislop.toml"), config("BLIP")).unwrap();
+    assert_eq!(matched(), vec!["ZORP", "BLIP"]);
+    fs::write(temp.path().join("main.py"), "# BLIP c\n").unwrap();
+    assert_eq!(matched(), vec!["BLIP"]);
+}
+
 // Tests for mock/fake/dummy patterns
 #[test]
 fn test_mock_umap_detection() {
@@ -616,3 +1129,511 @@ fn test_filename_check_can_be_disabled() {
         text
     );
 }
+
+#[test]
+fn test_multiple_profiles_combine() {
+    let temp = TempDir::new().unwrap();
+    let dir = temp.path();
+
+    let company = dir.join("company.toml");
+    fs::write(
+        &company,
+        r#"
+[metadata]
+name = "company"
+version = "1.0.0"
+
+[[patterns]]
+regex = "(?i)alpha-marker"
+severity = "low"
+message = "Company marker"
+category = "deferral"
+"#,
+    )
+    .unwrap();
+
+    let python = dir.join("python.toml");
+    fs::write(
+        &python,
+        r#"
+[metadata]
+name = "python"
+version = "1.0.0"
+
+[[patterns]]
+regex = "(?i)beta-marker"
+severity = "high"
+message = "Python marker"
+category = "hedging"
+"#,
+    )
+    .unwrap();
+
+    let file = dir.join("code.py");
+    fs::write(&file, "# alpha-marker\nx = 1\n# beta-marker\n").unwrap();
+
+    let output = Command::new(antislop_bin())
+        .arg("--no-filename-check")
+        .arg("-v")
+        .arg("--format")
+        .arg("json")
+        .arg("--profile")
+        .arg(&company)
+        .arg("--profile")
+        .arg(&python)
+        .arg(&file)
+        .output()
+        .unwrap();
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(text.contains("Company marker"), "got: {}", text);
+    assert!(text.contains("Python marker"), "got: {}", text);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Loaded profile: company"),
+        "got: {}",
+        stderr
+    );
+    assert!(stderr.contains("Loaded profile: python"), "got: {}", stderr);
+}
+
+#[test]
+fn test_exit_zero_reports_but_succeeds() {
+    let temp = TempDir::new().unwrap();
+    let file = temp.path().join("code.py");
+    fs::write(&file, "# TODO: implement this\nx = 1\n").unwrap();
+
+    let output = Command::new(antislop_bin())
+        .arg("--no-filename-check")
+        .arg("--exit-zero")
+        .arg("--profile")
+        .arg("antislop-standard")
+        .arg(&file)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "--exit-zero should always exit 0");
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        text.contains("TODO"),
+        "findings should still be reported: {}",
+        text
+    );
+}
+
+#[test]
+fn test_inline_pattern_flag() {
+    let temp = TempDir::new().unwrap();
+    let file = temp.path().join("code.py");
+    fs::write(&file, "# wibble: remove before release\nx = 1\n").unwrap();
+
+    let output = Command::new(antislop_bin())
+        .arg("--no-filename-check")
+        .arg("--format")
+        .arg("json")
+        .arg("--pattern")
+        .arg("(?i)wibble::high:deferral:Inline: wibble marker")
+        .arg(&file)
+        .output()
+        .unwrap();
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(text.contains("Inline: wibble marker"), "got: {}", text);
+    assert!(text.contains("\"severity\": \"high\""), "got: {}", text);
+}
+
+#[test]
+fn test_inline_pattern_flag_rejects_malformed_spec() {
+    let output = Command::new(antislop_bin())
+        .arg("--pattern")
+        .arg("TODO:urgent:placeholder:Missing severity")
+        .arg(".")
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Invalid --pattern"), "got: {}", stderr);
+    assert!(stderr.contains("mergeconflict"), "got: {}", stderr);
+}
+
+#[test]
+fn test_no_summary_keeps_exit_code() {
+    let temp = TempDir::new().unwrap();
+    let file = temp.path().join("code.py");
+    fs::write(&file, "# TODO: implement this\nx = 1\n").unwrap();
+
+    let output = Command::new(antislop_bin())
+        .arg("--no-filename-check")
+        .arg("--no-summary")
+        .arg("--format")
+        .arg("json")
+        .arg("--profile")
+        .arg("antislop-standard")
+        .arg(&file)
+        .output()
+        .unwrap();
+
+    assert!(
+        !output.status.success(),
+        "findings should still fail the run"
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json.get("summary").is_none());
+    assert!(!json["findings"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_no_default_patterns_without_profile_finds_nothing() {
+    let temp = TempDir::new().unwrap();
+    let file = temp.path().join("code.py");
+    fs::write(
+        &file,
+        "# TODO: implement this\n# hopefully this works\nx = 1\n",
+    )
+    .unwrap();
+
+    // Run from the temp dir so no project config replaces the defaults
+    let baseline = Command::new(antislop_bin())
+        .current_dir(temp.path())
+        .arg("--no-filename-check")
+        .arg(&file)
+        .output()
+        .unwrap();
+    assert!(!baseline.status.success(), "defaults should flag the TODO");
+
+    let output = Command::new(antislop_bin())
+        .current_dir(temp.path())
+        .arg("--no-default-patterns")
+        .arg("--format")
+        .arg("json")
+        .arg(&file)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["summary"]["total_findings"], 0);
+    assert!(json["findings"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_files_from_stdin_scans_only_listed_files() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let temp = TempDir::new().unwrap();
+    let listed = temp.path().join("listed.py");
+    let unlisted = temp.path().join("unlisted.py");
+    fs::write(&listed, "# TODO: implement this\n").unwrap();
+    fs::write(&unlisted, "# TODO: implement this too\n").unwrap();
+
+    let mut child = Command::new(antislop_bin())
+        .current_dir(temp.path())
+        .args([
+            "--files-from",
+            "-",
+            "--format",
+            "json",
+            "--no-filename-check",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(format!("{}\n", listed.display()).as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["summary"]["files_scanned"], 1);
+    let findings = json["findings"].as_array().unwrap();
+    assert!(!findings.is_empty());
+    assert!(findings
+        .iter()
+        .all(|f| std::path::Path::new(f["file"].as_str().unwrap()).ends_with("listed.py")));
+}
+
+#[test]
+fn test_parseable_summary_on_stderr() {
+    let temp = TempDir::new().unwrap();
+    let file = temp.path().join("code.py");
+    fs::write(&file, "# TODO: implement this\nx = 1\n").unwrap();
+
+    let output = Command::new(antislop_bin())
+        .current_dir(temp.path())
+        .args([
+            "--parseable-summary",
+            "--format",
+            "json",
+            "--no-filename-check",
+        ])
+        .arg(&file)
+        .output()
+        .unwrap();
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let line = stderr
+        .lines()
+        .find(|l| l.starts_with("antislop: "))
+        .expect("parseable summary line");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let summary = &json["summary"];
+    assert!(line.contains("files=1 "));
+    assert!(line.contains(&format!("findings={} ", summary["total_findings"])));
+    assert!(line.contains(&format!("score={} ", summary["total_score"])));
+    for key in ["critical=", "high=", "medium=", "low="] {
+        assert!(line.contains(key), "missing {key} in {line}");
+    }
+}
+
+#[test]
+fn test_pattern_stats_in_json() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("antislop.toml"),
+        r#"
+[[patterns]]
+regex = "(?i)TODO:"
+severity = "medium"
+message = "TODO"
+category = "placeholder"
+
+[[patterns]]
+regex = "(?i)hopefully"
+severity = "low"
+message = "Hedging"
+category = "hedging"
+"#,
+    )
+    .unwrap();
+    let file = temp.path().join("code.py");
+    fs::write(&file, "# TODO: one\n# TODO: two\n").unwrap();
+
+    let output = Command::new(antislop_bin())
+        .current_dir(temp.path())
+        .args(["--pattern-stats", "--format", "json", "--no-filename-check"])
+        .arg(&file)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let stats = json["pattern_stats"]
+        .as_array()
+        .expect("pattern_stats array");
+    let count = |regex: &str| {
+        stats
+            .iter()
+            .find(|s| s["regex"] == regex)
+            .map(|s| s["count"].clone())
+    };
+    assert_eq!(count("(?i)TODO:"), Some(serde_json::json!(2)));
+    assert_eq!(count("(?i)hopefully"), Some(serde_json::json!(0)));
+}
+
+#[test]
+fn test_hygiene_survey_json() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("Cargo.toml"),
+        "[package]\nname = \"x\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+
+    let output = Command::new(antislop_bin())
+        .args(["--hygiene-survey", "--json"])
+        .arg(temp.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let project_types = json["project_types"]
+        .as_array()
+        .expect("project_types array");
+    assert!(project_types
+        .iter()
+        .any(|p| p[0] == "Rust" && p[1] == "Cargo.toml"));
+    assert!(json["recommendations"].is_object());
+}
+
+#[test]
+fn test_hygiene_require_formatter_fails_without_formatter() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("Cargo.toml"),
+        "[package]\nname = \"x\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+
+    let run = |temp: &TempDir| {
+        Command::new(antislop_bin())
+            .args([
+                "--hygiene-survey",
+                "--json",
+                "--hygiene-require",
+                "formatter",
+            ])
+            .arg(temp.path())
+            .output()
+            .unwrap()
+    };
+
+    let output = run(&temp);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no formatter configured"));
+
+    fs::write(temp.path().join("rustfmt.toml"), "").unwrap();
+    assert!(run(&temp).status.success());
+}
+
+#[test]
+fn test_dump_patterns_attributes_sources() {
+    let temp = TempDir::new().unwrap();
+    let profile = temp.path().join("team.toml");
+    fs::write(
+        &profile,
+        r#"
+[metadata]
+name = "team"
+version = "1.0.0"
+
+[[patterns]]
+regex = "(?i)left as an exercise"
+severity = "medium"
+message = "Deferred work"
+category = "deferral"
+"#,
+    )
+    .unwrap();
+
+    let output = Command::new(antislop_bin())
+        .current_dir(temp.path())
+        .args(["--dump-patterns", "--json", "--profile"])
+        .arg(&profile)
+        .args(["--pattern", "(?i)hopefully:low:hedging:Hedge"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let patterns = json["patterns"].as_array().unwrap();
+    let source_of = |regex: &str| {
+        patterns
+            .iter()
+            .find(|p| p["regex"] == regex)
+            .map(|p| p["source"].as_str().unwrap().to_string())
+    };
+    assert_eq!(
+        source_of("(?i)left as an exercise").as_deref(),
+        Some("profile:team")
+    );
+    assert_eq!(source_of("(?i)hopefully").as_deref(), Some("inline"));
+    assert!(patterns.iter().any(|p| p["source"] == "core"));
+}
+
+#[test]
+fn test_scan_docs_flags_placeholder_prose() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("guide.md"),
+        "# Guide\n\nTODO: expand this section\n",
+    )
+    .unwrap();
+    fs::write(temp.path().join("main.py"), "x = 1\n").unwrap();
+
+    let scan = |extra: &[&str]| {
+        let output = Command::new(antislop_bin())
+            .current_dir(temp.path())
+            .args(["--format", "json", "--no-filename-check"])
+            .args(extra)
+            .arg(temp.path())
+            .output()
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["findings"].as_array().unwrap().clone()
+    };
+
+    assert!(scan(&[]).is_empty());
+    let findings = scan(&["--scan-docs"]);
+    assert!(!findings.is_empty());
+    assert!(findings
+        .iter()
+        .all(|f| f["line"] == 3 && f["file"].as_str().unwrap().ends_with("guide.md")));
+}
+
+#[test]
+fn test_huge_baseline_age_does_not_overflow() {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("app.py"), "# TODO: later\n").unwrap();
+
+    let run = |extra: &[&str]| {
+        Command::new(antislop_bin())
+            .current_dir(temp.path())
+            .args([
+                "--no-filename-check",
+                "--baseline",
+                "baseline.json",
+                "--baseline-age",
+                "99999999999999999",
+                "app.py",
+            ])
+            .args(extra)
+            .output()
+            .unwrap()
+    };
+    // The first run creates the baseline, the second checks its age
+    assert!(run(&["--refresh-baseline"]).status.success());
+    let output = run(&[]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(0), "{}", stderr);
+    assert!(!stderr.contains("days old"));
+}
+
+#[test]
+fn test_output_rejected_without_sqlite_format() {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("app.py"), "# TODO: later\n").unwrap();
+
+    let output = Command::new(antislop_bin())
+        .current_dir(temp.path())
+        .args(["--format", "json", "--output", "report.json", "app.py"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("--output is only supported"),
+        "got: {}",
+        stderr
+    );
+    assert!(!temp.path().join("report.json").exists());
+}
+
+#[test]
+fn test_category_help_lists_every_category() {
+    let output = Command::new(antislop_bin()).arg("--help").output().unwrap();
+    let help = String::from_utf8_lossy(&output.stdout);
+    let possible = help
+        .lines()
+        .filter(|line| line.contains("[possible values: placeholder"))
+        .collect::<Vec<_>>();
+    // --disable and --only
+    assert_eq!(possible.len(), 2, "{}", help);
+    for line in possible {
+        for category in antislop::PatternCategory::ALL {
+            assert!(
+                line.contains(category.as_str()),
+                "{} missing: {}",
+                category,
+                line
+            );
+        }
+    }
+}
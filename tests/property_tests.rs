@@ -19,6 +19,7 @@ fn get_test_scanner() -> Scanner {
         category: PatternCategory::Placeholder,
         ast_query: None,
         languages: vec![],
+        ..Default::default()
     }];
     Scanner::new(patterns).unwrap()
 }